@@ -0,0 +1,105 @@
+use bincode::config;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::distributions::Distribution;
+
+// `Vec<u8>` is decoded and encoded in one go, while `Vec<i8>` still goes through every item
+// one by one. Both encode to the same amount of bytes, so comparing the two shows the gain.
+
+fn random_bytes() -> Vec<u8> {
+    let mut rng = rand::thread_rng();
+    let dist = rand::distributions::Uniform::from(0..u8::MAX);
+    std::iter::from_fn(|| Some(dist.sample(&mut rng)))
+        .take(1_000_000)
+        .collect()
+}
+
+fn slice_decode_vec_u8(c: &mut Criterion) {
+    let config = config::standard();
+    let bytes = bincode::encode_to_vec(random_bytes(), config).unwrap();
+
+    c.bench_function("slice_decode_vec_u8", |b| {
+        b.iter(|| {
+            let _: (Vec<u8>, usize) =
+                black_box(bincode::decode_from_slice(black_box(&bytes), config).unwrap());
+        })
+    });
+}
+
+fn slice_decode_vec_i8(c: &mut Criterion) {
+    let config = config::standard();
+    let bytes = bincode::encode_to_vec(random_bytes(), config).unwrap();
+
+    c.bench_function("slice_decode_vec_i8", |b| {
+        b.iter(|| {
+            let _: (Vec<i8>, usize) =
+                black_box(bincode::decode_from_slice(black_box(&bytes), config).unwrap());
+        })
+    });
+}
+
+fn bufreader_decode_vec_u8(c: &mut Criterion) {
+    let config = config::standard();
+    let bytes = bincode::encode_to_vec(random_bytes(), config).unwrap();
+
+    c.bench_function("bufreader_decode_vec_u8", |b| {
+        b.iter(|| {
+            let _: Vec<u8> = black_box(
+                bincode::decode_from_reader(
+                    &mut std::io::BufReader::new(black_box(&bytes[..])),
+                    config,
+                )
+                .unwrap(),
+            );
+        })
+    });
+}
+
+fn bufreader_decode_vec_i8(c: &mut Criterion) {
+    let config = config::standard();
+    let bytes = bincode::encode_to_vec(random_bytes(), config).unwrap();
+
+    c.bench_function("bufreader_decode_vec_i8", |b| {
+        b.iter(|| {
+            let _: Vec<i8> = black_box(
+                bincode::decode_from_reader(
+                    &mut std::io::BufReader::new(black_box(&bytes[..])),
+                    config,
+                )
+                .unwrap(),
+            );
+        })
+    });
+}
+
+fn encode_vec_u8(c: &mut Criterion) {
+    let config = config::standard();
+    let input = random_bytes();
+
+    c.bench_function("encode_vec_u8", |b| {
+        b.iter(|| {
+            let _ = black_box(bincode::encode_to_vec(black_box(&input), config).unwrap());
+        })
+    });
+}
+
+fn encode_vec_i8(c: &mut Criterion) {
+    let config = config::standard();
+    let input: Vec<i8> = random_bytes().into_iter().map(|b| b as i8).collect();
+
+    c.bench_function("encode_vec_i8", |b| {
+        b.iter(|| {
+            let _ = black_box(bincode::encode_to_vec(black_box(&input), config).unwrap());
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    slice_decode_vec_u8,
+    slice_decode_vec_i8,
+    bufreader_decode_vec_u8,
+    bufreader_decode_vec_i8,
+    encode_vec_u8,
+    encode_vec_i8,
+);
+criterion_main!(benches);
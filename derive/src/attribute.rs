@@ -0,0 +1,338 @@
+use virtue::prelude::*;
+use virtue::utils::{parse_tagged_attribute, ParsedAttribute};
+
+const FIELD_ATTRIBUTES: &str = "Unknown attribute, expected one of: \"with_serde\", \"skip\", \"default\", \"with\", \"encode_with\", \"decode_with\", \"tag\", \"bound\", \"encode_bound\", \"decode_bound\", \"borrow_decode_bound\"";
+
+const CONTAINER_ATTRIBUTES: &str = "Unknown attribute, expected one of: \"crate\", \"tag_type\", \"default_trailing\", \"tagged\", \"version\", \"migrate_from\", \"into\", \"from\", \"try_from\", \"bound\", \"encode_bound\", \"decode_bound\", \"borrow_decode_bound\"";
+
+/// The where clause predicates set by `#[bincode(bound = "...")]` and the trait specific `encode_bound`, `decode_bound` and `borrow_decode_bound` attributes
+#[derive(Debug, Default, Clone)]
+pub struct Bounds {
+    pub bound: Option<String>,
+    pub encode: Option<String>,
+    pub decode: Option<String>,
+    pub borrow_decode: Option<String>,
+}
+
+impl Bounds {
+    /// The bound for the trait `trait_name`, e.g. `bincode::Encode`. Falls back to `bound` if there is no trait specific bound.
+    pub fn get(&self, trait_name: &str) -> Option<&str> {
+        let specific = match trait_name {
+            "bincode::Encode" => &self.encode,
+            "bincode::Decode" => &self.decode,
+            "bincode::BorrowDecode" => &self.borrow_decode,
+            _ => &None,
+        };
+        specific.as_deref().or(self.bound.as_deref())
+    }
+
+    /// Parse `key = value` if `key` is one of the bound attributes. Returns `false` if it is not.
+    fn parse(&mut self, key: &Ident, value: &Literal) -> Result<bool> {
+        let target = match key.to_string().as_str() {
+            "bound" => &mut self.bound,
+            "encode_bound" => &mut self.encode,
+            "decode_bound" => &mut self.decode,
+            "borrow_decode_bound" => &mut self.borrow_decode,
+            _ => return Ok(false),
+        };
+        let string = value.to_string();
+        match string.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            Some(contents) => *target = Some(contents.to_string()),
+            None => {
+                return Err(Error::custom_at(
+                    "Expected a string, e.g. \"T: bincode::Encode\"",
+                    value.span(),
+                ))
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// The `#[bincode(...)]` attributes that can be placed on a struct or enum
+#[derive(Debug, Default)]
+pub struct ContainerAttributes {
+    /// The path of the bincode crate set by `#[bincode(crate = "path")]`
+    pub crate_path: Option<String>,
+    /// The type of the variant index of an enum, set by `#[bincode(tag_type = "u8")]`
+    pub tag_type: Option<TagType>,
+    /// Set by `#[bincode(default_trailing)]`
+    pub default_trailing: bool,
+    /// Set by `#[bincode(tagged)]`
+    pub tagged: bool,
+    /// Set by `#[bincode(version = N)]` and `#[bincode(migrate_from = "T")]`
+    pub version: Option<Version>,
+    /// The type set by `#[bincode(into = "T")]`
+    pub into: Option<String>,
+    /// The conversion set by `#[bincode(from = "T")]` or `#[bincode(try_from = "T")]`
+    pub from: Option<Conversion>,
+    pub bounds: Bounds,
+}
+
+/// The integer type that the variant index of an enum is encoded as
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TagType {
+    U8,
+    U16,
+    #[default]
+    U32,
+    U64,
+}
+
+impl TagType {
+    fn parse(literal: &Literal) -> Result<Self> {
+        match parse_string_literal(literal)?.as_str() {
+            "u8" => Ok(Self::U8),
+            "u16" => Ok(Self::U16),
+            "u32" => Ok(Self::U32),
+            "u64" => Ok(Self::U64),
+            _ => Err(Error::custom_at(
+                "Expected one of: \"u8\", \"u16\", \"u32\", \"u64\"",
+                literal.span(),
+            )),
+        }
+    }
+
+    /// The name of the type, e.g. `u8`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+        }
+    }
+
+    /// The amount of variants that can be indexed by this type
+    pub fn max_variants(self) -> u128 {
+        match self {
+            Self::U8 => u8::MAX as u128 + 1,
+            Self::U16 => u16::MAX as u128 + 1,
+            Self::U32 => u32::MAX as u128 + 1,
+            Self::U64 => u64::MAX as u128 + 1,
+        }
+    }
+
+    /// An expression that converts the variant index `variant` to the `u32` of `DecodeError::UnexpectedVariant`. An index of a `u64` that does not fit is reported as `u32::MAX`.
+    pub fn to_u32(self, variant: &str) -> String {
+        match self {
+            Self::U8 | Self::U16 => format!("u32::from({})", variant),
+            Self::U32 => variant.to_string(),
+            Self::U64 => format!("u32::try_from({}).unwrap_or(u32::MAX)", variant),
+        }
+    }
+}
+
+/// The version of a type
+#[derive(Debug, Clone)]
+pub struct Version {
+    /// The number set by `#[bincode(version = N)]`
+    pub number: u32,
+    /// The type set by `#[bincode(migrate_from = "T")]`
+    pub migrate_from: Option<String>,
+}
+
+/// How a type is decoded from another type
+#[derive(Debug)]
+pub enum Conversion {
+    /// `#[bincode(from = "T")]`
+    From(String),
+    /// `#[bincode(try_from = "T")]`
+    TryFrom(String),
+}
+
+impl FromAttribute for ContainerAttributes {
+    fn parse(group: &Group) -> Result<Option<Self>> {
+        let attributes = match parse_tagged_attribute(group, "bincode")? {
+            Some(attributes) => attributes,
+            None => return Ok(None),
+        };
+        let mut result = Self::default();
+        let mut version = None;
+        let mut migrate_from = None;
+        for attribute in attributes {
+            match attribute {
+                ParsedAttribute::Property(key, value) if key.to_string() == "version" => {
+                    match value.to_string().parse() {
+                        Ok(number) if number > 0 => version = Some(number),
+                        _ => {
+                            return Err(Error::custom_at(
+                                "Expected a number between 1 and 4294967295, e.g. 1",
+                                value.span(),
+                            ))
+                        }
+                    }
+                }
+                ParsedAttribute::Property(key, value) if key.to_string() == "migrate_from" => {
+                    migrate_from = Some((parse_string_literal(&value)?, key));
+                }
+                ParsedAttribute::Property(key, value) if key.to_string() == "crate" => {
+                    let path = parse_string_literal(&value)?;
+                    if path.parse::<TokenStream>().is_err() {
+                        return Err(Error::custom_at(
+                            "Expected a path, e.g. \"my_crate::bincode\"",
+                            value.span(),
+                        ));
+                    }
+                    result.crate_path = Some(path);
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "default_trailing" => {
+                    result.default_trailing = true;
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "tagged" => {
+                    result.tagged = true;
+                }
+                ParsedAttribute::Property(key, value) if key.to_string() == "tag_type" => {
+                    result.tag_type = Some(TagType::parse(&value)?);
+                }
+                ParsedAttribute::Property(key, value) if key.to_string() == "into" => {
+                    result.into = Some(parse_string_literal(&value)?);
+                }
+                ParsedAttribute::Property(key, value)
+                    if key.to_string() == "from" || key.to_string() == "try_from" =>
+                {
+                    if result.from.is_some() {
+                        return Err(Error::custom_at(
+                            "\"from\" and \"try_from\" can not be combined",
+                            key.span(),
+                        ));
+                    }
+                    let ty = parse_string_literal(&value)?;
+                    result.from = Some(if key.to_string() == "from" {
+                        Conversion::From(ty)
+                    } else {
+                        Conversion::TryFrom(ty)
+                    });
+                }
+                ParsedAttribute::Property(key, value) if result.bounds.parse(&key, &value)? => {}
+                ParsedAttribute::Tag(i) => {
+                    return Err(Error::custom_at(CONTAINER_ATTRIBUTES, i.span()));
+                }
+                ParsedAttribute::Property(key, _) => {
+                    return Err(Error::custom_at(CONTAINER_ATTRIBUTES, key.span()));
+                }
+                _ => {}
+            }
+        }
+        result.version = match (version, migrate_from) {
+            (Some(number), migrate_from) => Some(Version {
+                number,
+                migrate_from: migrate_from.map(|(ty, _)| ty),
+            }),
+            (None, Some((_, key))) => {
+                return Err(Error::custom_at(
+                    "migrate_from requires a #[bincode(version = N)]",
+                    key.span(),
+                ))
+            }
+            (None, None) => None,
+        };
+        Ok(Some(result))
+    }
+}
+
+/// The `#[bincode(...)]` attributes that can be placed on a field
+#[derive(Debug, Default)]
+pub struct FieldAttributes {
+    pub with_serde: bool,
+    /// Set by `#[bincode(skip)]` and `#[bincode(default = "path")]`
+    pub skip: bool,
+    /// The path of the function set by `#[bincode(default = "path")]`
+    pub default: Option<String>,
+    /// The path of the function set by `#[bincode(encode_with = "path")]`, or `path::encode` for `#[bincode(with = "path")]`
+    pub encode_with: Option<String>,
+    /// The path of the function set by `#[bincode(decode_with = "path")]`, or `path::decode` for `#[bincode(with = "path")]`
+    pub decode_with: Option<String>,
+    /// The tag set by `#[bincode(tag = 1)]`, used by structs with `#[bincode(tagged)]`
+    pub tag: Option<u32>,
+    pub bounds: Bounds,
+}
+
+impl FieldAttributes {
+    /// The expression that a skipped field is decoded as
+    pub fn default_value(&self) -> String {
+        match &self.default {
+            Some(path) => format!("{}()", path),
+            None => String::from("core::default::Default::default()"),
+        }
+    }
+}
+
+impl FromAttribute for FieldAttributes {
+    fn parse(group: &Group) -> Result<Option<Self>> {
+        let attributes = match parse_tagged_attribute(group, "bincode")? {
+            Some(attributes) => attributes,
+            None => return Ok(None),
+        };
+        let mut result = Self::default();
+        for attribute in attributes {
+            match attribute {
+                ParsedAttribute::Tag(i) if i.to_string() == "with_serde" => {
+                    result.with_serde = true;
+                }
+                ParsedAttribute::Tag(i) if i.to_string() == "skip" => {
+                    result.skip = true;
+                }
+                ParsedAttribute::Property(key, value) if key.to_string() == "default" => {
+                    result.skip = true;
+                    result.default = Some(parse_string_literal(&value)?);
+                }
+                ParsedAttribute::Property(key, value) if key.to_string() == "with" => {
+                    let path = parse_string_literal(&value)?;
+                    set_once(&mut result.encode_with, format!("{}::encode", path), &key)?;
+                    set_once(&mut result.decode_with, format!("{}::decode", path), &key)?;
+                }
+                ParsedAttribute::Property(key, value) if key.to_string() == "encode_with" => {
+                    set_once(&mut result.encode_with, parse_string_literal(&value)?, &key)?;
+                }
+                ParsedAttribute::Property(key, value) if key.to_string() == "decode_with" => {
+                    set_once(&mut result.decode_with, parse_string_literal(&value)?, &key)?;
+                }
+                ParsedAttribute::Property(key, value) if key.to_string() == "tag" => {
+                    match value.to_string().parse() {
+                        Ok(tag) => result.tag = Some(tag),
+                        Err(_) => {
+                            return Err(Error::custom_at(
+                                "Expected a number between 0 and 4294967295, e.g. 1",
+                                value.span(),
+                            ))
+                        }
+                    }
+                }
+                ParsedAttribute::Property(key, value) if result.bounds.parse(&key, &value)? => {}
+                ParsedAttribute::Tag(i) => {
+                    return Err(Error::custom_at(FIELD_ATTRIBUTES, i.span()));
+                }
+                ParsedAttribute::Property(key, _) => {
+                    return Err(Error::custom_at(FIELD_ATTRIBUTES, key.span()));
+                }
+                _ => {}
+            }
+        }
+        Ok(Some(result))
+    }
+}
+
+/// Set `target` to `value`, or return an error if `target` was already set by another attribute
+fn set_once(target: &mut Option<String>, value: String, key: &Ident) -> Result {
+    if target.is_some() {
+        return Err(Error::custom_at(
+            "\"with\" can not be combined with \"encode_with\" or \"decode_with\"",
+            key.span(),
+        ));
+    }
+    *target = Some(value);
+    Ok(())
+}
+
+/// Return the contents of a string literal, e.g. `foo::bar` for `"foo::bar"`
+fn parse_string_literal(literal: &Literal) -> Result<String> {
+    let string = literal.to_string();
+    match string.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        Some(contents) if !contents.is_empty() => Ok(contents.to_string()),
+        _ => Err(Error::custom_at(
+            "Expected a non-empty string, e.g. \"path::to::function\"",
+            literal.span(),
+        )),
+    }
+}
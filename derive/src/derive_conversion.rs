@@ -0,0 +1,158 @@
+use crate::attribute::{Bounds, Conversion};
+use virtue::generate::{FnSelfArg, Generator, StreamBuilder};
+use virtue::parse::GenericConstraints;
+use virtue::prelude::*;
+
+/// Implements the traits of a struct or enum by converting it to or from another type, for the `into`, `from` and `try_from` container attributes
+pub struct DeriveConversion<'a> {
+    pub into: Option<&'a str>,
+    pub from: Option<&'a Conversion>,
+    pub bounds: Bounds,
+}
+
+impl DeriveConversion<'_> {
+    pub fn generate_encode(self, generator: &mut Generator) -> Result<()> {
+        let into = self.into.expect("into must be set");
+
+        generator
+            .impl_for("bincode::Encode")
+            .modify_generic_constraints(|_, where_constraints| {
+                push_explicit_bounds(where_constraints, "bincode::Encode", &self.bounds)
+            })?
+            .generate_fn("encode")
+            .with_generic_deps("E", ["bincode::enc::Encoder"])
+            .with_self_arg(FnSelfArg::RefSelf)
+            .with_arg("encoder", "&mut E")
+            .with_return_type("core::result::Result<(), bincode::error::EncodeError>")
+            .body(|fn_body| {
+                // let value: T = core::convert::Into::into(core::clone::Clone::clone(self));
+                // bincode::Encode::encode(&value, encoder)
+                fn_body.push_parsed(format!(
+                    "let value: {} = core::convert::Into::into(core::clone::Clone::clone(self));",
+                    into
+                ))?;
+                fn_body.push_parsed("bincode::Encode::encode(&value, encoder)")?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    pub fn generate_decode(self, generator: &mut Generator) -> Result<()> {
+        let from = self.from.expect("from must be set");
+
+        generator
+            .impl_for("bincode::Decode")
+            .modify_generic_constraints(|_, where_constraints| {
+                push_explicit_bounds(where_constraints, "bincode::Decode", &self.bounds)
+            })?
+            .generate_fn("decode")
+            .with_generic_deps("D", ["bincode::de::Decoder"])
+            .with_arg("decoder", "&mut D")
+            .with_return_type("core::result::Result<Self, bincode::error::DecodeError>")
+            .body(|fn_body| {
+                // let value: T = bincode::Decode::decode(decoder)?;
+                push_convert(fn_body, from, "bincode::Decode::decode(decoder)?")
+            })?;
+        Ok(())
+    }
+
+    pub fn generate_borrow_decode(self, generator: &mut Generator) -> Result<()> {
+        let from = self.from.expect("from must be set");
+
+        generator
+            .impl_for_with_lifetimes("bincode::BorrowDecode", ["__de"])
+            .new_lifetimes_depend_on_existing()
+            .modify_generic_constraints(|_, where_constraints| {
+                push_explicit_bounds(where_constraints, "bincode::BorrowDecode", &self.bounds)
+            })?
+            .generate_fn("borrow_decode")
+            .with_generic_deps("D", ["bincode::de::BorrowDecoder<'__de>"])
+            .with_arg("decoder", "&mut D")
+            .with_return_type("core::result::Result<Self, bincode::error::DecodeError>")
+            .body(|fn_body| {
+                // let value: T = bincode::BorrowDecode::borrow_decode(decoder)?;
+                push_convert(
+                    fn_body,
+                    from,
+                    "bincode::BorrowDecode::borrow_decode(decoder)?",
+                )
+            })?;
+        Ok(())
+    }
+
+    pub fn generate_max_size(self, generator: &mut Generator) -> Result<()> {
+        let into = self.into.expect("into must be set");
+
+        generator
+            .impl_for("bincode::MaxSize")
+            .modify_generic_constraints(|_, where_constraints| {
+                push_explicit_bounds(where_constraints, "bincode::MaxSize", &self.bounds)
+            })?
+            .generate_const("MAX_SIZE", "bincode::enc::MaxSizes")
+            .with_value(|value| {
+                // <T as bincode::MaxSize>::MAX_SIZE
+                value.push_parsed(format!("<{} as bincode::MaxSize>::MAX_SIZE", into))?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    pub fn generate_schema(self, generator: &mut Generator) -> Result<()> {
+        // The type is encoded as `into`, and decoded from `from`, so both should have the same schema
+        let ty = match (self.into, self.from) {
+            (Some(into), _) => into,
+            (None, Some(Conversion::From(ty) | Conversion::TryFrom(ty))) => ty.as_str(),
+            (None, None) => unreachable!("into or from must be set"),
+        };
+
+        generator
+            .impl_for("bincode::Schema")
+            .modify_generic_constraints(|_, where_constraints| {
+                push_explicit_bounds(where_constraints, "bincode::Schema", &self.bounds)
+            })?
+            .generate_fn("schema")
+            .with_arg("definitions", "&mut bincode::schema::Definitions")
+            .with_return_type("bincode::schema::Type")
+            .body(|fn_body| {
+                // <T as bincode::Schema>::schema(definitions)
+                fn_body.push_parsed(format!("<{} as bincode::Schema>::schema(definitions)", ty))?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+}
+
+/// Add the bounds for `trait_name`, if any. Unlike the other derives, no constraints are added for the generic parameters, because they depend on the type that is converted to or from.
+fn push_explicit_bounds(
+    where_constraints: &mut GenericConstraints,
+    trait_name: &str,
+    bounds: &Bounds,
+) -> Result {
+    match bounds.get(trait_name) {
+        Some(bound) if !bound.trim().is_empty() => where_constraints.push_parsed_constraint(bound),
+        _ => Ok(()),
+    }
+}
+
+/// Decode the type of `from` with `decode`, and convert it to `Self`
+fn push_convert(builder: &mut StreamBuilder, from: &Conversion, decode: &str) -> Result {
+    match from {
+        Conversion::From(ty) => {
+            // Ok(<Self as core::convert::From<T>>::from(value))
+            builder.push_parsed(format!("let value: {} = {};", ty, decode))?;
+            builder.push_parsed(format!(
+                "Ok(<Self as core::convert::From<{}>>::from(value))",
+                ty
+            ))?;
+        }
+        Conversion::TryFrom(ty) => {
+            // <Self as core::convert::TryFrom<T>>::try_from(value).map_err(..)
+            builder.push_parsed(format!("let value: {} = {};", ty, decode))?;
+            builder.push_parsed(format!(
+                "<Self as core::convert::TryFrom<{}>>::try_from(value).map_err(|e| bincode::error::DecodeError::conversion_failed(core::any::type_name::<Self>(), e))",
+                ty
+            ))?;
+        }
+    }
+    Ok(())
+}
@@ -1,4 +1,9 @@
-use super::FieldAttribute;
+use crate::attribute::{Bounds, FieldAttributes, TagType, Version};
+use crate::derive_struct::{
+    collect_bounds, field_context, push_define_schema, push_generic_constraints,
+    push_max_size_of_fields, push_schema_fields, schema_version,
+};
+use crate::derive_version;
 use virtue::generate::{FnSelfArg, Generator, StreamBuilder};
 use virtue::parse::{EnumVariant, Fields};
 use virtue::prelude::*;
@@ -7,10 +12,13 @@ const TUPLE_FIELD_PREFIX: &str = "field_";
 
 pub struct DeriveEnum {
     pub variants: Vec<EnumVariant>,
+    pub bounds: Bounds,
+    pub tag_type: TagType,
+    pub version: Option<Version>,
 }
 
 impl DeriveEnum {
-    fn iter_fields(&self) -> EnumVariantIterator {
+    fn iter_fields(&self) -> EnumVariantIterator<'_> {
         EnumVariantIterator {
             idx: 0,
             last_val: None,
@@ -18,20 +26,42 @@ impl DeriveEnum {
         }
     }
 
+    /// The bounds of the enum and of the fields of every variant
+    fn bounds(&self) -> Result<Vec<Bounds>> {
+        collect_bounds(
+            &self.bounds,
+            self.variants
+                .iter()
+                .filter_map(|variant| variant.fields.as_ref()),
+        )
+    }
+
+    /// Return an error if there are more variants than `tag_type` can index
+    fn check_tag_type(&self) -> Result {
+        if self.variants.len() as u128 > self.tag_type.max_variants() {
+            return Err(Error::custom(format!(
+                "This enum has too many variants for tag_type \"{}\"",
+                self.tag_type.as_str()
+            )));
+        }
+        Ok(())
+    }
+
     pub fn generate_encode(self, generator: &mut Generator) -> Result<()> {
+        self.check_tag_type()?;
+        let bounds = self.bounds()?;
         generator
-            .impl_for("bincode::Encode")?
+            .impl_for("bincode::Encode")
             .modify_generic_constraints(|generics, where_constraints| {
-                for g in generics.iter_generics() {
-                    where_constraints.push_constraint(g, "bincode::Encode").unwrap();
-                }
-            })
+                push_generic_constraints(generics, where_constraints, "bincode::Encode", &bounds)
+            })?
             .generate_fn("encode")
-            .with_generic("E", ["bincode::enc::Encoder"])
+            .with_generic_deps("E", ["bincode::enc::Encoder"])
             .with_self_arg(FnSelfArg::RefSelf)
             .with_arg("encoder", "&mut E")
             .with_return_type("core::result::Result<(), bincode::error::EncodeError>")
             .body(|fn_body| {
+                derive_version::push_encode_version(fn_body, &self.version)?;
                 fn_body.ident_str("match");
                 fn_body.ident_str("self");
                 fn_body.group(Delimiter::Brace, |match_body| {
@@ -46,17 +76,29 @@ impl DeriveEnum {
 
                         // if we have any fields, declare them here
                         // Self::Variant { a, b, c }
-                        if let Some(delimiter) = variant.fields.delimiter() {
-                            match_body.group(delimiter, |field_body| {
-                                for (idx, field_name) in
-                                    variant.fields.names().into_iter().enumerate()
-                                {
+                        if let Some(fields) = variant.fields.as_ref() {
+                            match_body.group(fields.delimiter(), |field_body| {
+                                for (idx, field_name) in fields.names().into_iter().enumerate() {
                                     if idx != 0 {
                                         field_body.punct(',');
                                     }
-                                    field_body.push(
-                                        field_name.to_token_tree_with_prefix(TUPLE_FIELD_PREFIX),
-                                    );
+                                    let attributes = field_name
+                                        .attributes()
+                                        .get_attribute::<FieldAttributes>()?
+                                        .unwrap_or_default();
+                                    if attributes.skip {
+                                        // Skipped fields are not bound, so they don't trigger unused variable warnings
+                                        // Self::Variant { a: _, b, c } or Self::Variant(_, b, c)
+                                        if fields.delimiter() == Delimiter::Brace {
+                                            field_body.ident(field_name.unwrap_ident().clone());
+                                            field_body.punct(':');
+                                        }
+                                        field_body.ident_str("_");
+                                    } else {
+                                        field_body.push(
+                                            field_name.to_token_tree_with_prefix(TUPLE_FIELD_PREFIX),
+                                        );
+                                    }
                                 }
                                 Ok(())
                             })?;
@@ -69,14 +111,17 @@ impl DeriveEnum {
                         // Body of this variant
                         // Note that the fields are available as locals because of the match destructuring above
                         // {
-                        //      encoder.encode_u32(n)?;
+                        //      <u32 as bincode::Encode>::encode(&(n), encoder)?;
                         //      bincode::Encode::encode(a, encoder)?;
                         //      bincode::Encode::encode(b, encoder)?;
                         //      bincode::Encode::encode(c, encoder)?;
                         // }
                         match_body.group(Delimiter::Brace, |body| {
                             // variant index
-                            body.push_parsed("<u32 as bincode::Encode>::encode")?;
+                            body.push_parsed(format!(
+                                "<{} as bincode::Encode>::encode",
+                                self.tag_type.as_str()
+                            ))?;
                             body.group(Delimiter::Parenthesis, |args| {
                                 args.punct('&');
                                 args.group(Delimiter::Parenthesis, |num| {
@@ -90,8 +135,21 @@ impl DeriveEnum {
                             body.punct('?');
                             body.punct(';');
                             // If we have any fields, encode them all one by one
-                            for field_name in variant.fields.names() {
-                                if field_name.attributes().has_attribute(FieldAttribute::WithSerde)? {
+                            for field_name in variant.fields.iter().flat_map(Fields::names) {
+                                let attributes = field_name
+                                    .attributes()
+                                    .get_attribute::<FieldAttributes>()?
+                                    .unwrap_or_default();
+                                if attributes.skip {
+                                    continue;
+                                }
+                                if let Some(encode_with) = &attributes.encode_with {
+                                    body.push_parsed(format!(
+                                        "{}({}, encoder)?;",
+                                        encode_with,
+                                        field_name.to_string_with_prefix(TUPLE_FIELD_PREFIX),
+                                    ))?;
+                                } else if attributes.with_serde {
                                     body.push_parsed(format!(
                                         "bincode::Encode::encode(&bincode::serde::Compat({}), encoder)?;",
                                         field_name.to_string_with_prefix(TUPLE_FIELD_PREFIX),
@@ -146,7 +204,7 @@ impl DeriveEnum {
             err_inner.group(Delimiter::Brace, |variant_inner| {
                 variant_inner.ident_str("found");
                 variant_inner.punct(':');
-                variant_inner.ident_str("variant");
+                variant_inner.push_parsed(self.tag_type.to_u32("variant"))?;
                 variant_inner.punct(',');
 
                 variant_inner.ident_str("type_name");
@@ -157,7 +215,7 @@ impl DeriveEnum {
                 variant_inner.ident_str("allowed");
                 variant_inner.punct(':');
 
-                if self.variants.iter().any(|i| i.has_fixed_value()) {
+                if self.variants.iter().any(|i| i.value.is_some()) {
                     // we have fixed values, implement AllowedEnumVariants::Allowed
                     variant_inner.push_parsed("bincode::error::AllowedEnumVariants::Allowed")?;
                     variant_inner.group(Delimiter::Parenthesis, |allowed_inner| {
@@ -190,28 +248,35 @@ impl DeriveEnum {
     pub fn generate_decode(&self, generator: &mut Generator) -> Result<()> {
         // Remember to keep this mostly in sync with generate_borrow_decode
 
+        self.check_tag_type()?;
         let enum_name = generator.target_name().to_string();
+        let bounds = self.bounds()?;
 
-        generator
-            .impl_for("bincode::Decode")?
-            .modify_generic_constraints(|generics, where_constraints| {
-                for g in generics.iter_generics() {
-                    where_constraints.push_constraint(g, "bincode::Decode").unwrap();
-                }
-            })
-            .generate_fn("decode")
-            .with_generic("D", ["bincode::de::Decoder"])
-            .with_arg("decoder", "&mut D")
-            .with_return_type("core::result::Result<Self, bincode::error::DecodeError>")
-            .body(|fn_builder| {
+        let (trait_name, fn_name) = derive_version::decode_trait(&self.version);
+
+        let mut impl_for = generator.impl_for(trait_name);
+        impl_for.modify_generic_constraints(|generics, where_constraints| {
+            push_generic_constraints(generics, where_constraints, "bincode::Decode", &bounds)
+        })?;
+        derive_version::with_version_arg(
+            impl_for
+                .generate_fn(fn_name)
+                .with_generic_deps("D", ["bincode::de::Decoder"])
+                .with_arg("decoder", "&mut D"),
+            &self.version,
+        )
+        .with_return_type("core::result::Result<Self, bincode::error::DecodeError>")
+        .body(|fn_builder| {
+                derive_version::push_check_version(fn_builder, &self.version)?;
                 if self.variants.is_empty() {
                     fn_builder.push_parsed("core::result::Result::Err(bincode::error::DecodeError::EmptyEnum { type_name: core::any::type_name::<Self>() })")?;
                 } else {
-                    fn_builder
-                        .push_parsed(
-                            "let variant_index = <u32 as bincode::Decode>::decode(decoder)?;",
-                        )?;
-                    fn_builder.push_parsed("match variant_index")?;
+                    fn_builder.push_parsed("bincode::de::Decoder::enter_depth(decoder)?;")?;
+                    fn_builder.push_parsed(format!(
+                        "let variant_index = <{} as bincode::Decode>::decode(decoder)?;",
+                        self.tag_type.as_str()
+                    ))?;
+                    fn_builder.push_parsed("let result = match variant_index")?;
                     fn_builder.group(Delimiter::Brace, |variant_case| {
                         for (mut variant_index, variant) in self.iter_fields() {
                             // idx => Ok(..)
@@ -232,20 +297,29 @@ impl DeriveEnum {
                                 variant_case_body.ident(variant.name.clone());
 
                                 variant_case_body.group(Delimiter::Brace, |variant_body| {
-                                    let is_tuple = matches!(variant.fields, Fields::Tuple(_));
-                                    for (idx, field) in variant.fields.names().into_iter().enumerate() {
+                                    let is_tuple = matches!(variant.fields, Some(Fields::Tuple(_)));
+                                    for (idx, field) in variant.fields.iter().flat_map(Fields::names).enumerate() {
                                         if is_tuple {
                                             variant_body.lit_usize(idx);
                                         } else {
-                                            variant_body.ident(field.unwrap_ident().clone());
+                                            variant_body.ident(field.unwrap_ident());
                                         }
                                         variant_body.punct(':');
-                                        if field.attributes().has_attribute(FieldAttribute::WithSerde)? {
+                                        let context = field_context(&format!("{}::{}.{}", enum_name, variant.name, field));
+                                        let attributes = field
+                                            .attributes()
+                                            .get_attribute::<FieldAttributes>()?
+                                            .unwrap_or_default();
+                                        if attributes.skip {
+                                            variant_body.push_parsed(format!("{},", attributes.default_value()))?;
+                                        } else if let Some(decode_with) = &attributes.decode_with {
+                                            variant_body.push_parsed(format!("{}(decoder){}?,", decode_with, context))?;
+                                        } else if attributes.with_serde {
                                             variant_body
-                                                .push_parsed("<bincode::serde::Compat<_> as bincode::Decode>::decode(decoder)?.0,")?;
+                                                .push_parsed(format!("<bincode::serde::Compat<_> as bincode::Decode>::decode(decoder){}?.0,", context))?;
                                         } else {
                                             variant_body
-                                                .push_parsed("bincode::Decode::decode(decoder)?,")?;
+                                                .push_parsed(format!("bincode::Decode::decode(decoder){}?,", context))?;
                                         }
                                     }
                                     Ok(())
@@ -258,6 +332,9 @@ impl DeriveEnum {
                         // invalid idx
                         self.invalid_variant_case(&enum_name, variant_case)
                     })?;
+                    fn_builder.punct(';');
+                    fn_builder.push_parsed("bincode::de::Decoder::leave_depth(decoder);")?;
+                    fn_builder.ident_str("result");
                 }
                 Ok(())
             })?;
@@ -267,25 +344,29 @@ impl DeriveEnum {
     pub fn generate_borrow_decode(self, generator: &mut Generator) -> Result<()> {
         // Remember to keep this mostly in sync with generate_decode
 
+        self.check_tag_type()?;
         let enum_name = generator.target_name().to_string();
+        let bounds = self.bounds()?;
 
-        generator.impl_for_with_lifetimes("bincode::BorrowDecode", &["__de"])?
+        generator.impl_for_with_lifetimes("bincode::BorrowDecode", ["__de"])
+            .new_lifetimes_depend_on_existing()
             .modify_generic_constraints(|generics, where_constraints| {
-                for g in generics.iter_generics() {
-                    where_constraints.push_constraint(g, "bincode::enc::BorrowDecode").unwrap();
-                }
-            })
+                push_generic_constraints(generics, where_constraints, "bincode::BorrowDecode", &bounds)
+            })?
             .generate_fn("borrow_decode")
-            .with_generic("D", ["bincode::de::BorrowDecoder<'__de>"])
+            .with_generic_deps("D", ["bincode::de::BorrowDecoder<'__de>"])
             .with_arg("decoder", "&mut D")
             .with_return_type("core::result::Result<Self, bincode::error::DecodeError>")
             .body(|fn_builder| {
                 if self.variants.is_empty() {
                     fn_builder.push_parsed("core::result::Result::Err(bincode::error::DecodeError::EmptyEnum { type_name: core::any::type_name::<Self>() })")?;
                 } else {
-                    fn_builder
-                        .push_parsed("let variant_index = <u32 as bincode::Decode>::decode(decoder)?;")?;
-                    fn_builder.push_parsed("match variant_index")?;
+                    fn_builder.push_parsed("bincode::de::Decoder::enter_depth(decoder)?;")?;
+                    fn_builder.push_parsed(format!(
+                        "let variant_index = <{} as bincode::Decode>::decode(decoder)?;",
+                        self.tag_type.as_str()
+                    ))?;
+                    fn_builder.push_parsed("let result = match variant_index")?;
                     fn_builder.group(Delimiter::Brace, |variant_case| {
                         for (mut variant_index, variant) in self.iter_fields() {
                             // idx => Ok(..)
@@ -306,19 +387,28 @@ impl DeriveEnum {
                                 variant_case_body.ident(variant.name.clone());
 
                                 variant_case_body.group(Delimiter::Brace, |variant_body| {
-                                    let is_tuple = matches!(variant.fields, Fields::Tuple(_));
-                                    for (idx, field) in variant.fields.names().into_iter().enumerate() {
+                                    let is_tuple = matches!(variant.fields, Some(Fields::Tuple(_)));
+                                    for (idx, field) in variant.fields.iter().flat_map(Fields::names).enumerate() {
                                         if is_tuple {
                                             variant_body.lit_usize(idx);
                                         } else {
-                                            variant_body.ident(field.unwrap_ident().clone());
+                                            variant_body.ident(field.unwrap_ident());
                                         }
                                         variant_body.punct(':');
-                                        if field.attributes().has_attribute(FieldAttribute::WithSerde)? {
+                                        let context = field_context(&format!("{}::{}.{}", enum_name, variant.name, field));
+                                        let attributes = field
+                                            .attributes()
+                                            .get_attribute::<FieldAttributes>()?
+                                            .unwrap_or_default();
+                                        if attributes.skip {
+                                            variant_body.push_parsed(format!("{},", attributes.default_value()))?;
+                                        } else if let Some(decode_with) = &attributes.decode_with {
+                                            variant_body.push_parsed(format!("{}(decoder){}?,", decode_with, context))?;
+                                        } else if attributes.with_serde {
                                             variant_body
-                                                .push_parsed("<bincode::serde::BorrowCompat<_> as bincode::BorrowDecode>::borrow_decode(decoder)?.0,")?;
+                                                .push_parsed(format!("<bincode::serde::BorrowCompat<_> as bincode::BorrowDecode>::borrow_decode(decoder){}?.0,", context))?;
                                         } else {
-                                            variant_body.push_parsed("bincode::BorrowDecode::borrow_decode(decoder)?,")?;
+                                            variant_body.push_parsed(format!("bincode::BorrowDecode::borrow_decode(decoder){}?,", context))?;
                                         }
                                     }
                                     Ok(())
@@ -331,9 +421,120 @@ impl DeriveEnum {
                         // invalid idx
                         self.invalid_variant_case(&enum_name, variant_case)
                     })?;
+                    fn_builder.punct(';');
+                    fn_builder.push_parsed("bincode::de::Decoder::leave_depth(decoder);")?;
+                    fn_builder.ident_str("result");
+                }
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    pub fn generate_max_size(self, generator: &mut Generator) -> Result<()> {
+        self.check_tag_type()?;
+        let bounds = self.bounds()?;
+        generator
+            .impl_for("bincode::MaxSize")
+            .modify_generic_constraints(|generics, where_constraints| {
+                push_generic_constraints(generics, where_constraints, "bincode::MaxSize", &bounds)
+            })?
+            .generate_const("MAX_SIZE", "bincode::enc::MaxSizes")
+            .with_value(|value| {
+                if self.variants.is_empty() {
+                    // An empty enum can never be encoded
+                    value.push_parsed("bincode::enc::MaxSizes::ZERO")?;
+                    return Ok(());
                 }
+                // <u32 as bincode::MaxSize>::MAX_SIZE.add(
+                //     bincode::enc::MaxSizes::ZERO
+                //         .max(bincode::enc::MaxSizes::ZERO.add(<A as bincode::MaxSize>::MAX_SIZE))
+                //         .max(bincode::enc::MaxSizes::ZERO.add(<B as bincode::MaxSize>::MAX_SIZE))
+                //         ...
+                // )
+                value.push_parsed(format!(
+                    "<{} as bincode::MaxSize>::MAX_SIZE.add",
+                    self.tag_type.as_str()
+                ))?;
+                value.group(Delimiter::Parenthesis, |variants| {
+                    variants.push_parsed("bincode::enc::MaxSizes::ZERO")?;
+                    for variant in &self.variants {
+                        variants.push_parsed(".max")?;
+                        variants.group(Delimiter::Parenthesis, |variant_size| {
+                            variant_size.push_parsed("bincode::enc::MaxSizes::ZERO")?;
+                            if let Some(fields) = &variant.fields {
+                                push_max_size_of_fields(variant_size, fields)?;
+                            }
+                            Ok(())
+                        })?;
+                    }
+                    Ok(())
+                })?;
+                derive_version::push_max_size_version(value, &self.version)?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    pub fn generate_schema(self, generator: &mut Generator) -> Result<()> {
+        self.check_tag_type()?;
+        let bounds = self.bounds()?;
+
+        // bincode::schema::Variant::new("A", 0, core::convert::From::from([..])),
+        let mut variants = StreamBuilder::new();
+        let mut uses_definitions = false;
+        for (variant_index, variant) in self.iter_fields() {
+            variants.push_parsed("bincode::schema::Variant::new")?;
+            variants.group(Delimiter::Parenthesis, |args| {
+                args.lit_str(variant.name.to_string());
+                args.punct(',');
+                args.extend(variant_index);
+                args.punct(',');
+                uses_definitions |= push_schema_fields(args, variant.fields.as_ref())?;
                 Ok(())
             })?;
+            variants.punct(',');
+        }
+
+        generator
+            .impl_for("bincode::Schema")
+            .modify_generic_constraints(|generics, where_constraints| {
+                push_generic_constraints(generics, where_constraints, "bincode::Schema", &bounds)
+            })?
+            .generate_fn("schema")
+            .with_arg("definitions", "&mut bincode::schema::Definitions")
+            .with_return_type("bincode::schema::Type")
+            .body(|fn_body| {
+                // definitions.define(core::any::type_name::<Self>(), |definitions| {
+                //     bincode::schema::Definition::Enum(bincode::schema::EnumSchema {
+                //         version: None,
+                //         tag_type: bincode::schema::Type::U32,
+                //         variants: core::convert::From::from([..]),
+                //     })
+                // })
+                push_define_schema(fn_body, uses_definitions, |definition| {
+                    definition.push_parsed("bincode::schema::Definition::Enum")?;
+                    definition.group(Delimiter::Parenthesis, |schema| {
+                        schema.push_parsed("bincode::schema::EnumSchema")?;
+                        schema.group(Delimiter::Brace, |schema_body| {
+                            schema_body.push_parsed(format!(
+                                "version: {}, tag_type: bincode::schema::Type::{}, variants: core::convert::From::from",
+                                schema_version(&self.version),
+                                self.tag_type.as_str().to_uppercase()
+                            ))?;
+                            schema_body.group(Delimiter::Parenthesis, |from| {
+                                from.group(Delimiter::Bracket, |list| {
+                                    list.append(variants);
+                                    Ok(())
+                                })?;
+                                Ok(())
+                            })?;
+                            Ok(())
+                        })?;
+                        Ok(())
+                    })?;
+                    Ok(())
+                })
+            })?;
         Ok(())
     }
 }
@@ -341,7 +542,7 @@ impl DeriveEnum {
 struct EnumVariantIterator<'a> {
     variants: &'a [EnumVariant],
     idx: usize,
-    last_val: Option<(Literal, u32)>,
+    last_val: Option<(Literal, u64)>,
 }
 
 impl<'a> Iterator for EnumVariantIterator<'a> {
@@ -352,7 +553,7 @@ impl<'a> Iterator for EnumVariantIterator<'a> {
         let variant = self.variants.get(self.idx)?;
         self.idx += 1;
 
-        let tokens = if let Fields::Integer(lit) = &variant.fields {
+        let tokens = if let Some(lit) = &variant.value {
             let tree = TokenTree::Literal(lit.clone());
             self.last_val = Some((lit.clone(), 0));
             vec![tree]
@@ -361,10 +562,10 @@ impl<'a> Iterator for EnumVariantIterator<'a> {
             vec![
                 TokenTree::Literal(lit.clone()),
                 TokenTree::Punct(Punct::new('+', Spacing::Alone)),
-                TokenTree::Literal(Literal::u32_suffixed(*add)),
+                TokenTree::Literal(Literal::u64_unsuffixed(*add)),
             ]
         } else {
-            vec![TokenTree::Literal(Literal::u32_suffixed(idx as u32))]
+            vec![TokenTree::Literal(Literal::usize_unsuffixed(idx))]
         };
 
         Some((tokens, variant))
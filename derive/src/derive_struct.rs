@@ -1,47 +1,70 @@
-use super::FieldAttribute;
+use crate::attribute::{Bounds, FieldAttributes, Version};
+use crate::derive_version;
 use virtue::generate::Generator;
-use virtue::parse::Fields;
+use virtue::parse::{Fields, GenericConstraints, Generics, UnnamedField};
 use virtue::prelude::*;
 
 pub struct DeriveStruct {
-    pub fields: Fields,
+    pub fields: Option<Fields>,
+    pub bounds: Bounds,
+    pub default_trailing: bool,
+    pub version: Option<Version>,
 }
 
 impl DeriveStruct {
     pub fn generate_encode(self, generator: &mut Generator) -> Result<()> {
-        let DeriveStruct { fields } = self;
+        let DeriveStruct {
+            fields,
+            bounds,
+            default_trailing,
+            version,
+        } = self;
+        let bounds = collect_bounds(&bounds, fields.iter())?;
 
         generator
-            .impl_for("bincode::Encode")?
+            .impl_for("bincode::Encode")
             .modify_generic_constraints(|generics, where_constraints| {
-                for g in generics.iter_generics() {
-                    where_constraints
-                        .push_constraint(g, "bincode::Encode")
-                        .unwrap();
-                }
-            })
+                push_generic_constraints(generics, where_constraints, "bincode::Encode", &bounds)
+            })?
             .generate_fn("encode")
-            .with_generic("E", ["bincode::enc::Encoder"])
+            .with_generic_deps("E", ["bincode::enc::Encoder"])
             .with_self_arg(virtue::generate::FnSelfArg::RefSelf)
             .with_arg("encoder", "&mut E")
             .with_return_type("core::result::Result<(), bincode::error::EncodeError>")
             .body(|fn_body| {
-                for field in fields.names() {
-                    if field
-                        .attributes()
-                        .has_attribute(FieldAttribute::WithSerde)?
-                    {
-                        fn_body.push_parsed(format!(
-                            "bincode::Encode::encode(&bincode::serde::Compat(&self.{}), encoder)?;",
-                            field
-                        ))?;
-                    } else {
-                        fn_body.push_parsed(format!(
-                            "bincode::Encode::encode(&self.{}, encoder)?;",
-                            field
-                        ))?;
-                    }
+                derive_version::push_encode_version(fn_body, &version)?;
+                if default_trailing {
+                    // let mut size_encoder = bincode::enc::EncoderImpl::<_, E::C>::new(..);
+                    // {
+                    //     let encoder = &mut size_encoder;
+                    //     bincode::Encode::encode(&self.a, encoder)?;
+                    //     ...
+                    // }
+                    // let record_len = size_encoder.into_writer().bytes_written;
+                    // <u64 as bincode::Encode>::encode(&(record_len as u64), encoder)?;
+                    // if bincode::enc::write::Writer::count_only(bincode::enc::Encoder::writer(encoder), record_len) {
+                    //     return Ok(());
+                    // }
+                    fn_body.push_parsed(
+                        "let mut size_encoder = bincode::enc::EncoderImpl::<_, <E as bincode::enc::Encoder>::C>::new(bincode::enc::write::SizeWriter::default(), *bincode::enc::Encoder::config(encoder));",
+                    )?;
+                    fn_body.group(Delimiter::Brace, |size_body| {
+                        size_body.push_parsed("let encoder = &mut size_encoder;")?;
+                        push_encode_fields(size_body, &fields)
+                    })?;
+                    fn_body.push_parsed(
+                        "let record_len = size_encoder.into_writer().bytes_written;",
+                    )?;
+                    fn_body.push_parsed(
+                        "<u64 as bincode::Encode>::encode(&(record_len as u64), encoder)?;",
+                    )?;
+                    // When only the size of this struct is needed, the fields don't have to be encoded a second time.
+                    // Without this, nested structs would be encoded 2^depth times.
+                    fn_body.push_parsed(
+                        "if bincode::enc::write::Writer::count_only(bincode::enc::Encoder::writer(encoder), record_len) { return Ok(()); }",
+                    )?;
                 }
+                push_encode_fields(fn_body, &fields)?;
                 fn_body.push_parsed("Ok(())")?;
                 Ok(())
             })?;
@@ -50,97 +73,506 @@ impl DeriveStruct {
 
     pub fn generate_decode(self, generator: &mut Generator) -> Result<()> {
         // Remember to keep this mostly in sync with generate_borrow_decode
-        let DeriveStruct { fields } = self;
+        let DeriveStruct {
+            fields,
+            bounds,
+            default_trailing,
+            version,
+        } = self;
+        let bounds = collect_bounds(&bounds, fields.iter())?;
+        let struct_name = generator.target_name().to_string();
 
-        generator
-            .impl_for("bincode::Decode")?
-            .modify_generic_constraints(|generics, where_constraints| {
-                for g in generics.iter_generics() {
-                    where_constraints.push_constraint(g, "bincode::Decode").unwrap();
-                }
-            })
-            .generate_fn("decode")
-            .with_generic("D", ["bincode::de::Decoder"])
-            .with_arg("decoder", "&mut D")
-            .with_return_type("core::result::Result<Self, bincode::error::DecodeError>")
-            .body(|fn_body| {
-                // Ok(Self {
-                fn_body.ident_str("Ok");
-                fn_body.group(Delimiter::Parenthesis, |ok_group| {
-                    ok_group.ident_str("Self");
-                    ok_group.group(Delimiter::Brace, |struct_body| {
-                        // Fields
-                        // {
-                        //      a: bincode::Decode::decode(decoder)?,
-                        //      b: bincode::Decode::decode(decoder)?,
-                        //      ...
-                        // }
-                        for field in fields.names() {
-                            if field.attributes().has_attribute(FieldAttribute::WithSerde)? {
-                                struct_body
-                                    .push_parsed(format!(
-                                        "{}: (<bincode::serde::Compat<_> as bincode::Decode>::decode(decoder)?).0,",
-                                        field
-                                    ))?;
-                            } else {
-                                struct_body
-                                    .push_parsed(format!(
-                                        "{}: bincode::Decode::decode(decoder)?,",
-                                        field
-                                    ))?;
-                            }
+        let (trait_name, fn_name) = derive_version::decode_trait(&version);
+
+        let mut impl_for = generator.impl_for(trait_name);
+        impl_for.modify_generic_constraints(|generics, where_constraints| {
+            push_generic_constraints(generics, where_constraints, "bincode::Decode", &bounds)
+        })?;
+        derive_version::with_version_arg(
+            impl_for
+                .generate_fn(fn_name)
+                .with_generic_deps("D", ["bincode::de::Decoder"])
+                .with_arg("decoder", "&mut D"),
+            &version,
+        )
+        .with_return_type("core::result::Result<Self, bincode::error::DecodeError>")
+        .body(|fn_body| {
+            derive_version::push_check_version(fn_body, &version)?;
+            // bincode::de::Decoder::enter_depth(decoder)?;
+            // let result = Ok(Self {
+            fn_body.push_parsed("bincode::de::Decoder::enter_depth(decoder)?;")?;
+            if default_trailing {
+                fn_body.push_parsed("let record = bincode::de::Record::begin(decoder)?;")?;
+            }
+            fn_body.push_parsed("let result = ")?;
+            fn_body.ident_str("Ok");
+            fn_body.group(Delimiter::Parenthesis, |ok_group| {
+                ok_group.ident_str("Self");
+                ok_group.group(Delimiter::Brace, |struct_body| {
+                    // Fields
+                    // {
+                    //      a: bincode::Decode::decode(decoder).map_err(..)?,
+                    //      b: bincode::Decode::decode(decoder).map_err(..)?,
+                    //      ...
+                    // }
+                    for field in fields.iter().flat_map(Fields::names) {
+                        let attributes = field
+                            .attributes()
+                            .get_attribute::<FieldAttributes>()?
+                            .unwrap_or_default();
+                        if attributes.skip {
+                            struct_body.push_parsed(format!(
+                                "{}: {},",
+                                field,
+                                attributes.default_value()
+                            ))?;
+                            continue;
                         }
-                        Ok(())
-                    })?;
+                        let context = field_context(&format!("{}.{}", struct_name, field));
+                        let value = decode_field(&attributes, &context, false);
+                        if default_trailing {
+                            // a: if record.is_finished(&*decoder) { Default::default() } else { .. },
+                            struct_body.push_parsed(format!(
+                                "{}: if record.is_finished(&*decoder) {{ {} }} else {{ {} }},",
+                                field,
+                                attributes.default_value(),
+                                value
+                            ))?;
+                        } else {
+                            struct_body.push_parsed(format!("{}: {},", field, value))?;
+                        }
+                    }
                     Ok(())
                 })?;
                 Ok(())
             })?;
+            // });
+            // record.finish(decoder)?;
+            // bincode::de::Decoder::leave_depth(decoder);
+            // result
+            fn_body.punct(';');
+            if default_trailing {
+                fn_body.push_parsed("record.finish(decoder)?;")?;
+            }
+            fn_body.push_parsed("bincode::de::Decoder::leave_depth(decoder);")?;
+            fn_body.ident_str("result");
+            Ok(())
+        })?;
         Ok(())
     }
 
     pub fn generate_borrow_decode(self, generator: &mut Generator) -> Result<()> {
         // Remember to keep this mostly in sync with generate_decode
-        let DeriveStruct { fields } = self;
+        let DeriveStruct {
+            fields,
+            bounds,
+            default_trailing,
+            version: _,
+        } = self;
+        let bounds = collect_bounds(&bounds, fields.iter())?;
+        let struct_name = generator.target_name().to_string();
 
         generator
-            .impl_for_with_lifetimes("bincode::BorrowDecode", &["__de"])?
+            .impl_for_with_lifetimes("bincode::BorrowDecode", ["__de"])
+            .new_lifetimes_depend_on_existing()
             .modify_generic_constraints(|generics, where_constraints| {
-                for g in generics.iter_generics() {
-                    where_constraints.push_constraint(g, "bincode::BorrowDecode").unwrap();
-                }
-            })
+                push_generic_constraints(
+                    generics,
+                    where_constraints,
+                    "bincode::BorrowDecode",
+                    &bounds,
+                )
+            })?
             .generate_fn("borrow_decode")
-            .with_generic("D", ["bincode::de::BorrowDecoder<'__de>"])
+            .with_generic_deps("D", ["bincode::de::BorrowDecoder<'__de>"])
             .with_arg("decoder", "&mut D")
             .with_return_type("core::result::Result<Self, bincode::error::DecodeError>")
             .body(|fn_body| {
-                // Ok(Self {
+                // bincode::de::Decoder::enter_depth(decoder)?;
+                // let result = Ok(Self {
+                fn_body.push_parsed("bincode::de::Decoder::enter_depth(decoder)?;")?;
+                if default_trailing {
+                    fn_body.push_parsed("let record = bincode::de::Record::begin(decoder)?;")?;
+                }
+                fn_body.push_parsed("let result = ")?;
                 fn_body.ident_str("Ok");
                 fn_body.group(Delimiter::Parenthesis, |ok_group| {
                     ok_group.ident_str("Self");
                     ok_group.group(Delimiter::Brace, |struct_body| {
-                        for field in fields.names() {
-                            if field.attributes().has_attribute(FieldAttribute::WithSerde)? {
-                                struct_body
-                                    .push_parsed(format!(
-                                        "{}: (<bincode::serde::BorrowCompat<_> as bincode::BorrowDecode>::borrow_decode(decoder)?).0,",
-                                        field
-                                    ))?;
+                        for field in fields.iter().flat_map(Fields::names) {
+                            let attributes = field
+                                .attributes()
+                                .get_attribute::<FieldAttributes>()?
+                                .unwrap_or_default();
+                            if attributes.skip {
+                                struct_body.push_parsed(format!(
+                                    "{}: {},",
+                                    field,
+                                    attributes.default_value()
+                                ))?;
+                                continue;
+                            }
+                            let context = field_context(&format!("{}.{}", struct_name, field));
+                            let value = decode_field(&attributes, &context, true);
+                            if default_trailing {
+                                // a: if record.is_finished(&*decoder) { Default::default() } else { .. },
+                                struct_body.push_parsed(format!(
+                                    "{}: if record.is_finished(&*decoder) {{ {} }} else {{ {} }},",
+                                    field,
+                                    attributes.default_value(),
+                                    value
+                                ))?;
                             } else {
-                                struct_body
-                                    .push_parsed(format!(
-                                        "{}: bincode::BorrowDecode::borrow_decode(decoder)?,",
-                                        field
-                                    ))?;
+                                struct_body.push_parsed(format!("{}: {},", field, value))?;
                             }
                         }
                         Ok(())
                     })?;
                     Ok(())
                 })?;
+                // });
+                // record.finish(decoder)?;
+                // bincode::de::Decoder::leave_depth(decoder);
+                // result
+                fn_body.punct(';');
+                if default_trailing {
+                    fn_body.push_parsed("record.finish(decoder)?;")?;
+                }
+                fn_body.push_parsed("bincode::de::Decoder::leave_depth(decoder);")?;
+                fn_body.ident_str("result");
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    pub fn generate_max_size(self, generator: &mut Generator) -> Result<()> {
+        let DeriveStruct {
+            fields,
+            bounds,
+            default_trailing,
+            version,
+        } = self;
+        let bounds = collect_bounds(&bounds, fields.iter())?;
+
+        generator
+            .impl_for("bincode::MaxSize")
+            .modify_generic_constraints(|generics, where_constraints| {
+                push_generic_constraints(generics, where_constraints, "bincode::MaxSize", &bounds)
+            })?
+            .generate_const("MAX_SIZE", "bincode::enc::MaxSizes")
+            .with_value(|value| {
+                // bincode::enc::MaxSizes::ZERO
+                //     .add(<A as bincode::MaxSize>::MAX_SIZE)
+                //     .add(<B as bincode::MaxSize>::MAX_SIZE)
+                //     ...
+                value.push_parsed("bincode::enc::MaxSizes::ZERO")?;
+                derive_version::push_max_size_version(value, &version)?;
+                if default_trailing {
+                    value.push_parsed(".add(<u64 as bincode::MaxSize>::MAX_SIZE)")?;
+                }
+                if let Some(fields) = &fields {
+                    push_max_size_of_fields(value, fields)?;
+                }
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    pub fn generate_schema(self, generator: &mut Generator) -> Result<()> {
+        let DeriveStruct {
+            fields,
+            bounds,
+            default_trailing,
+            version,
+        } = self;
+        let bounds = collect_bounds(&bounds, fields.iter())?;
+        let layout = if default_trailing {
+            "DefaultTrailing"
+        } else {
+            "Sequential"
+        };
+
+        let mut schema_fields = StreamBuilder::new();
+        let uses_definitions = push_schema_fields(&mut schema_fields, fields.as_ref())?;
+
+        generator
+            .impl_for("bincode::Schema")
+            .modify_generic_constraints(|generics, where_constraints| {
+                push_generic_constraints(generics, where_constraints, "bincode::Schema", &bounds)
+            })?
+            .generate_fn("schema")
+            .with_arg("definitions", "&mut bincode::schema::Definitions")
+            .with_return_type("bincode::schema::Type")
+            .body(|fn_body| {
+                // definitions.define(core::any::type_name::<Self>(), |definitions| {
+                //     bincode::schema::Definition::Struct(bincode::schema::StructSchema {
+                //         version: None,
+                //         layout: bincode::schema::StructLayout::Sequential,
+                //         fields: core::convert::From::from([..]),
+                //     })
+                // })
+                push_define_schema(fn_body, uses_definitions, |definition| {
+                    definition.push_parsed("bincode::schema::Definition::Struct")?;
+                    definition.group(Delimiter::Parenthesis, |schema| {
+                        schema.push_parsed("bincode::schema::StructSchema")?;
+                        schema.group(Delimiter::Brace, |schema_body| {
+                            schema_body.push_parsed(format!(
+                                "version: {}, layout: bincode::schema::StructLayout::{}, fields: ",
+                                schema_version(&version),
+                                layout
+                            ))?;
+                            schema_body.append(schema_fields);
+                            Ok(())
+                        })?;
+                        Ok(())
+                    })?;
                     Ok(())
+                })
             })?;
         Ok(())
     }
 }
+
+/// Encode every field in `fields` that is not skipped
+fn push_encode_fields(fn_body: &mut StreamBuilder, fields: &Option<Fields>) -> Result {
+    for field in fields.iter().flat_map(Fields::names) {
+        let attributes = field
+            .attributes()
+            .get_attribute::<FieldAttributes>()?
+            .unwrap_or_default();
+        if attributes.skip {
+            continue;
+        }
+        fn_body.push_parsed(encode_field(&attributes, &format!("&self.{}", field)))?;
+    }
+    Ok(())
+}
+
+/// The statement that encodes `value`, e.g. `&self.a`, of a field with `attributes`
+pub(crate) fn encode_field(attributes: &FieldAttributes, value: &str) -> String {
+    if let Some(encode_with) = &attributes.encode_with {
+        format!("{}({}, encoder)?;", encode_with, value)
+    } else if attributes.with_serde {
+        format!(
+            "bincode::Encode::encode(&bincode::serde::Compat({}), encoder)?;",
+            value
+        )
+    } else {
+        format!("bincode::Encode::encode({}, encoder)?;", value)
+    }
+}
+
+/// The expression that decodes a field with `attributes`, where `context` is the `.map_err(..)` of [field_context]
+pub(crate) fn decode_field(attributes: &FieldAttributes, context: &str, borrow: bool) -> String {
+    if let Some(decode_with) = &attributes.decode_with {
+        format!("{}(decoder){}?", decode_with, context)
+    } else if attributes.with_serde && borrow {
+        format!("(<bincode::serde::BorrowCompat<_> as bincode::BorrowDecode>::borrow_decode(decoder){}?).0", context)
+    } else if attributes.with_serde {
+        format!(
+            "(<bincode::serde::Compat<_> as bincode::Decode>::decode(decoder){}?).0",
+            context
+        )
+    } else if borrow {
+        format!("bincode::BorrowDecode::borrow_decode(decoder){}?", context)
+    } else {
+        format!("bincode::Decode::decode(decoder){}?", context)
+    }
+}
+
+/// The bounds of the container, followed by the bounds of every field in `fields`
+pub(crate) fn collect_bounds<'a>(
+    container: &Bounds,
+    fields: impl Iterator<Item = &'a Fields>,
+) -> Result<Vec<Bounds>> {
+    let mut result = vec![container.clone()];
+    for field in fields.flat_map(Fields::names) {
+        if let Some(attributes) = field.attributes().get_attribute::<FieldAttributes>()? {
+            result.push(attributes.bounds);
+        }
+    }
+    Ok(result)
+}
+
+/// Add the where clause predicates for `trait_name` (e.g. `bincode::Encode`) to `where_constraints`.
+///
+/// If the container or any of the fields has a bound for this trait, only those bounds are added. Otherwise every generic parameter has to implement `trait_name`.
+pub(crate) fn push_generic_constraints(
+    generics: &Generics,
+    where_constraints: &mut GenericConstraints,
+    trait_name: &str,
+    bounds: &[Bounds],
+) -> Result {
+    let explicit: Vec<&str> = bounds
+        .iter()
+        .filter_map(|bounds| bounds.get(trait_name))
+        .collect();
+    if explicit.is_empty() {
+        for g in generics.iter_generics() {
+            where_constraints.push_constraint(g, trait_name)?;
+        }
+    }
+    for bound in explicit {
+        if !bound.trim().is_empty() {
+            where_constraints.push_parsed_constraint(bound)?;
+        }
+    }
+    Ok(())
+}
+
+/// `.map_err(..)` that adds `path` (e.g. `Type.field`) to the context of a decode error
+pub(crate) fn field_context(path: &str) -> String {
+    format!(
+        ".map_err(|e| e.with_context(bincode::de::Decoder::offset(&*decoder), \"{}\"))",
+        path
+    )
+}
+
+/// Append `.add(<T as bincode::MaxSize>::MAX_SIZE)` for the type `T` of every field in `fields` that is not skipped
+pub(crate) fn push_max_size_of_fields(builder: &mut StreamBuilder, fields: &Fields) -> Result {
+    let fields: Vec<&UnnamedField> = match fields {
+        Fields::Tuple(fields) => fields.iter().collect(),
+        Fields::Struct(fields) => fields.iter().map(|(_, field)| field).collect(),
+    };
+    for field in fields {
+        let attributes = field
+            .attributes
+            .get_attribute::<FieldAttributes>()?
+            .unwrap_or_default();
+        if attributes.skip {
+            continue;
+        }
+        if attributes.with_serde {
+            return Err(Error::custom_at(
+                "MaxSize can not be derived for fields with #[bincode(with_serde)]",
+                field.span(),
+            ));
+        }
+        if attributes.encode_with.is_some() {
+            return Err(Error::custom_at(
+                "MaxSize can not be derived for fields with a custom encode function",
+                field.span(),
+            ));
+        }
+        builder.punct('.');
+        builder.ident_str("add");
+        builder.group(Delimiter::Parenthesis, |add| {
+            add.punct('<');
+            add.extend(field.r#type.iter().cloned());
+            add.push_parsed("as bincode::MaxSize>::MAX_SIZE")?;
+            Ok(())
+        })?;
+    }
+    Ok(())
+}
+
+/// Push `core::convert::From::from([bincode::schema::Field::new("a", ..), ..])` with the schema of every field in `fields` that is not skipped.
+///
+/// Returns `true` if the schema of a field is created with the `definitions` variable, so the caller knows whether it is used.
+pub(crate) fn push_schema_fields(
+    builder: &mut StreamBuilder,
+    fields: Option<&Fields>,
+) -> Result<bool> {
+    let fields: Vec<(String, &UnnamedField)> = match fields {
+        None => Vec::new(),
+        Some(Fields::Struct(fields)) => fields
+            .iter()
+            .map(|(ident, field)| (ident.to_string(), field))
+            .collect(),
+        Some(Fields::Tuple(fields)) => fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| (index.to_string(), field))
+            .collect(),
+    };
+    let mut uses_definitions = false;
+    builder.push_parsed("core::convert::From::from")?;
+    builder.group(Delimiter::Parenthesis, |from| {
+        from.group(Delimiter::Bracket, |list| {
+            for (name, field) in fields {
+                let attributes = field
+                    .attributes
+                    .get_attribute::<FieldAttributes>()?
+                    .unwrap_or_default();
+                if attributes.skip {
+                    continue;
+                }
+                // Fields with a custom encoding can not be described
+                let opaque = attributes.encode_with.is_some() || attributes.with_serde;
+                uses_definitions |= !opaque;
+                let push_field = |builder: &mut StreamBuilder| -> Result {
+                    // bincode::schema::Field::new("a", <T as bincode::Schema>::schema(definitions))
+                    // bincode::schema::Field::new("a", bincode::schema::Type::Opaque(core::convert::Into::into(core::any::type_name::<T>())))
+                    builder.push_parsed("bincode::schema::Field::new")?;
+                    builder.group(Delimiter::Parenthesis, |args| {
+                        args.lit_str(&name);
+                        args.punct(',');
+                        if opaque {
+                            args.push_parsed("bincode::schema::Type::Opaque")?;
+                            args.group(Delimiter::Parenthesis, |opaque| {
+                                opaque.push_parsed("core::convert::Into::into")?;
+                                opaque.group(Delimiter::Parenthesis, |type_name| {
+                                    type_name.push_parsed("core::any::type_name::<")?;
+                                    type_name.extend(field.r#type.iter().cloned());
+                                    type_name.push_parsed(">()")?;
+                                    Ok(())
+                                })?;
+                                Ok(())
+                            })?;
+                        } else {
+                            args.punct('<');
+                            args.extend(field.r#type.iter().cloned());
+                            args.push_parsed("as bincode::Schema>::schema(definitions)")?;
+                        }
+                        Ok(())
+                    })?;
+                    Ok(())
+                };
+                match attributes.tag {
+                    Some(tag) => {
+                        // bincode::schema::Field { tag: Some(1), ..bincode::schema::Field::new(..) }
+                        list.push_parsed("bincode::schema::Field")?;
+                        list.group(Delimiter::Brace, |field_body| {
+                            field_body.push_parsed(format!(
+                                "tag: core::option::Option::Some({}), ..",
+                                tag
+                            ))?;
+                            push_field(field_body)
+                        })?;
+                    }
+                    None => push_field(list)?,
+                }
+                list.punct(',');
+            }
+            Ok(())
+        })?;
+        Ok(())
+    })?;
+    Ok(uses_definitions)
+}
+
+/// The `version` of a `bincode::schema::StructSchema` or `bincode::schema::EnumSchema`
+pub(crate) fn schema_version(version: &Option<Version>) -> String {
+    match version {
+        Some(version) => format!("core::option::Option::Some({})", version.number),
+        None => "core::option::Option::None".to_string(),
+    }
+}
+
+/// Push `definitions.define(core::any::type_name::<Self>(), |definitions| ..)`, where `definition` pushes the body of the closure
+pub(crate) fn push_define_schema(
+    builder: &mut StreamBuilder,
+    uses_definitions: bool,
+    definition: impl FnOnce(&mut StreamBuilder) -> Result,
+) -> Result {
+    builder.push_parsed("definitions.define")?;
+    builder.group(Delimiter::Parenthesis, |args| {
+        args.push_parsed(format!(
+            "core::any::type_name::<Self>(), |{}: &mut bincode::schema::Definitions|",
+            if uses_definitions { "definitions" } else { "_" }
+        ))?;
+        args.group(Delimiter::Brace, definition)?;
+        Ok(())
+    })?;
+    Ok(())
+}
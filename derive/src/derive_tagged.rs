@@ -0,0 +1,415 @@
+use crate::attribute::{Bounds, FieldAttributes, Version};
+use crate::derive_struct::{
+    collect_bounds, decode_field, encode_field, field_context, push_define_schema,
+    push_generic_constraints, push_max_size_of_fields, push_schema_fields, schema_version,
+};
+use crate::derive_version;
+use virtue::generate::Generator;
+use virtue::prelude::*;
+
+/// Implements the traits of a struct with `#[bincode(tagged)]`.
+///
+/// The struct is encoded as a record (see `bincode::de::Record`) of entries. Every entry is the tag of a field as a `u32`, followed by the value of the field as a record.
+pub struct DeriveTagged {
+    pub fields: Option<Fields>,
+    pub bounds: Bounds,
+    pub version: Option<Version>,
+}
+
+struct TaggedField {
+    /// The name of the field, e.g. `a` or `0`
+    name: String,
+    /// The index of the field, used for the local variable `field_<index>` when decoding
+    index: usize,
+    tag: u32,
+    ty: Vec<TokenTree>,
+    attributes: FieldAttributes,
+}
+
+impl DeriveTagged {
+    /// All fields that are not skipped, with their tags
+    fn tagged_fields(&self) -> Result<Vec<TaggedField>> {
+        let fields: Vec<(String, &UnnamedField)> = match &self.fields {
+            None => Vec::new(),
+            Some(Fields::Struct(fields)) => fields
+                .iter()
+                .map(|(ident, field)| (ident.to_string(), field))
+                .collect(),
+            Some(Fields::Tuple(fields)) => fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| (index.to_string(), field))
+                .collect(),
+        };
+        let mut result: Vec<TaggedField> = Vec::new();
+        for (index, (name, field)) in fields.into_iter().enumerate() {
+            let attributes = field
+                .attributes
+                .get_attribute::<FieldAttributes>()?
+                .unwrap_or_default();
+            if attributes.skip {
+                continue;
+            }
+            let tag = match attributes.tag {
+                Some(tag) => tag,
+                None => {
+                    return Err(Error::custom_at(
+                        "Every field of a struct with #[bincode(tagged)] needs a #[bincode(tag = ..)], or #[bincode(skip)]",
+                        field.span(),
+                    ))
+                }
+            };
+            if result.iter().any(|other| other.tag == tag) {
+                return Err(Error::custom_at(
+                    format!("Tag {} is used by multiple fields", tag),
+                    field.span(),
+                ));
+            }
+            result.push(TaggedField {
+                name,
+                index,
+                tag,
+                ty: field.r#type.clone(),
+                attributes,
+            });
+        }
+        Ok(result)
+    }
+
+    /// The fields with `#[bincode(skip)]`
+    fn skipped_fields(&self) -> Result<Vec<(String, FieldAttributes)>> {
+        let mut result = Vec::new();
+        for field in self.fields.iter().flat_map(Fields::names) {
+            let attributes = field
+                .attributes()
+                .get_attribute::<FieldAttributes>()?
+                .unwrap_or_default();
+            if attributes.skip {
+                result.push((field.to_string(), attributes));
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn generate_encode(self, generator: &mut Generator) -> Result<()> {
+        let fields = self.tagged_fields()?;
+        let bounds = collect_bounds(&self.bounds, self.fields.iter())?;
+
+        generator
+            .impl_for("bincode::Encode")
+            .modify_generic_constraints(|generics, where_constraints| {
+                push_generic_constraints(generics, where_constraints, "bincode::Encode", &bounds)
+            })?
+            .generate_fn("encode")
+            .with_generic_deps("E", ["bincode::enc::Encoder"])
+            .with_self_arg(FnSelfArg::RefSelf)
+            .with_arg("encoder", "&mut E")
+            .with_return_type("core::result::Result<(), bincode::error::EncodeError>")
+            .body(|fn_body| {
+                derive_version::push_encode_version(fn_body, &self.version)?;
+                // let len_<index> = {
+                //     let mut size_encoder = bincode::enc::EncoderImpl::<_, E::C>::new(..);
+                //     {
+                //         let encoder = &mut size_encoder;
+                //         bincode::Encode::encode(&self.a, encoder)?;
+                //     }
+                //     size_encoder.into_writer().bytes_written
+                // };
+                let size_encoder = "let mut size_encoder = bincode::enc::EncoderImpl::<_, <E as bincode::enc::Encoder>::C>::new(bincode::enc::write::SizeWriter::default(), *bincode::enc::Encoder::config(encoder));";
+                for field in &fields {
+                    fn_body.push_parsed(format!("let len_{} = ", field.index))?;
+                    fn_body.group(Delimiter::Brace, |len_body| {
+                        len_body.push_parsed(size_encoder)?;
+                        len_body.group(Delimiter::Brace, |field_body| {
+                            field_body.push_parsed("let encoder = &mut size_encoder;")?;
+                            field_body.push_parsed(encode_field(
+                                &field.attributes,
+                                &format!("&self.{}", field.name),
+                            ))?;
+                            Ok(())
+                        })?;
+                        len_body.push_parsed("size_encoder.into_writer().bytes_written")?;
+                        Ok(())
+                    })?;
+                    fn_body.punct(';');
+                }
+
+                // The length of the record is the length of the tags and lengths of all entries, plus the length of the values
+                // let record_len = {
+                //     let mut size_encoder = ..;
+                //     let encoder = &mut size_encoder;
+                //     <u32 as bincode::Encode>::encode(&1, encoder)?;
+                //     <u64 as bincode::Encode>::encode(&(len_0 as u64), encoder)?;
+                //     ...
+                //     size_encoder.into_writer().bytes_written
+                // } + len_0 + ...;
+                if fields.is_empty() {
+                    fn_body.push_parsed("let record_len = 0usize;")?;
+                } else {
+                    fn_body.push_parsed("let record_len = ")?;
+                    fn_body.group(Delimiter::Brace, |len_body| {
+                        len_body.push_parsed(size_encoder)?;
+                        len_body.push_parsed("let encoder = &mut size_encoder;")?;
+                        for field in &fields {
+                            push_entry_header(len_body, field)?;
+                        }
+                        len_body.push_parsed("size_encoder.into_writer().bytes_written")?;
+                        Ok(())
+                    })?;
+                    for field in &fields {
+                        fn_body.push_parsed(format!("+ len_{}", field.index))?;
+                    }
+                    fn_body.punct(';');
+                }
+
+                // <u64 as bincode::Encode>::encode(&(record_len as u64), encoder)?;
+                // if bincode::enc::write::Writer::count_only(bincode::enc::Encoder::writer(encoder), record_len) {
+                //     return Ok(());
+                // }
+                // <u32 as bincode::Encode>::encode(&1, encoder)?;
+                // <u64 as bincode::Encode>::encode(&(len_0 as u64), encoder)?;
+                // bincode::Encode::encode(&self.a, encoder)?;
+                // ...
+                fn_body.push_parsed(
+                    "<u64 as bincode::Encode>::encode(&(record_len as u64), encoder)?;",
+                )?;
+                // When only the size of this struct is needed, the entries don't have to be encoded a second time.
+                // Without this, nested structs would be encoded 2^depth times.
+                fn_body.push_parsed(
+                    "if bincode::enc::write::Writer::count_only(bincode::enc::Encoder::writer(encoder), record_len) { return Ok(()); }",
+                )?;
+                for field in &fields {
+                    push_entry_header(fn_body, field)?;
+                    fn_body.push_parsed(encode_field(
+                        &field.attributes,
+                        &format!("&self.{}", field.name),
+                    ))?;
+                }
+                fn_body.push_parsed("Ok(())")?;
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    pub fn generate_decode(self, generator: &mut Generator) -> Result<()> {
+        // Remember to keep this mostly in sync with generate_borrow_decode
+        let struct_name = generator.target_name().to_string();
+        let bounds = collect_bounds(&self.bounds, self.fields.iter())?;
+
+        let (trait_name, fn_name) = derive_version::decode_trait(&self.version);
+
+        let mut impl_for = generator.impl_for(trait_name);
+        impl_for.modify_generic_constraints(|generics, where_constraints| {
+            push_generic_constraints(generics, where_constraints, "bincode::Decode", &bounds)
+        })?;
+        derive_version::with_version_arg(
+            impl_for
+                .generate_fn(fn_name)
+                .with_generic_deps("D", ["bincode::de::Decoder"])
+                .with_arg("decoder", "&mut D"),
+            &self.version,
+        )
+        .with_return_type("core::result::Result<Self, bincode::error::DecodeError>")
+        .body(|fn_body| {
+            derive_version::push_check_version(fn_body, &self.version)?;
+            self.push_decode_body(fn_body, &struct_name, false)
+        })?;
+        Ok(())
+    }
+
+    pub fn generate_borrow_decode(self, generator: &mut Generator) -> Result<()> {
+        // Remember to keep this mostly in sync with generate_decode
+        let struct_name = generator.target_name().to_string();
+        let bounds = collect_bounds(&self.bounds, self.fields.iter())?;
+
+        generator
+            .impl_for_with_lifetimes("bincode::BorrowDecode", ["__de"])
+            .new_lifetimes_depend_on_existing()
+            .modify_generic_constraints(|generics, where_constraints| {
+                push_generic_constraints(
+                    generics,
+                    where_constraints,
+                    "bincode::BorrowDecode",
+                    &bounds,
+                )
+            })?
+            .generate_fn("borrow_decode")
+            .with_generic_deps("D", ["bincode::de::BorrowDecoder<'__de>"])
+            .with_arg("decoder", "&mut D")
+            .with_return_type("core::result::Result<Self, bincode::error::DecodeError>")
+            .body(|fn_body| self.push_decode_body(fn_body, &struct_name, true))?;
+        Ok(())
+    }
+
+    fn push_decode_body(
+        &self,
+        fn_body: &mut StreamBuilder,
+        struct_name: &str,
+        borrow: bool,
+    ) -> Result {
+        let fields = self.tagged_fields()?;
+
+        // bincode::de::Decoder::enter_depth(decoder)?;
+        // let record = bincode::de::Record::begin(decoder)?;
+        // let mut field_0: core::option::Option<A> = core::option::Option::None;
+        // ...
+        fn_body.push_parsed("bincode::de::Decoder::enter_depth(decoder)?;")?;
+        fn_body.push_parsed("let record = bincode::de::Record::begin(decoder)?;")?;
+        for field in &fields {
+            fn_body.push_parsed(format!(
+                "let mut field_{}: core::option::Option<",
+                field.index
+            ))?;
+            fn_body.extend(field.ty.iter().cloned());
+            fn_body.push_parsed("> = core::option::Option::None;")?;
+        }
+
+        // while !record.is_finished(&*decoder) {
+        //     let tag = <u32 as bincode::Decode>::decode(decoder)?;
+        //     let entry = bincode::de::Record::begin(decoder)?;
+        //     match tag {
+        //         1 => field_0 = core::option::Option::Some(bincode::Decode::decode(decoder).map_err(..)?),
+        //         ...
+        //         _ => {}
+        //     }
+        //     entry.finish(decoder)?;
+        // }
+        // record.finish(decoder)?;
+        fn_body.push_parsed("while !record.is_finished(&*decoder)")?;
+        fn_body.group(Delimiter::Brace, |loop_body| {
+            loop_body.push_parsed("let tag = <u32 as bincode::Decode>::decode(decoder)?;")?;
+            loop_body.push_parsed("let entry = bincode::de::Record::begin(decoder)?;")?;
+            loop_body.push_parsed("match tag")?;
+            loop_body.group(Delimiter::Brace, |match_body| {
+                for field in &fields {
+                    let context = field_context(&format!("{}.{}", struct_name, field.name));
+                    match_body.push_parsed(format!(
+                        "{} => field_{} = core::option::Option::Some({}),",
+                        field.tag,
+                        field.index,
+                        decode_field(&field.attributes, &context, borrow)
+                    ))?;
+                }
+                match_body.push_parsed("_ => {}")?;
+                Ok(())
+            })?;
+            loop_body.push_parsed("entry.finish(decoder)?;")?;
+            Ok(())
+        })?;
+        fn_body.push_parsed("record.finish(decoder)?;")?;
+
+        // let result = Ok(Self {
+        //     a: match field_0 { core::option::Option::Some(value) => value, core::option::Option::None => Default::default() },
+        //     skipped: Default::default(),
+        // });
+        // bincode::de::Decoder::leave_depth(decoder);
+        // result
+        fn_body.push_parsed("let result = Ok")?;
+        fn_body.group(Delimiter::Parenthesis, |ok_group| {
+            ok_group.ident_str("Self");
+            ok_group.group(Delimiter::Brace, |struct_body| {
+                for field in &fields {
+                    struct_body.push_parsed(format!(
+                        "{}: match field_{} {{ core::option::Option::Some(value) => value, core::option::Option::None => {} }},",
+                        field.name,
+                        field.index,
+                        field.attributes.default_value()
+                    ))?;
+                }
+                for (name, attributes) in self.skipped_fields()? {
+                    struct_body.push_parsed(format!("{}: {},", name, attributes.default_value()))?;
+                }
+                Ok(())
+            })?;
+            Ok(())
+        })?;
+        fn_body.punct(';');
+        fn_body.push_parsed("bincode::de::Decoder::leave_depth(decoder);")?;
+        fn_body.ident_str("result");
+        Ok(())
+    }
+
+    pub fn generate_max_size(self, generator: &mut Generator) -> Result<()> {
+        let fields = self.tagged_fields()?;
+        let bounds = collect_bounds(&self.bounds, self.fields.iter())?;
+
+        generator
+            .impl_for("bincode::MaxSize")
+            .modify_generic_constraints(|generics, where_constraints| {
+                push_generic_constraints(generics, where_constraints, "bincode::MaxSize", &bounds)
+            })?
+            .generate_const("MAX_SIZE", "bincode::enc::MaxSizes")
+            .with_value(|value| {
+                // bincode::enc::MaxSizes::ZERO
+                //     .add(<u64 as bincode::MaxSize>::MAX_SIZE)
+                //     .add(<u32 as bincode::MaxSize>::MAX_SIZE)
+                //     .add(<u64 as bincode::MaxSize>::MAX_SIZE)
+                //     ...
+                //     .add(<A as bincode::MaxSize>::MAX_SIZE)
+                //     ...
+                value.push_parsed("bincode::enc::MaxSizes::ZERO")?;
+                derive_version::push_max_size_version(value, &self.version)?;
+                value.push_parsed(".add(<u64 as bincode::MaxSize>::MAX_SIZE)")?;
+                for _ in &fields {
+                    value.push_parsed(
+                        ".add(<u32 as bincode::MaxSize>::MAX_SIZE).add(<u64 as bincode::MaxSize>::MAX_SIZE)",
+                    )?;
+                }
+                if let Some(fields) = &self.fields {
+                    push_max_size_of_fields(value, fields)?;
+                }
+                Ok(())
+            })?;
+        Ok(())
+    }
+
+    pub fn generate_schema(self, generator: &mut Generator) -> Result<()> {
+        // Check the tags of the fields
+        self.tagged_fields()?;
+        let bounds = collect_bounds(&self.bounds, self.fields.iter())?;
+
+        let mut schema_fields = StreamBuilder::new();
+        let uses_definitions = push_schema_fields(&mut schema_fields, self.fields.as_ref())?;
+
+        generator
+            .impl_for("bincode::Schema")
+            .modify_generic_constraints(|generics, where_constraints| {
+                push_generic_constraints(generics, where_constraints, "bincode::Schema", &bounds)
+            })?
+            .generate_fn("schema")
+            .with_arg("definitions", "&mut bincode::schema::Definitions")
+            .with_return_type("bincode::schema::Type")
+            .body(|fn_body| {
+                push_define_schema(fn_body, uses_definitions, |definition| {
+                    definition.push_parsed("bincode::schema::Definition::Struct")?;
+                    definition.group(Delimiter::Parenthesis, |schema| {
+                        schema.push_parsed("bincode::schema::StructSchema")?;
+                        schema.group(Delimiter::Brace, |schema_body| {
+                            schema_body.push_parsed(format!(
+                                "version: {}, layout: bincode::schema::StructLayout::Tagged, fields: ",
+                                schema_version(&self.version)
+                            ))?;
+                            schema_body.append(schema_fields);
+                            Ok(())
+                        })?;
+                        Ok(())
+                    })?;
+                    Ok(())
+                })
+            })?;
+        Ok(())
+    }
+}
+
+/// Encode the tag and the length of the value of `field`
+fn push_entry_header(builder: &mut StreamBuilder, field: &TaggedField) -> Result {
+    builder.push_parsed(format!(
+        "<u32 as bincode::Encode>::encode(&{}, encoder)?;",
+        field.tag
+    ))?;
+    builder.push_parsed(format!(
+        "<u64 as bincode::Encode>::encode(&(len_{} as u64), encoder)?;",
+        field.index
+    ))?;
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use crate::attribute::Version;
+use virtue::generate::{FnBuilder, Generator, ImplFor};
+use virtue::prelude::*;
+
+/// The trait and function that decode the fields of a type: `bincode::de::DecodeVersioned::decode_versioned` for types with a version, otherwise `bincode::Decode::decode`
+pub fn decode_trait(version: &Option<Version>) -> (&'static str, &'static str) {
+    if version.is_some() {
+        ("bincode::de::DecodeVersioned", "decode_versioned")
+    } else {
+        ("bincode::Decode", "decode")
+    }
+}
+
+/// Add the `version: u32` argument of `decode_versioned` to `builder` if the type has a version
+pub fn with_version_arg<'a, 'b>(
+    builder: FnBuilder<'a, ImplFor<'b, Generator>>,
+    version: &Option<Version>,
+) -> FnBuilder<'a, ImplFor<'b, Generator>> {
+    if version.is_some() {
+        builder.with_arg("version", "u32")
+    } else {
+        builder
+    }
+}
+
+/// Encode the version number in front of the fields
+pub fn push_encode_version(builder: &mut StreamBuilder, version: &Option<Version>) -> Result {
+    if let Some(version) = version {
+        // <u32 as bincode::Encode>::encode(&N, encoder)?;
+        builder.push_parsed(format!(
+            "<u32 as bincode::Encode>::encode(&{}u32, encoder)?;",
+            version.number
+        ))?;
+    }
+    Ok(())
+}
+
+/// Add the max size of the version number
+pub fn push_max_size_version(builder: &mut StreamBuilder, version: &Option<Version>) -> Result {
+    if version.is_some() {
+        builder.push_parsed(".add(<u32 as bincode::MaxSize>::MAX_SIZE)")?;
+    }
+    Ok(())
+}
+
+/// Return early from `decode_versioned` if `version` is not the current version, by migrating from the older type or returning `DecodeError::UnknownVersion`
+pub fn push_check_version(builder: &mut StreamBuilder, version: &Option<Version>) -> Result {
+    let version = match version {
+        Some(version) => version,
+        None => return Ok(()),
+    };
+    // if version != N {
+    //     if version < N {
+    //         return Ok(<Self as From<Old>>::from(<Old as bincode::de::DecodeVersioned>::decode_versioned(decoder, version)?));
+    //     }
+    //     return Err(bincode::error::DecodeError::UnknownVersion { .. });
+    // }
+    builder.push_parsed(format!("if version != {}", version.number))?;
+    builder.group(Delimiter::Brace, |body| {
+        if let Some(migrate_from) = &version.migrate_from {
+            body.push_parsed(format!(
+                "if version < {} {{ return core::result::Result::Ok(<Self as core::convert::From<{}>>::from(<{} as bincode::de::DecodeVersioned>::decode_versioned(decoder, version)?)); }}",
+                version.number, migrate_from, migrate_from
+            ))?;
+        }
+        body.push_parsed(format!(
+            "return core::result::Result::Err(bincode::error::DecodeError::UnknownVersion {{ type_name: core::any::type_name::<Self>(), found: version, current: {} }});",
+            version.number
+        ))?;
+        Ok(())
+    })?;
+    Ok(())
+}
+
+/// Implement `Decode` for a type with a version, by reading the version number and calling `DecodeVersioned::decode_versioned`
+pub fn generate_decode(generator: &mut Generator) -> Result {
+    generator
+        .impl_for("bincode::Decode")
+        .modify_generic_constraints(|_, where_constraints| {
+            where_constraints.push_parsed_constraint("Self: bincode::de::DecodeVersioned")
+        })?
+        .generate_fn("decode")
+        .with_generic_deps("D", ["bincode::de::Decoder"])
+        .with_arg("decoder", "&mut D")
+        .with_return_type("core::result::Result<Self, bincode::error::DecodeError>")
+        .body(|fn_body| {
+            // let version = <u32 as bincode::Decode>::decode(decoder)?;
+            // <Self as bincode::de::DecodeVersioned>::decode_versioned(decoder, version)
+            fn_body.push_parsed("let version = <u32 as bincode::Decode>::decode(decoder)?;")?;
+            fn_body.push_parsed(
+                "<Self as bincode::de::DecodeVersioned>::decode_versioned(decoder, version)",
+            )?;
+            Ok(())
+        })?;
+    Ok(())
+}
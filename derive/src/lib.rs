@@ -1,8 +1,73 @@
+mod attribute;
+mod derive_conversion;
 mod derive_enum;
 mod derive_struct;
+mod derive_tagged;
+mod derive_version;
 
+use attribute::{ContainerAttributes, FieldAttributes};
+use virtue::parse::Attribute;
 use virtue::prelude::*;
 
+/// Derive `bincode::Encode` for a struct or enum. Every field must implement `Encode`.
+///
+/// # Field attributes
+///
+/// - `#[bincode(with_serde)]`: encode and decode the field with its `serde` implementation. Requires the `serde` feature.
+/// - `#[bincode(skip)]`: do not encode the field. When decoding, the field is set to `Default::default()`.
+/// - `#[bincode(default = "path")]`: do not encode the field. When decoding, the field is set to the result of calling `path()`.
+/// - `#[bincode(encode_with = "path")]`: encode the field by calling `path(&field, encoder)`, where `path` is a function like `fn encode<E: bincode::enc::Encoder>(value: &T, encoder: &mut E) -> Result<(), bincode::error::EncodeError>`.
+/// - `#[bincode(decode_with = "path")]`: decode the field by calling `path(decoder)`, where `path` is a function like `fn decode<D: bincode::de::Decoder>(decoder: &mut D) -> Result<T, bincode::error::DecodeError>`. For `BorrowDecode`, the function can also take a `D: bincode::de::BorrowDecoder<'de>`.
+/// - `#[bincode(tag = 1)]`: the tag of the field in a struct with `#[bincode(tagged)]`.
+/// - `#[bincode(with = "module")]`: the same as `#[bincode(encode_with = "module::encode", decode_with = "module::decode")]`.
+/// - `#[bincode(bound = "...")]`, `#[bincode(encode_bound = "...")]`, `#[bincode(decode_bound = "...")]` and `#[bincode(borrow_decode_bound = "...")]`: see [Generic bounds](#generic-bounds).
+///
+/// # Container attributes
+///
+/// - `#[bincode(crate = "path")]`: use `path` instead of `bincode` in the generated code. This is useful when bincode is used through a re-export, e.g. `#[bincode(crate = "my_crate::bincode")]`.
+/// - `#[bincode(tag_type = "u8")]`: encode the variant index of an enum as the given integer type, one of `u8`, `u16`, `u32` or `u64`. The default is `u32`. Every variant index, including explicit discriminants like `Variant = 5`, must fit in this type. Explicit discriminants must also fit in a `u32`, because they are reported in `DecodeError::UnexpectedVariant`.
+/// - `#[bincode(default_trailing)]`: encode the fields of a struct as a record that is prefixed with its length in bytes, as a `u64`. When decoding, fields that are missing at the end of the record are set to `Default::default()`, and unknown bytes at the end of the record are skipped. This allows adding fields to the end of a struct, while still being able to decode data that was encoded by older and newer versions. See [`Record`] for more information.
+/// - `#[bincode(tagged)]`: encode the fields of a struct with a stable numeric tag, like protobuf. Every field that is not skipped needs a unique `#[bincode(tag = 1)]`. Fields can be reordered, removed and added without breaking data that was encoded by other versions of the struct, as long as the tag of a field never changes and is not reused: when decoding, fields with an unknown tag are skipped and fields that are missing are set to `Default::default()`. See the [specification](https://github.com/bincode-org/bincode/blob/trunk/docs/spec.md) for the format.
+/// - `#[bincode(into = "T")]`: encode the type by cloning it and converting it into `T` with `Into<T>`. The type must implement `Clone`, and `T` must implement `Encode`. When deriving `MaxSize`, the max size of `T` is used.
+/// - `#[bincode(from = "T")]`: decode a `T` and convert it into the type with `From<T>`.
+/// - `#[bincode(try_from = "T")]`: decode a `T` and convert it into the type with `TryFrom<T>`. If the conversion fails, [`DecodeError::ConversionFailedString`] is returned with the error message of the conversion, or [`DecodeError::ConversionFailed`] without the `alloc` feature. The error type must implement `Display`.
+/// - `#[bincode(version = N)]`: encode the version number `N` as a `u32` in front of the type. When decoding, a different version number returns [`DecodeError::UnknownVersion`], unless it is an older version that can be migrated. `Decode` is implemented by reading the version number and calling [`DecodeVersioned`], which is implemented for the type as well. `N` must be at least 1.
+/// - `#[bincode(migrate_from = "T")]`: when decoding a version older than `N`, decode `T` with [`DecodeVersioned`] instead and convert it into the type with `From<T>`. `T` must have a version as well, so older versions are migrated through every type in the chain. Requires `version`.
+///
+/// `from` and `try_from` can not be combined. `version` can not be combined with `into`, `from` or `try_from`, and can not be used when deriving `BorrowDecode`. These attributes are usually used together, e.g. `#[bincode(into = "T", from = "T")]`.
+///
+/// The `bound` attributes described in [Generic bounds](#generic-bounds) can also be placed on a struct or enum.
+///
+/// # Generic bounds
+///
+/// By default, every generic parameter `T` of the type is required to implement the derived trait, e.g. `where T: bincode::Encode`. This does not work for every type, e.g. when `T` is only used in a `PhantomData<T>` or when a field has an associated type like `T::Id`.
+///
+/// The `bound` attributes replace these constraints with the where clause predicates in the given string:
+///
+/// - `#[bincode(bound = "...")]`: used for every derived trait.
+/// - `#[bincode(encode_bound = "...")]`, `#[bincode(decode_bound = "...")]` and `#[bincode(borrow_decode_bound = "...")]`: used for `Encode`, `Decode` and `BorrowDecode`, instead of `bound`. The lifetime of `BorrowDecode` is called `'__de`, e.g. `T: bincode::BorrowDecode<'__de>`.
+///
+/// When any of the attributes on the type or its fields has a bound for the derived trait, the bounds of all of them are combined, and no other constraints are generated. An empty string, e.g. `#[bincode(bound = "")]`, removes the constraints entirely.
+///
+/// ```ignore
+/// #[derive(bincode::Encode, bincode::Decode)]
+/// #[bincode(bound = "")]
+/// struct Marker<T> {
+///     marker: core::marker::PhantomData<T>,
+/// }
+///
+/// #[derive(bincode::Encode, bincode::Decode)]
+/// struct Reference<T: Entity> {
+///     #[bincode(encode_bound = "T::Id: bincode::Encode", decode_bound = "T::Id: bincode::Decode")]
+///     id: T::Id,
+/// }
+/// ```
+///
+/// [`Record`]: ../bincode/de/struct.Record.html
+/// [`DecodeVersioned`]: ../bincode/de/trait.DecodeVersioned.html
+/// [`DecodeError::UnknownVersion`]: ../bincode/error/enum.DecodeError.html#variant.UnknownVersion
+/// [`DecodeError::ConversionFailed`]: ../bincode/error/enum.DecodeError.html#variant.ConversionFailed
+/// [`DecodeError::ConversionFailedString`]: ../bincode/error/enum.DecodeError.html#variant.ConversionFailedString
 #[proc_macro_derive(Encode, attributes(bincode))]
 pub fn derive_encode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_encode_inner(input).unwrap_or_else(|e| e.into_token_stream())
@@ -10,29 +75,56 @@ pub fn derive_encode(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 
 fn derive_encode_inner(input: TokenStream) -> Result<TokenStream> {
     let parse = Parse::new(input)?;
-    let (mut generator, body) = parse.into_generator();
+    let (mut generator, attributes, body) = parse.into_generator();
+    let container = container_attributes(attributes, &body)?;
 
-    match body {
-        Body::Struct(body) => {
-            derive_struct::DeriveStruct {
-                fields: body.fields,
-            }
-            .generate_encode(&mut generator)?;
+    if container.into.is_some() {
+        derive_conversion::DeriveConversion {
+            into: container.into.as_deref(),
+            from: None,
+            bounds: container.bounds,
         }
-        Body::Enum(body) => {
-            derive_enum::DeriveEnum {
-                variants: body.variants,
+        .generate_encode(&mut generator)?;
+    } else {
+        match body {
+            Body::Struct(body) if container.tagged => {
+                derive_tagged::DeriveTagged {
+                    fields: body.fields,
+                    bounds: container.bounds,
+                    version: container.version.clone(),
+                }
+                .generate_encode(&mut generator)?;
+            }
+            Body::Struct(body) => {
+                derive_struct::DeriveStruct {
+                    fields: body.fields,
+                    bounds: container.bounds,
+                    default_trailing: container.default_trailing,
+                    version: container.version.clone(),
+                }
+                .generate_encode(&mut generator)?;
+            }
+            Body::Enum(body) => {
+                derive_enum::DeriveEnum {
+                    variants: body.variants,
+                    bounds: container.bounds,
+                    tag_type: container.tag_type.unwrap_or_default(),
+                    version: container.version.clone(),
+                }
+                .generate_encode(&mut generator)?;
             }
-            .generate_encode(&mut generator)?;
         }
     }
 
-    let name = generator.target_name().clone();
-    let stream = generator.finish()?;
+    let name = generator.target_name();
+    let stream = with_crate_path(generator.finish()?, container.crate_path.as_deref());
     dump_output(name, "Encode", &stream);
     Ok(stream)
 }
 
+/// Derive `bincode::Decode` for a struct or enum. Every field must implement `Decode`. For types with lifetimes, derive `BorrowDecode` instead.
+///
+/// See [Encode](derive.Encode.html) for the supported attributes.
 #[proc_macro_derive(Decode, attributes(bincode))]
 pub fn derive_decode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_decode_inner(input).unwrap_or_else(|e| e.into_token_stream())
@@ -40,29 +132,59 @@ pub fn derive_decode(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 
 fn derive_decode_inner(input: TokenStream) -> Result<TokenStream> {
     let parse = Parse::new(input)?;
-    let (mut generator, body) = parse.into_generator();
+    let (mut generator, attributes, body) = parse.into_generator();
+    let container = container_attributes(attributes, &body)?;
 
-    match body {
-        Body::Struct(body) => {
-            derive_struct::DeriveStruct {
-                fields: body.fields,
-            }
-            .generate_decode(&mut generator)?;
+    if container.from.is_some() {
+        derive_conversion::DeriveConversion {
+            into: None,
+            from: container.from.as_ref(),
+            bounds: container.bounds,
         }
-        Body::Enum(body) => {
-            derive_enum::DeriveEnum {
-                variants: body.variants,
+        .generate_decode(&mut generator)?;
+    } else {
+        match body {
+            Body::Struct(body) if container.tagged => {
+                derive_tagged::DeriveTagged {
+                    fields: body.fields,
+                    bounds: container.bounds,
+                    version: container.version.clone(),
+                }
+                .generate_decode(&mut generator)?;
+            }
+            Body::Struct(body) => {
+                derive_struct::DeriveStruct {
+                    fields: body.fields,
+                    bounds: container.bounds,
+                    default_trailing: container.default_trailing,
+                    version: container.version.clone(),
+                }
+                .generate_decode(&mut generator)?;
+            }
+            Body::Enum(body) => {
+                derive_enum::DeriveEnum {
+                    variants: body.variants,
+                    bounds: container.bounds,
+                    tag_type: container.tag_type.unwrap_or_default(),
+                    version: container.version.clone(),
+                }
+                .generate_decode(&mut generator)?;
             }
-            .generate_decode(&mut generator)?;
         }
     }
+    if container.version.is_some() {
+        derive_version::generate_decode(&mut generator)?;
+    }
 
-    let name = generator.target_name().clone();
-    let stream = generator.finish()?;
+    let name = generator.target_name();
+    let stream = with_crate_path(generator.finish()?, container.crate_path.as_deref());
     dump_output(name, "Decode", &stream);
     Ok(stream)
 }
 
+/// Derive `bincode::BorrowDecode` for a struct or enum. Every field must implement `BorrowDecode`.
+///
+/// See [Encode](derive.Encode.html) for the supported attributes.
 #[proc_macro_derive(BorrowDecode, attributes(bincode))]
 pub fn derive_brrow_decode(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_borrow_decode_inner(input).unwrap_or_else(|e| e.into_token_stream())
@@ -70,29 +192,238 @@ pub fn derive_brrow_decode(input: proc_macro::TokenStream) -> proc_macro::TokenS
 
 fn derive_borrow_decode_inner(input: TokenStream) -> Result<TokenStream> {
     let parse = Parse::new(input)?;
-    let (mut generator, body) = parse.into_generator();
+    let (mut generator, attributes, body) = parse.into_generator();
+    let container = container_attributes(attributes, &body)?;
+    if container.version.is_some() {
+        return Err(Error::custom(
+            "version can not be used with BorrowDecode, only with Decode",
+        ));
+    }
 
-    match body {
-        Body::Struct(body) => {
-            derive_struct::DeriveStruct {
-                fields: body.fields,
-            }
-            .generate_borrow_decode(&mut generator)?;
+    if container.from.is_some() {
+        derive_conversion::DeriveConversion {
+            into: None,
+            from: container.from.as_ref(),
+            bounds: container.bounds,
         }
-        Body::Enum(body) => {
-            derive_enum::DeriveEnum {
-                variants: body.variants,
+        .generate_borrow_decode(&mut generator)?;
+    } else {
+        match body {
+            Body::Struct(body) if container.tagged => {
+                derive_tagged::DeriveTagged {
+                    fields: body.fields,
+                    bounds: container.bounds,
+                    version: container.version.clone(),
+                }
+                .generate_borrow_decode(&mut generator)?;
+            }
+            Body::Struct(body) => {
+                derive_struct::DeriveStruct {
+                    fields: body.fields,
+                    bounds: container.bounds,
+                    default_trailing: container.default_trailing,
+                    version: container.version.clone(),
+                }
+                .generate_borrow_decode(&mut generator)?;
+            }
+            Body::Enum(body) => {
+                derive_enum::DeriveEnum {
+                    variants: body.variants,
+                    bounds: container.bounds,
+                    tag_type: container.tag_type.unwrap_or_default(),
+                    version: container.version.clone(),
+                }
+                .generate_borrow_decode(&mut generator)?;
             }
-            .generate_borrow_decode(&mut generator)?;
         }
     }
 
-    let name = generator.target_name().clone();
-    let stream = generator.finish()?;
+    let name = generator.target_name();
+    let stream = with_crate_path(generator.finish()?, container.crate_path.as_deref());
     dump_output(name, "BorrowDecode", &stream);
     Ok(stream)
 }
 
+/// Derive `bincode::MaxSize` for a struct or enum. Every field that is not skipped must implement `MaxSize`.
+#[proc_macro_derive(MaxSize, attributes(bincode))]
+pub fn derive_max_size(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    derive_max_size_inner(input).unwrap_or_else(|e| e.into_token_stream())
+}
+
+fn derive_max_size_inner(input: TokenStream) -> Result<TokenStream> {
+    let parse = Parse::new(input)?;
+    let (mut generator, attributes, body) = parse.into_generator();
+    let container = container_attributes(attributes, &body)?;
+
+    if container.into.is_some() {
+        derive_conversion::DeriveConversion {
+            into: container.into.as_deref(),
+            from: None,
+            bounds: container.bounds,
+        }
+        .generate_max_size(&mut generator)?;
+    } else {
+        match body {
+            Body::Struct(body) if container.tagged => {
+                derive_tagged::DeriveTagged {
+                    fields: body.fields,
+                    bounds: container.bounds,
+                    version: container.version.clone(),
+                }
+                .generate_max_size(&mut generator)?;
+            }
+            Body::Struct(body) => {
+                derive_struct::DeriveStruct {
+                    fields: body.fields,
+                    bounds: container.bounds,
+                    default_trailing: container.default_trailing,
+                    version: container.version.clone(),
+                }
+                .generate_max_size(&mut generator)?;
+            }
+            Body::Enum(body) => {
+                derive_enum::DeriveEnum {
+                    variants: body.variants,
+                    bounds: container.bounds,
+                    tag_type: container.tag_type.unwrap_or_default(),
+                    version: container.version.clone(),
+                }
+                .generate_max_size(&mut generator)?;
+            }
+        }
+    }
+
+    let name = generator.target_name();
+    let stream = with_crate_path(generator.finish()?, container.crate_path.as_deref());
+    dump_output(name, "MaxSize", &stream);
+    Ok(stream)
+}
+
+/// Derive `bincode::Schema` for a struct or enum. Every field that is not skipped must implement `Schema`. Requires the `alloc` feature of bincode.
+///
+/// The schema reflects the attributes that change the encoding, e.g. `tag_type`, `default_trailing`, `tagged` and `version`. Fields with `with_serde` or `encode_with` are described as `bincode::schema::Type::Opaque`. Types with `into` or `from` have the schema of the type they are converted to or from.
+///
+/// See [Encode](derive.Encode.html) for the supported attributes.
+#[proc_macro_derive(Schema, attributes(bincode))]
+pub fn derive_schema(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    derive_schema_inner(input).unwrap_or_else(|e| e.into_token_stream())
+}
+
+fn derive_schema_inner(input: TokenStream) -> Result<TokenStream> {
+    let parse = Parse::new(input)?;
+    let (mut generator, attributes, body) = parse.into_generator();
+    let container = container_attributes(attributes, &body)?;
+
+    if container.into.is_some() || container.from.is_some() {
+        derive_conversion::DeriveConversion {
+            into: container.into.as_deref(),
+            from: container.from.as_ref(),
+            bounds: container.bounds,
+        }
+        .generate_schema(&mut generator)?;
+    } else {
+        match body {
+            Body::Struct(body) if container.tagged => {
+                derive_tagged::DeriveTagged {
+                    fields: body.fields,
+                    bounds: container.bounds,
+                    version: container.version.clone(),
+                }
+                .generate_schema(&mut generator)?;
+            }
+            Body::Struct(body) => {
+                derive_struct::DeriveStruct {
+                    fields: body.fields,
+                    bounds: container.bounds,
+                    default_trailing: container.default_trailing,
+                    version: container.version.clone(),
+                }
+                .generate_schema(&mut generator)?;
+            }
+            Body::Enum(body) => {
+                derive_enum::DeriveEnum {
+                    variants: body.variants,
+                    bounds: container.bounds,
+                    tag_type: container.tag_type.unwrap_or_default(),
+                    version: container.version.clone(),
+                }
+                .generate_schema(&mut generator)?;
+            }
+        }
+    }
+
+    let name = generator.target_name();
+    let stream = with_crate_path(generator.finish()?, container.crate_path.as_deref());
+    dump_output(name, "Schema", &stream);
+    Ok(stream)
+}
+
+fn container_attributes(attributes: Vec<Attribute>, body: &Body) -> Result<ContainerAttributes> {
+    let container = attributes
+        .get_attribute::<ContainerAttributes>()?
+        .unwrap_or_default();
+    if container.tag_type.is_some() && matches!(body, Body::Struct(_)) {
+        return Err(Error::custom("tag_type can only be used on enums"));
+    }
+    if container.default_trailing && matches!(body, Body::Enum(_)) {
+        return Err(Error::custom(
+            "default_trailing can only be used on structs",
+        ));
+    }
+    if container.tagged && matches!(body, Body::Enum(_)) {
+        return Err(Error::custom("tagged can only be used on structs"));
+    }
+    if container.tagged && container.default_trailing {
+        return Err(Error::custom(
+            "tagged can not be combined with default_trailing",
+        ));
+    }
+    if container.version.is_some() && (container.into.is_some() || container.from.is_some()) {
+        return Err(Error::custom(
+            "version can not be combined with into, from or try_from",
+        ));
+    }
+    if !container.tagged {
+        let fields: Vec<&Fields> = match body {
+            Body::Struct(body) => body.fields.iter().collect(),
+            Body::Enum(body) => body
+                .variants
+                .iter()
+                .filter_map(|variant| variant.fields.as_ref())
+                .collect(),
+        };
+        for field in fields.into_iter().flat_map(Fields::names) {
+            let attributes = field.attributes().get_attribute::<FieldAttributes>()?;
+            if attributes.is_some_and(|attributes| attributes.tag.is_some()) {
+                return Err(Error::custom(format!(
+                    "Field {} has a tag, but tag can only be used on the fields of a struct with #[bincode(tagged)]",
+                    field
+                )));
+            }
+        }
+    }
+    Ok(container)
+}
+
+/// Wrap the generated code in `const _: () = { use path as bincode; .. };` for `#[bincode(crate = "path")]`, so every `bincode::` path refers to `path` instead
+fn with_crate_path(stream: TokenStream, crate_path: Option<&str>) -> TokenStream {
+    let crate_path = match crate_path {
+        Some(crate_path) => crate_path,
+        None => return stream,
+    };
+    let mut inner: TokenStream = format!("use {} as bincode;", crate_path)
+        .parse()
+        .expect("crate path is validated when parsing the attribute");
+    inner.extend(stream);
+
+    let mut result: TokenStream = "const _: () =".parse().unwrap();
+    result.extend([
+        TokenTree::Group(Group::new(Delimiter::Brace, inner)),
+        TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+    ]);
+    result
+}
+
 fn dump_output(name: Ident, derive: &str, stream: &TokenStream) {
     use std::io::Write;
 
@@ -107,26 +438,3 @@ fn dump_output(name: Ident, derive: &str, stream: &TokenStream) {
         }
     }
 }
-
-#[derive(Debug, Copy, Clone, PartialEq)]
-enum FieldAttribute {
-    WithSerde,
-}
-
-impl FromAttribute for FieldAttribute {
-    fn parse(group: &Group) -> Result<Option<Self>> {
-        let body = match virtue::utils::parse_tagged_attribute(group, "bincode") {
-            Some(body) => body,
-            None => return Ok(None),
-        };
-        match body.into_iter().next() {
-            Some(TokenTree::Ident(ident)) if ident.to_string() == "with_serde" => {
-                Ok(Some(Self::WithSerde))
-            }
-            token => Err(virtue::Error::custom_at_opt_token(
-                "Unknown attribute, expected one of: \"with_serde\"",
-                token,
-            )),
-        }
-    }
-}
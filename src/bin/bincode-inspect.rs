@@ -0,0 +1,484 @@
+//! Print the contents of a file with bincode-encoded data, using a schema of the encoded type.
+//!
+//! The schema file contains a [TypeSchema] that is encoded with `bincode::config::standard()`, e.g.:
+//!
+//! ```ignore
+//! let schema = bincode::schema_of::<MyType, _>(bincode::config::standard());
+//! std::fs::write("my_type.schema", bincode::encode_to_vec(schema, bincode::config::standard())?)?;
+//! ```
+//!
+//! Run `bincode-inspect --help` for the available options.
+
+use bincode::{
+    config::{self, DynConfig, Endian, IntEncoding},
+    de::{read::SliceReader, Decoder, DecoderImpl},
+    error::DecodeError,
+    schema::TypeSchema,
+    value::{Span, Value},
+};
+use std::{fmt::Write, ops::Range, process::ExitCode};
+
+const USAGE: &str = "\
+Usage: bincode-inspect [OPTIONS] <SCHEMA> <FILE>
+
+Print the values that are encoded in FILE as JSON or as an annotated hexdump.
+
+SCHEMA is a file with a `bincode::schema::TypeSchema` of the type of the values,
+encoded with `bincode::config::standard()`. FILE can contain multiple values that
+are encoded back-to-back.
+
+The values are decoded with the config that the schema was created with, unless
+one of the config options is given.
+
+Options:
+  --format <json|hexdump>                 The output format [default: json]
+  --config <standard|legacy>              Decode with the standard or legacy config
+  --endian <little|big>                   The byte order of integers and floats
+  --int-encoding <variable|fixed|leb128>  The encoding of integers
+  --array-length <skip|write>             If the length of fixed size arrays is encoded
+  -h, --help                              Print this message
+";
+
+/// The maximum amount of bytes that a value can claim, so an invalid length does not allocate too much memory
+const LIMIT: usize = 1 << 30;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Format {
+    Json,
+    Hexdump,
+}
+
+struct Options {
+    schema: String,
+    file: String,
+    format: Format,
+    endian: Option<Endian>,
+    int_encoding: Option<IntEncoding>,
+    skip_fixed_array_length: Option<bool>,
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            print!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match run(&options) {
+        Ok(output) => {
+            print!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Parse the command line arguments. Returns `None` if the help should be printed.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut paths = Vec::new();
+    let mut format = Format::Json;
+    let mut endian = None;
+    let mut int_encoding = None;
+    let mut skip_fixed_array_length = None;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("{} requires a value", name))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--format" => {
+                format = match value("--format")?.as_str() {
+                    "json" => Format::Json,
+                    "hexdump" => Format::Hexdump,
+                    other => return Err(format!("unknown format `{}`", other)),
+                }
+            }
+            "--config" => match value("--config")?.as_str() {
+                "standard" => {
+                    endian = Some(Endian::Little);
+                    int_encoding = Some(IntEncoding::Variable);
+                    skip_fixed_array_length = Some(true);
+                }
+                "legacy" => {
+                    endian = Some(Endian::Little);
+                    int_encoding = Some(IntEncoding::Fixed);
+                    skip_fixed_array_length = Some(false);
+                }
+                other => return Err(format!("unknown config `{}`", other)),
+            },
+            "--endian" => {
+                endian = Some(match value("--endian")?.as_str() {
+                    "little" => Endian::Little,
+                    "big" => Endian::Big,
+                    other => return Err(format!("unknown endian `{}`", other)),
+                })
+            }
+            "--int-encoding" => {
+                int_encoding = Some(match value("--int-encoding")?.as_str() {
+                    "variable" => IntEncoding::Variable,
+                    "fixed" => IntEncoding::Fixed,
+                    "leb128" => IntEncoding::Leb128,
+                    other => return Err(format!("unknown int encoding `{}`", other)),
+                })
+            }
+            "--array-length" => {
+                skip_fixed_array_length = Some(match value("--array-length")?.as_str() {
+                    "skip" => true,
+                    "write" => false,
+                    other => return Err(format!("unknown array length `{}`", other)),
+                })
+            }
+            other if other.starts_with('-') => return Err(format!("unknown option `{}`", other)),
+            _ => paths.push(arg),
+        }
+    }
+    match <[String; 2]>::try_from(paths) {
+        Ok([schema, file]) => Ok(Some(Options {
+            schema,
+            file,
+            format,
+            endian,
+            int_encoding,
+            skip_fixed_array_length,
+        })),
+        Err(_) => Err("expected a schema file and a data file".to_string()),
+    }
+}
+
+fn run(options: &Options) -> Result<String, String> {
+    let schema_bytes = std::fs::read(&options.schema)
+        .map_err(|error| format!("could not read `{}`: {}", options.schema, error))?;
+    let (schema, _): (TypeSchema, usize) =
+        bincode::decode_from_slice(&schema_bytes, config::standard())
+            .map_err(|error| format!("`{}` is not a valid schema: {}", options.schema, error))?;
+    let bytes = std::fs::read(&options.file)
+        .map_err(|error| format!("could not read `{}`: {}", options.file, error))?;
+
+    let config = DynConfig {
+        endian: options.endian.unwrap_or(schema.endian),
+        int_encoding: options.int_encoding.unwrap_or(schema.int_encoding),
+        skip_fixed_array_length: options
+            .skip_fixed_array_length
+            .unwrap_or(schema.skip_fixed_array_length),
+        limit: Some(LIMIT),
+        ..DynConfig::standard()
+    };
+    inspect(&bytes, &schema, options.format, config).map_err(|(offset, error)| {
+        format!(
+            "could not decode `{}` at byte {}: {}",
+            options.file, offset, error
+        )
+    })
+}
+
+/// Decode every value in `bytes` and format them. Returns the offset of the value that could not be decoded on error.
+fn inspect(
+    bytes: &[u8],
+    schema: &TypeSchema,
+    format: Format,
+    config: DynConfig,
+) -> Result<String, (usize, DecodeError)> {
+    let mut output = String::new();
+    let mut offset = 0;
+    while offset < bytes.len() {
+        // Every value is decoded separately, so the limit applies to every value
+        let mut decoder = DecoderImpl::new(SliceReader::new(&bytes[offset..]), config);
+        let (value, mut spans) = bincode::value::decode_with_spans(&mut decoder, schema)
+            .map_err(|error| (offset, error))?;
+        for span in &mut spans {
+            span.range = span.range.start + offset..span.range.end + offset;
+        }
+        match format {
+            Format::Json => {
+                write_json(&mut output, &value, 0);
+                output.push('\n');
+            }
+            Format::Hexdump => {
+                if offset > 0 {
+                    output.push('\n');
+                }
+                write_hexdump(&mut output, bytes, &value, &spans);
+            }
+        }
+        if decoder.offset() == 0 {
+            // The type is not encoded at all, so there is only one value
+            break;
+        }
+        offset += decoder.offset();
+    }
+    Ok(output)
+}
+
+/// Write the bytes of every value, next to its path and the value. The bytes that are encoded before the values that a value contains, e.g. the length of a sequence, are labeled separately.
+fn write_hexdump(output: &mut String, bytes: &[u8], value: &Value, spans: &[Span]) {
+    let mut values = Vec::new();
+    flatten(value, &mut values);
+    // Every value has a span, in the same order as the values are flattened
+    let entries: Vec<(&Span, &Value)> = spans
+        .iter()
+        .zip(values)
+        .filter(|(span, _)| !span.range.is_empty())
+        .collect();
+    let mut index = 0;
+    while index < entries.len() {
+        index = write_hexdump_entry(output, bytes, &entries, index);
+    }
+}
+
+/// Write the entry at `index` and the entries it contains. Returns the index of the next entry.
+fn write_hexdump_entry(
+    output: &mut String,
+    bytes: &[u8],
+    entries: &[(&Span, &Value)],
+    index: usize,
+) -> usize {
+    let (span, value) = entries[index];
+    let header = |range| {
+        let label = format!("${} ({})", span.path, header_name(value));
+        (range, label)
+    };
+    let mut cursor = span.range.start;
+    let mut next = index + 1;
+    while let Some((child, _)) = entries.get(next) {
+        if child.range.end > span.range.end {
+            break;
+        }
+        if cursor < child.range.start {
+            let (range, label) = header(cursor..child.range.start);
+            write_hexdump_line(output, bytes, range, &label);
+        }
+        cursor = child.range.end;
+        next = write_hexdump_entry(output, bytes, entries, next);
+    }
+    if next == index + 1 {
+        let mut label = format!("${} = ", span.path);
+        write_json(&mut label, value, 0);
+        write_hexdump_line(output, bytes, span.range.clone(), &label);
+    } else if cursor < span.range.end {
+        let (range, label) = header(cursor..span.range.end);
+        write_hexdump_line(output, bytes, range, &label);
+    }
+    next
+}
+
+fn write_hexdump_line(output: &mut String, bytes: &[u8], range: Range<usize>, label: &str) {
+    let start = range.start;
+    for (index, chunk) in bytes[range].chunks(16).enumerate() {
+        let mut line = format!("{:08x}  ", start + index * 16);
+        for byte in chunk {
+            write!(line, "{:02x} ", byte).unwrap();
+        }
+        if index == 0 {
+            write!(
+                line,
+                "{:width$}{}",
+                "",
+                label,
+                width = 3 * (16 - chunk.len()) + 1
+            )
+            .unwrap();
+        }
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+}
+
+/// The name of the bytes that are encoded before the values that `value` contains
+fn header_name(value: &Value) -> &'static str {
+    match value {
+        Value::Seq(_) | Value::Map(_) | Value::Bytes(_) | Value::String(_) => "length",
+        Value::Option(_) => "option",
+        Value::Enum { .. } => "variant",
+        _ => "header",
+    }
+}
+
+/// Add `value` and all values it contains to `values`, in the order in which they are encoded
+fn flatten<'a>(value: &'a Value, values: &mut Vec<&'a Value>) {
+    values.push(value);
+    match value {
+        Value::Option(Some(value)) => flatten(value, values),
+        Value::Seq(items) => items.iter().for_each(|item| flatten(item, values)),
+        Value::Map(entries) => entries.iter().for_each(|(key, value)| {
+            flatten(key, values);
+            flatten(value, values);
+        }),
+        Value::Struct(fields) | Value::Enum { fields, .. } => {
+            fields.iter().for_each(|(_, value)| flatten(value, values))
+        }
+        _ => {}
+    }
+}
+
+fn write_json(output: &mut String, value: &Value, indent: usize) {
+    match value {
+        Value::Unit | Value::Option(None) => output.push_str("null"),
+        Value::Bool(value) => write!(output, "{}", value).unwrap(),
+        Value::U8(value) => write!(output, "{}", value).unwrap(),
+        Value::U16(value) => write!(output, "{}", value).unwrap(),
+        Value::U32(value) => write!(output, "{}", value).unwrap(),
+        Value::U64(value) => write!(output, "{}", value).unwrap(),
+        Value::U128(value) => write!(output, "{}", value).unwrap(),
+        Value::I8(value) => write!(output, "{}", value).unwrap(),
+        Value::I16(value) => write!(output, "{}", value).unwrap(),
+        Value::I32(value) => write!(output, "{}", value).unwrap(),
+        Value::I64(value) => write!(output, "{}", value).unwrap(),
+        Value::I128(value) => write!(output, "{}", value).unwrap(),
+        Value::F32(value) => write_json_float(output, f64::from(*value)),
+        Value::F64(value) => write_json_float(output, *value),
+        Value::Char(value) => write_json_string(output, value.encode_utf8(&mut [0; 4])),
+        Value::String(value) => write_json_string(output, value),
+        Value::Bytes(bytes) => {
+            output.push('[');
+            for (index, byte) in bytes.iter().enumerate() {
+                if index > 0 {
+                    output.push_str(", ");
+                }
+                write!(output, "{}", byte).unwrap();
+            }
+            output.push(']');
+        }
+        Value::Raw(bytes) => write_json_string(output, &hex(bytes)),
+        Value::Option(Some(value)) => write_json(output, value, indent),
+        Value::Seq(items) => write_json_array(output, items.iter(), indent, write_json),
+        Value::Map(entries) => {
+            if entries
+                .iter()
+                .all(|(key, _)| matches!(key, Value::String(_)))
+            {
+                let fields = entries.iter().map(|(key, value)| match key {
+                    Value::String(key) => (key.as_str(), value),
+                    _ => unreachable!(),
+                });
+                write_json_object(output, fields, indent);
+            } else {
+                write_json_array(
+                    output,
+                    entries.iter(),
+                    indent,
+                    |output, (key, value), indent| {
+                        write_json_array(output, [key, value].into_iter(), indent, write_json)
+                    },
+                );
+            }
+        }
+        Value::Struct(fields) => write_json_object(
+            output,
+            fields.iter().map(|(name, value)| (name.as_str(), value)),
+            indent,
+        ),
+        Value::Enum { variant, fields } if fields.is_empty() => write_json_string(output, variant),
+        Value::Enum { variant, fields } => {
+            // Enums are written as an object with the variant as its only key
+            output.push_str("{\n");
+            write_indent(output, indent + 1);
+            write_json_string(output, variant);
+            output.push_str(": ");
+            write_json_object(
+                output,
+                fields.iter().map(|(name, value)| (name.as_str(), value)),
+                indent + 1,
+            );
+            output.push('\n');
+            write_indent(output, indent);
+            output.push('}');
+        }
+    }
+}
+
+fn write_json_array<T>(
+    output: &mut String,
+    items: impl ExactSizeIterator<Item = T>,
+    indent: usize,
+    write_item: impl Fn(&mut String, T, usize),
+) {
+    write_json_list(output, ('[', ']'), items, indent, write_item);
+}
+
+fn write_json_object<'a>(
+    output: &mut String,
+    fields: impl ExactSizeIterator<Item = (&'a str, &'a Value)>,
+    indent: usize,
+) {
+    write_json_list(
+        output,
+        ('{', '}'),
+        fields,
+        indent,
+        |output, (name, value), indent| {
+            write_json_string(output, name);
+            output.push_str(": ");
+            write_json(output, value, indent);
+        },
+    );
+}
+
+/// Write every item on its own line, surrounded by the `(open, close)` delimiters
+fn write_json_list<T>(
+    output: &mut String,
+    (open, close): (char, char),
+    items: impl ExactSizeIterator<Item = T>,
+    indent: usize,
+    write_item: impl Fn(&mut String, T, usize),
+) {
+    output.push(open);
+    if items.len() == 0 {
+        output.push(close);
+        return;
+    }
+    output.push('\n');
+    for (index, item) in items.enumerate() {
+        if index > 0 {
+            output.push_str(",\n");
+        }
+        write_indent(output, indent + 1);
+        write_item(output, item, indent + 1);
+    }
+    output.push('\n');
+    write_indent(output, indent);
+    output.push(close);
+}
+
+fn write_json_float(output: &mut String, value: f64) {
+    if value.is_finite() {
+        write!(output, "{:?}", value).unwrap();
+    } else {
+        // JSON has no representation for infinity and NaN
+        write_json_string(output, &value.to_string());
+    }
+}
+
+fn write_json_string(output: &mut String, value: &str) {
+    output.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(output, "\\u{:04x}", c as u32).unwrap(),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+fn write_indent(output: &mut String, indent: usize) {
+    for _ in 0..indent {
+        output.push_str("  ");
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
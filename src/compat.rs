@@ -0,0 +1,521 @@
+//! Check if a new version of a type can read the data that was written by an old version.
+//!
+//! [check] compares two [TypeSchema]s, usually created with [schema_of](crate::schema_of) for the old and new version of a type, and reports every change that breaks decoding the old data with the new type. This can be used in tests, to make sure a change to a type does not break existing data:
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # {
+//! use bincode::compat::{Incompatibility, IncompatibilityKind};
+//! use bincode::schema::Type;
+//!
+//! #[derive(bincode::Schema)]
+//! struct SettingsV1 {
+//!     volume: u8,
+//!     brightness: u16,
+//! }
+//!
+//! #[derive(bincode::Schema)]
+//! struct SettingsV2 {
+//!     volume: u8,
+//!     // Widening an integer is fine with the variable int encoding of the standard config
+//!     brightness: u32,
+//! }
+//!
+//! #[derive(bincode::Schema)]
+//! struct SettingsV3 {
+//!     volume: u16,
+//!     brightness: u32,
+//! }
+//!
+//! let config = bincode::config::standard();
+//! let v1 = bincode::schema_of::<SettingsV1, _>(config);
+//! let v2 = bincode::schema_of::<SettingsV2, _>(config);
+//! let v3 = bincode::schema_of::<SettingsV3, _>(config);
+//!
+//! assert_eq!(bincode::compat::check(&v1, &v2), Ok(()));
+//! // An u8 is always a single byte, so it can not be widened
+//! assert_eq!(
+//!     bincode::compat::check(&v2, &v3),
+//!     Err(vec![Incompatibility {
+//!         path: String::from(".volume"),
+//!         kind: IncompatibilityKind::TypeChanged {
+//!             old: Type::U8,
+//!             new: Type::U16,
+//!         },
+//!     }])
+//! );
+//! # }
+//! ```
+//!
+//! The check is based on the schemas only. Types with `#[bincode(version = N)]` decode older versions with `#[bincode(migrate_from = "T")]`, which is not part of the schema, so the structure of an older version is not compared. Custom encodings, like `#[bincode(encode_with = "..")]`, are only compared by the name of their type.
+
+use crate::schema::{
+    Definition, EnumSchema, Field, IntEncoding, StructLayout, StructSchema, Type, TypeSchema,
+};
+use alloc::{
+    collections::BTreeSet,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Check if data that was encoded with `old` can be decoded with `new`.
+///
+/// Returns every change that breaks this. See the [module documentation](crate::compat) for more information.
+pub fn check(old: &TypeSchema, new: &TypeSchema) -> Result<(), Vec<Incompatibility>> {
+    let mut checker = Checker {
+        old,
+        new,
+        compared: BTreeSet::new(),
+        result: Vec::new(),
+    };
+    if old.endian != new.endian {
+        checker.report("", IncompatibilityKind::ConfigChanged { option: "endian" });
+    }
+    if old.int_encoding != new.int_encoding {
+        checker.report(
+            "",
+            IncompatibilityKind::ConfigChanged {
+                option: "int_encoding",
+            },
+        );
+    }
+    if old.skip_fixed_array_length != new.skip_fixed_array_length {
+        checker.report(
+            "",
+            IncompatibilityKind::ConfigChanged {
+                option: "skip_fixed_array_length",
+            },
+        );
+    }
+    checker.check_type("", &old.root, &new.root);
+    if checker.result.is_empty() {
+        Ok(())
+    } else {
+        Err(checker.result)
+    }
+}
+
+/// A change that prevents the new version of a type from decoding data of the old version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Incompatibility {
+    /// The location of the change, e.g. `.settings.volume`, `::Variant.0` or `[]` for the items of a sequence. This is empty for the type that was checked.
+    pub path: String,
+    /// The change
+    pub kind: IncompatibilityKind,
+}
+
+impl core::fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{:?}", self.kind)
+        } else {
+            write!(f, "{}: {:?}", self.path, self.kind)
+        }
+    }
+}
+
+/// The kind of an [Incompatibility].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IncompatibilityKind {
+    /// An option of the configuration, e.g. `int_encoding`, is different
+    ConfigChanged {
+        /// The name of the option
+        option: &'static str,
+    },
+    /// The type changed in a way that is encoded differently, e.g. an integer with a different width in a fixed int encoding
+    TypeChanged {
+        /// The old type
+        old: Type,
+        /// The new type
+        new: Type,
+    },
+    /// The length of an array changed
+    ArrayLengthChanged {
+        /// The old length
+        old: usize,
+        /// The new length
+        new: usize,
+    },
+    /// The amount of items in a tuple changed
+    TupleLengthChanged {
+        /// The old amount of items
+        old: usize,
+        /// The new amount of items
+        new: usize,
+    },
+    /// The layout of a struct changed, e.g. `#[bincode(tagged)]` was added
+    LayoutChanged {
+        /// The old layout
+        old: StructLayout,
+        /// The new layout
+        new: StructLayout,
+    },
+    /// A struct or enum has a version in only one of the schemas
+    VersionChanged {
+        /// The old version
+        old: Option<u32>,
+        /// The new version
+        new: Option<u32>,
+    },
+    /// The new version of a type is older than the old version
+    VersionDowngraded {
+        /// The old version
+        old: u32,
+        /// The new version
+        new: u32,
+    },
+    /// A field was added. This is only allowed at the end of a struct with `#[bincode(default_trailing)]`, or in a struct with `#[bincode(tagged)]`.
+    FieldAdded {
+        /// The name of the field
+        name: String,
+    },
+    /// A field was removed. This is only allowed at the end of a struct with `#[bincode(default_trailing)]`, or in a struct with `#[bincode(tagged)]`.
+    FieldRemoved {
+        /// The name of the field
+        name: String,
+    },
+    /// A field moved to a different position
+    FieldMoved {
+        /// The name of the field
+        name: String,
+        /// The old position
+        old: usize,
+        /// The new position
+        new: usize,
+    },
+    /// The integer type of the variant index of an enum changed
+    TagTypeChanged {
+        /// The old type
+        old: Type,
+        /// The new type
+        new: Type,
+    },
+    /// A variant of an enum was removed
+    VariantRemoved {
+        /// The name of the variant
+        name: String,
+    },
+    /// The index of a variant changed, e.g. because a variant was inserted before it
+    VariantIndexChanged {
+        /// The name of the variant
+        name: String,
+        /// The old index
+        old: u64,
+        /// The new index
+        new: u64,
+    },
+    /// A [Type::Named] refers to a definition that does not exist in its schema
+    MissingDefinition {
+        /// The name of the definition
+        name: String,
+    },
+}
+
+struct Checker<'a> {
+    old: &'a TypeSchema,
+    new: &'a TypeSchema,
+    /// The pairs of old and new definitions that are being compared or have been compared, so recursive types are only compared once
+    compared: BTreeSet<(&'a str, &'a str)>,
+    result: Vec<Incompatibility>,
+}
+
+impl<'a> Checker<'a> {
+    fn report(&mut self, path: &str, kind: IncompatibilityKind) {
+        self.result.push(Incompatibility {
+            path: path.to_string(),
+            kind,
+        });
+    }
+
+    fn check_type(&mut self, path: &str, old: &'a Type, new: &'a Type) {
+        match (old, new) {
+            (Type::Option(old), Type::Option(new)) => self.check_type(path, old, new),
+            (Type::Seq(old), Type::Seq(new)) => self.check_type(&format!("{}[]", path), old, new),
+            (
+                Type::Map {
+                    key: old_key,
+                    value: old_value,
+                },
+                Type::Map {
+                    key: new_key,
+                    value: new_value,
+                },
+            ) => {
+                self.check_type(&format!("{}.key", path), old_key, new_key);
+                self.check_type(&format!("{}.value", path), old_value, new_value);
+            }
+            (
+                Type::Array {
+                    item: old_item,
+                    len: old_len,
+                },
+                Type::Array {
+                    item: new_item,
+                    len: new_len,
+                },
+            ) => {
+                if old_len != new_len {
+                    self.report(
+                        path,
+                        IncompatibilityKind::ArrayLengthChanged {
+                            old: *old_len,
+                            new: *new_len,
+                        },
+                    );
+                }
+                self.check_type(&format!("{}[]", path), old_item, new_item);
+            }
+            (Type::Tuple(old), Type::Tuple(new)) => {
+                if old.len() != new.len() {
+                    self.report(
+                        path,
+                        IncompatibilityKind::TupleLengthChanged {
+                            old: old.len(),
+                            new: new.len(),
+                        },
+                    );
+                }
+                for (index, (old, new)) in old.iter().zip(new).enumerate() {
+                    self.check_type(&format!("{}.{}", path, index), old, new);
+                }
+            }
+            (Type::Named(old), Type::Named(new)) => self.check_definition(path, old, new),
+            (old, new) if !self.is_readable_as(old, new) => {
+                self.report(
+                    path,
+                    IncompatibilityKind::TypeChanged {
+                        old: old.clone(),
+                        new: new.clone(),
+                    },
+                );
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns `true` if a value of the type `old` can be read as `new`, for types that are not compared by their contents
+    fn is_readable_as(&self, old: &Type, new: &Type) -> bool {
+        if old == new {
+            return true;
+        }
+        // Varints and LEB128 integers of every width are encoded the same, so an integer can be read by a wider integer of the same signedness. u8 and i8 are always a single byte.
+        let variable = self.old.int_encoding == self.new.int_encoding
+            && self.old.int_encoding != IntEncoding::Fixed;
+        match (varint_width(old), varint_width(new)) {
+            (Some((old_signed, old_width)), Some((new_signed, new_width))) => {
+                variable && old_signed == new_signed && old_width <= new_width
+            }
+            _ => false,
+        }
+    }
+
+    fn check_definition(&mut self, path: &str, old_name: &'a str, new_name: &'a str) {
+        if !self.compared.insert((old_name, new_name)) {
+            return;
+        }
+        let old = match self.old.definitions.get(old_name) {
+            Some(old) => old,
+            None => {
+                self.report(
+                    path,
+                    IncompatibilityKind::MissingDefinition {
+                        name: old_name.to_string(),
+                    },
+                );
+                return;
+            }
+        };
+        let new = match self.new.definitions.get(new_name) {
+            Some(new) => new,
+            None => {
+                self.report(
+                    path,
+                    IncompatibilityKind::MissingDefinition {
+                        name: new_name.to_string(),
+                    },
+                );
+                return;
+            }
+        };
+        match (old, new) {
+            (Definition::Struct(old), Definition::Struct(new)) => self.check_struct(path, old, new),
+            (Definition::Enum(old), Definition::Enum(new)) => self.check_enum(path, old, new),
+            _ => self.report(
+                path,
+                IncompatibilityKind::TypeChanged {
+                    old: Type::Named(old_name.to_string()),
+                    new: Type::Named(new_name.to_string()),
+                },
+            ),
+        }
+    }
+
+    /// Check the versions of a struct or enum. Returns `true` if the contents should be compared.
+    fn check_version(&mut self, path: &str, old: Option<u32>, new: Option<u32>) -> bool {
+        match (old, new) {
+            (None, None) => true,
+            (Some(old), Some(new)) if old == new => true,
+            // The old version is migrated with `migrate_from`, which is not part of the schema
+            (Some(old), Some(new)) if old < new => false,
+            (Some(old), Some(new)) => {
+                self.report(path, IncompatibilityKind::VersionDowngraded { old, new });
+                false
+            }
+            (old, new) => {
+                self.report(path, IncompatibilityKind::VersionChanged { old, new });
+                false
+            }
+        }
+    }
+
+    fn check_struct(&mut self, path: &str, old: &'a StructSchema, new: &'a StructSchema) {
+        if !self.check_version(path, old.version, new.version) {
+            return;
+        }
+        if old.layout != new.layout {
+            self.report(
+                path,
+                IncompatibilityKind::LayoutChanged {
+                    old: old.layout,
+                    new: new.layout,
+                },
+            );
+            return;
+        }
+        match old.layout {
+            StructLayout::Sequential => self.check_fields(path, &old.fields, &new.fields, false),
+            StructLayout::DefaultTrailing => {
+                self.check_fields(path, &old.fields, &new.fields, true)
+            }
+            StructLayout::Tagged => {
+                // Fields are matched by their tag. Unknown tags are skipped, and missing tags are set to their default value.
+                for old_field in &old.fields {
+                    if let Some(new_field) = new
+                        .fields
+                        .iter()
+                        .find(|new_field| new_field.tag == old_field.tag)
+                    {
+                        self.check_type(
+                            &format!("{}.{}", path, new_field.name),
+                            &old_field.ty,
+                            &new_field.ty,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Compare fields that are encoded in order. If `trailing` is set, fields can be added and removed at the end.
+    fn check_fields(&mut self, path: &str, old: &'a [Field], new: &'a [Field], trailing: bool) {
+        for (index, old_field) in old.iter().enumerate() {
+            let moved_to = new
+                .iter()
+                .position(|new_field| new_field.name == old_field.name)
+                .filter(|new_index| *new_index != index);
+            if let Some(new_index) = moved_to {
+                // Tuple fields are named by their position, so they can not move
+                self.report(
+                    path,
+                    IncompatibilityKind::FieldMoved {
+                        name: old_field.name.clone(),
+                        old: index,
+                        new: new_index,
+                    },
+                );
+            }
+            match new.get(index) {
+                Some(new_field) if moved_to.is_none() => self.check_type(
+                    &format!("{}.{}", path, new_field.name),
+                    &old_field.ty,
+                    &new_field.ty,
+                ),
+                Some(_) => {}
+                None if trailing => {}
+                None => self.report(
+                    path,
+                    IncompatibilityKind::FieldRemoved {
+                        name: old_field.name.clone(),
+                    },
+                ),
+            }
+        }
+        if !trailing {
+            for new_field in new.iter().skip(old.len()) {
+                self.report(
+                    path,
+                    IncompatibilityKind::FieldAdded {
+                        name: new_field.name.clone(),
+                    },
+                );
+            }
+        }
+    }
+
+    fn check_enum(&mut self, path: &str, old: &'a EnumSchema, new: &'a EnumSchema) {
+        if !self.check_version(path, old.version, new.version) {
+            return;
+        }
+        if !self.is_readable_as(&old.tag_type, &new.tag_type) {
+            self.report(
+                path,
+                IncompatibilityKind::TagTypeChanged {
+                    old: old.tag_type.clone(),
+                    new: new.tag_type.clone(),
+                },
+            );
+        }
+        // Variants are matched by name. New variants can be added, as long as the index of the old variants stays the same.
+        for old_variant in &old.variants {
+            let new_variant = match new
+                .variants
+                .iter()
+                .find(|new_variant| new_variant.name == old_variant.name)
+            {
+                Some(new_variant) => new_variant,
+                None => {
+                    self.report(
+                        path,
+                        IncompatibilityKind::VariantRemoved {
+                            name: old_variant.name.clone(),
+                        },
+                    );
+                    continue;
+                }
+            };
+            if new_variant.index != old_variant.index {
+                self.report(
+                    path,
+                    IncompatibilityKind::VariantIndexChanged {
+                        name: old_variant.name.clone(),
+                        old: old_variant.index,
+                        new: new_variant.index,
+                    },
+                );
+            }
+            self.check_fields(
+                &format!("{}::{}", path, old_variant.name),
+                &old_variant.fields,
+                &new_variant.fields,
+                false,
+            );
+        }
+    }
+}
+
+/// The signedness and width in bytes of an integer that is encoded as a varint with [IntEncoding::Variable] or [IntEncoding::Leb128]
+fn varint_width(ty: &Type) -> Option<(bool, u8)> {
+    match ty {
+        Type::U16 => Some((false, 2)),
+        Type::U32 => Some((false, 4)),
+        Type::U64 => Some((false, 8)),
+        Type::U128 => Some((false, 16)),
+        Type::I16 => Some((true, 2)),
+        Type::I32 => Some((true, 4)),
+        Type::I64 => Some((true, 8)),
+        Type::I128 => Some((true, 16)),
+        _ => None,
+    }
+}
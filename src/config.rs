@@ -12,12 +12,19 @@
 //!     // pick one of:
 //!     .with_variable_int_encoding()
 //!     .with_fixed_int_encoding()
+//!     .with_leb128_int_encoding()
 //!     // pick one of:
 //!     .skip_fixed_array_length()
-//!     .write_fixed_array_length();
+//!     .write_fixed_array_length()
+//!     // pick one of:
+//!     .with_max_container_len::<10_000>()
+//!     .with_no_max_container_len()
+//!     // pick one of:
+//!     .with_max_depth::<128>()
+//!     .with_no_max_depth();
 //! ```
 //!
-//! See [Configuration] for more information on the configuration options.
+//! See [Configuration] for more information on the configuration options. To choose the options at runtime, use a [DynConfig] instead.
 
 pub(crate) use self::internal::*;
 use core::marker::PhantomData;
@@ -28,22 +35,40 @@ use core::marker::PhantomData;
 /// The following methods are mutually exclusive and will overwrite each other. The last call to one of these methods determines the behavior of the configuration:
 ///
 /// - [with_little_endian] and [with_big_endian]
-/// - [with_fixed_int_encoding] and [with_variable_int_encoding]
+/// - [with_fixed_int_encoding], [with_variable_int_encoding] and [with_leb128_int_encoding]
 /// - [skip_fixed_array_length] and [write_fixed_array_length]
-///
+/// - [with_limit] and [with_no_limit]
+/// - [with_max_container_len] and [with_no_max_container_len]
+/// - [with_max_depth] and [with_no_max_depth]
 ///
 /// [with_little_endian]: #method.with_little_endian
 /// [with_big_endian]: #method.with_big_endian
 /// [with_fixed_int_encoding]: #method.with_fixed_int_encoding
 /// [with_variable_int_encoding]: #method.with_variable_int_encoding
+/// [with_leb128_int_encoding]: #method.with_leb128_int_encoding
 /// [skip_fixed_array_length]: #method.skip_fixed_array_length
 /// [write_fixed_array_length]: #method.write_fixed_array_length
+/// [with_limit]: #method.with_limit
+/// [with_no_limit]: #method.with_no_limit
+/// [with_max_container_len]: #method.with_max_container_len
+/// [with_no_max_container_len]: #method.with_no_max_container_len
+/// [with_max_depth]: #method.with_max_depth
+/// [with_no_max_depth]: #method.with_no_max_depth
 #[derive(Copy, Clone)]
-pub struct Configuration<E = LittleEndian, I = Varint, A = SkipFixedArrayLength, L = NoLimit> {
+pub struct Configuration<
+    E = LittleEndian,
+    I = Varint,
+    A = SkipFixedArrayLength,
+    L = NoLimit,
+    M = NoMaxContainerLen,
+    D = NoMaxDepth,
+> {
     _e: PhantomData<E>,
     _i: PhantomData<I>,
     _a: PhantomData<A>,
     _l: PhantomData<L>,
+    _m: PhantomData<M>,
+    _d: PhantomData<D>,
 }
 
 /// The default config for bincode 2.0. By default this will be:
@@ -62,32 +87,35 @@ pub const fn legacy() -> Configuration<LittleEndian, Fixint, WriteFixedArrayLeng
     generate()
 }
 
-const fn generate<_E, _I, _A, _L>() -> Configuration<_E, _I, _A, _L> {
+const fn generate<_E, _I, _A, _L, _M, _D>() -> Configuration<_E, _I, _A, _L, _M, _D> {
     Configuration {
         _e: PhantomData,
         _i: PhantomData,
         _a: PhantomData,
         _l: PhantomData,
+        _m: PhantomData,
+        _d: PhantomData,
     }
 }
 
 // When adding more features to configuration, follow these steps:
 // - Create 2 or more structs that can be used as a type (e.g. Limit and NoLimit)
 // - Add an `Internal...Config` to the `internal` module
-// - Make sure `Config` and `impl<T> Config for T` extend from this new trait
+// - Add a method for the option to `InternalConfig`, and implement it for `Configuration` and `DynConfig`
+// - Add a field for the option to `DynConfig`
 // - Add a generic to `Configuration`
 // - Add this generic to `const fn generate<...>()`
 // - Add this generic to _every_ function in `Configuration`
 // - Add your new methods
 
-impl<E, I, A, L> Configuration<E, I, A, L> {
+impl<E, I, A, L, M, D> Configuration<E, I, A, L, M, D> {
     /// Makes bincode encode all integer types in big endian.
-    pub const fn with_big_endian(self) -> Configuration<BigEndian, I, A, L> {
+    pub const fn with_big_endian(self) -> Configuration<BigEndian, I, A, L, M, D> {
         generate()
     }
 
     /// Makes bincode encode all integer types in little endian.
-    pub const fn with_little_endian(self) -> Configuration<LittleEndian, I, A, L> {
+    pub const fn with_little_endian(self) -> Configuration<LittleEndian, I, A, L, M, D> {
         generate()
     }
 
@@ -100,7 +128,7 @@ impl<E, I, A, L> Configuration<E, I, A, L> {
     /// 3. If `2**16 <= u < 2**32`, encode it as a literal byte 252, followed by a u32 with value `u`.
     /// 4. If `2**32 <= u < 2**64`, encode it as a literal byte 253, followed by a u64 with value `u`.
     /// 5. If `2**64 <= u < 2**128`, encode it as a literal byte 254, followed by a
-    ///   u128 with value `u`.
+    ///    u128 with value `u`.
     ///
     /// Then, for signed integers, we first convert to unsigned using the zigzag algorithm,
     /// and then encode them as we do for unsigned integers generally. The reason we use this
@@ -148,7 +176,7 @@ impl<E, I, A, L> Configuration<E, I, A, L> {
     ///
     /// Note that u256 and the like are unsupported by this format; if and when they are added to the
     /// language, they may be supported via the extension point given by the 255 byte.
-    pub const fn with_variable_int_encoding(self) -> Configuration<E, Varint, A, L> {
+    pub const fn with_variable_int_encoding(self) -> Configuration<E, Varint, A, L, M, D> {
         generate()
     }
 
@@ -157,50 +185,229 @@ impl<E, I, A, L> Configuration<E, I, A, L> {
     /// * Fixed size integers are encoded directly
     /// * Enum discriminants are encoded as u32
     /// * Lengths and usize are encoded as u64
-    pub const fn with_fixed_int_encoding(self) -> Configuration<E, Fixint, A, L> {
+    pub const fn with_fixed_int_encoding(self) -> Configuration<E, Fixint, A, L, M, D> {
+        generate()
+    }
+
+    /// Makes bincode encode all integer types (excepting u8 and i8) as unsigned LEB128, the varint format of Protocol Buffers, WebAssembly and DWARF.
+    ///
+    /// Every byte holds 7 bits of the integer, starting with the least significant bits. The highest bit of a byte is set if more bytes follow. Signed integers are first converted to unsigned with the zigzag algorithm of [with_variable_int_encoding], like the `sint32` and `sint64` types of Protocol Buffers.
+    ///
+    /// LEB128 has no byte order, so the endianness of the configuration only affects floats and fixed-size values.
+    ///
+    /// ```
+    /// let config = bincode::config::standard().with_leb128_int_encoding();
+    /// let mut buffer = [0u8; 16];
+    /// let len = bincode::encode_into_slice(300u32, &mut buffer, config).unwrap();
+    /// assert_eq!(&buffer[..len], [0xAC, 0x02]);
+    /// let len = bincode::encode_into_slice(-2i64, &mut buffer, config).unwrap();
+    /// assert_eq!(&buffer[..len], [0x03]);
+    /// ```
+    ///
+    /// [with_variable_int_encoding]: #method.with_variable_int_encoding
+    pub const fn with_leb128_int_encoding(self) -> Configuration<E, Leb128, A, L, M, D> {
         generate()
     }
 
     /// Skip writing the length of fixed size arrays (`[u8; N]`) before writing the array
-    pub const fn skip_fixed_array_length(self) -> Configuration<E, I, SkipFixedArrayLength, L> {
+    pub const fn skip_fixed_array_length(
+        self,
+    ) -> Configuration<E, I, SkipFixedArrayLength, L, M, D> {
         generate()
     }
 
     /// Write the length of fixed size arrays (`[u8; N]`) before writing the array
-    pub const fn write_fixed_array_length(self) -> Configuration<E, I, WriteFixedArrayLength, L> {
+    pub const fn write_fixed_array_length(
+        self,
+    ) -> Configuration<E, I, WriteFixedArrayLength, L, M, D> {
         generate()
     }
 
     /// Sets the byte limit to `limit`.
-    pub const fn with_limit<const N: usize>(self) -> Configuration<E, I, A, Limit<N>> {
+    pub const fn with_limit<const N: usize>(self) -> Configuration<E, I, A, Limit<N>, M, D> {
         generate()
     }
 
     /// Clear the byte limit.
-    pub const fn with_no_limit(self) -> Configuration<E, I, A, NoLimit> {
+    pub const fn with_no_limit(self) -> Configuration<E, I, A, NoLimit, M, D> {
+        generate()
+    }
+
+    /// Sets the maximum length of containers to `N`.
+    ///
+    /// When decoding a container (e.g. a `Vec`, `String`, `HashMap` or `&[u8]`) with more than `N` entries, `DecodeError::ContainerLengthExceeded` is returned before the container is allocated. Unlike [with_limit], this also protects against huge containers of small or zero-sized items.
+    ///
+    /// This does not apply to fixed size arrays (`[T; N]`), as their length is determined by their type.
+    ///
+    /// [with_limit]: #method.with_limit
+    pub const fn with_max_container_len<const N: usize>(
+        self,
+    ) -> Configuration<E, I, A, L, MaxContainerLen<N>, D> {
+        generate()
+    }
+
+    /// Clear the maximum container length.
+    pub const fn with_no_max_container_len(
+        self,
+    ) -> Configuration<E, I, A, L, NoMaxContainerLen, D> {
+        generate()
+    }
+
+    /// Sets the maximum nesting depth to `N`.
+    ///
+    /// Every value with a derived `Decode` or `BorrowDecode` implementation, and every struct, enum, tuple, sequence and map that is decoded through serde, counts as one level of nesting. When decoding a value that is nested deeper than `N` levels, `DecodeError::DepthLimitExceeded` is returned. This prevents recursive types (e.g. a tree of `Box<Node>`) from overflowing the stack when decoding untrusted input.
+    pub const fn with_max_depth<const N: usize>(self) -> Configuration<E, I, A, L, M, MaxDepth<N>> {
+        generate()
+    }
+
+    /// Clear the maximum nesting depth.
+    pub const fn with_no_max_depth(self) -> Configuration<E, I, A, L, M, NoMaxDepth> {
         generate()
     }
 }
 
 /// Indicates a type is valid for controlling the bincode configuration
-pub trait Config:
-    InternalEndianConfig
-    + InternalArrayLengthConfig
-    + InternalIntEncodingConfig
-    + InternalLimitConfig
-    + Copy
-    + Clone
-{
+///
+/// This is implemented by [Configuration], whose options are known at compile time, and by [DynConfig], whose options are chosen at runtime.
+pub trait Config: InternalConfig + Copy + Clone {}
+
+impl<T> Config for T where T: InternalConfig + Copy + Clone {}
+
+/// The byte order of a configuration.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Endian {
+    /// Little endian, see [Configuration::with_little_endian]
+    Little,
+    /// Big endian, see [Configuration::with_big_endian]
+    Big,
+}
+
+/// The integer encoding of a configuration.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IntEncoding {
+    /// Every integer has the size of its type, see [Configuration::with_fixed_int_encoding]
+    Fixed,
+    /// Integers are encoded as varints, see [Configuration::with_variable_int_encoding]
+    Variable,
+    /// Integers are encoded as LEB128, see [Configuration::with_leb128_int_encoding]
+    Leb128,
+}
+
+/// A configuration whose options are ordinary fields, so they can be chosen at runtime.
+///
+/// Every [Configuration] is a different type, so picking e.g. the endianness from a config file means that every combination of options has to be written out. A `DynConfig` has the same options as a [Configuration] and can be used in the same places, e.g. with [encode_into_slice] and [decode_from_slice]:
+///
+/// ```
+/// use bincode::config::{DynConfig, Endian};
+///
+/// let big_endian = true; // e.g. read from a protocol handshake
+///
+/// let mut config = DynConfig::standard();
+/// if big_endian {
+///     config.endian = Endian::Big;
+/// }
+/// config.limit = Some(1024);
+///
+/// let mut bytes = [0u8; 4];
+/// bincode::encode_into_slice(1.5f32, &mut bytes, config).unwrap();
+/// assert_eq!(bytes, [0x3F, 0xC0, 0x00, 0x00]);
+/// let (decoded, _): (f32, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+/// assert_eq!(decoded, 1.5);
+///
+/// // The same options as the equivalent `Configuration`
+/// let static_config = bincode::config::standard().with_big_endian().with_limit::<1024>();
+/// assert_eq!(DynConfig::from_config(static_config), config);
+/// ```
+///
+/// The options are checked every time they are used, so a `DynConfig` is slightly slower than a [Configuration]. [max_encoded_size] is a `const fn` and can only be used with a [Configuration].
+///
+/// [encode_into_slice]: ../fn.encode_into_slice.html
+/// [decode_from_slice]: ../fn.decode_from_slice.html
+/// [max_encoded_size]: ../fn.max_encoded_size.html
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DynConfig {
+    /// The byte order of integers and floats, see [Configuration::with_big_endian] and [Configuration::with_little_endian]
+    pub endian: Endian,
+    /// The encoding of integers, see [Configuration::with_fixed_int_encoding], [Configuration::with_variable_int_encoding] and [Configuration::with_leb128_int_encoding]
+    pub int_encoding: IntEncoding,
+    /// If the length of fixed size arrays is skipped, see [Configuration::skip_fixed_array_length]
+    pub skip_fixed_array_length: bool,
+    /// The byte limit, see [Configuration::with_limit]
+    pub limit: Option<usize>,
+    /// The maximum length of containers, see [Configuration::with_max_container_len]
+    pub max_container_len: Option<usize>,
+    /// The maximum nesting depth, see [Configuration::with_max_depth]
+    pub max_depth: Option<usize>,
 }
 
-impl<T> Config for T where
-    T: InternalEndianConfig
-        + InternalArrayLengthConfig
-        + InternalIntEncodingConfig
-        + InternalLimitConfig
-        + Copy
-        + Clone
-{
+impl DynConfig {
+    /// The options of [standard]
+    pub const fn standard() -> Self {
+        Self {
+            endian: Endian::Little,
+            int_encoding: IntEncoding::Variable,
+            skip_fixed_array_length: true,
+            limit: None,
+            max_container_len: None,
+            max_depth: None,
+        }
+    }
+
+    /// The options of [legacy]
+    pub const fn legacy() -> Self {
+        Self {
+            endian: Endian::Little,
+            int_encoding: IntEncoding::Fixed,
+            skip_fixed_array_length: false,
+            limit: None,
+            max_container_len: None,
+            max_depth: None,
+        }
+    }
+
+    /// The options of any other config, e.g. a [Configuration].
+    pub fn from_config<C: Config>(config: C) -> Self {
+        Self {
+            endian: config.endian(),
+            int_encoding: config.int_encoding(),
+            skip_fixed_array_length: config.skip_fixed_array_length(),
+            limit: config.limit(),
+            max_container_len: config.max_container_len(),
+            max_depth: config.max_depth(),
+        }
+    }
+}
+
+impl Default for DynConfig {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl InternalConfig for DynConfig {
+    fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    fn int_encoding(&self) -> IntEncoding {
+        self.int_encoding
+    }
+
+    fn skip_fixed_array_length(&self) -> bool {
+        self.skip_fixed_array_length
+    }
+
+    fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    fn max_container_len(&self) -> Option<usize> {
+        self.max_container_len
+    }
+
+    fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
 }
 
 #[doc(hidden)]
@@ -235,6 +442,14 @@ impl InternalIntEncodingConfig for Varint {
     const INT_ENCODING: IntEncoding = IntEncoding::Variable;
 }
 
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct Leb128 {}
+
+impl InternalIntEncodingConfig for Leb128 {
+    const INT_ENCODING: IntEncoding = IntEncoding::Leb128;
+}
+
 #[doc(hidden)]
 #[derive(Copy, Clone)]
 pub struct SkipFixedArrayLength {}
@@ -265,45 +480,114 @@ impl<const N: usize> InternalLimitConfig for Limit<N> {
     const LIMIT: Option<usize> = Some(N);
 }
 
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct NoMaxContainerLen {}
+impl InternalContainerLenConfig for NoMaxContainerLen {
+    const MAX_CONTAINER_LEN: Option<usize> = None;
+}
+
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct MaxContainerLen<const N: usize> {}
+impl<const N: usize> InternalContainerLenConfig for MaxContainerLen<N> {
+    const MAX_CONTAINER_LEN: Option<usize> = Some(N);
+}
+
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct NoMaxDepth {}
+impl InternalDepthConfig for NoMaxDepth {
+    const MAX_DEPTH: Option<usize> = None;
+}
+
+#[doc(hidden)]
+#[derive(Copy, Clone)]
+pub struct MaxDepth<const N: usize> {}
+impl<const N: usize> InternalDepthConfig for MaxDepth<N> {
+    const MAX_DEPTH: Option<usize> = Some(N);
+}
+
 mod internal {
-    use super::Configuration;
+    use super::{Configuration, Endian, IntEncoding};
+
+    /// The options of a config. The encoding and decoding logic only reads the options through this trait.
+    pub trait InternalConfig {
+        fn endian(&self) -> Endian;
+        fn int_encoding(&self) -> IntEncoding;
+        fn skip_fixed_array_length(&self) -> bool;
+        fn limit(&self) -> Option<usize>;
+        fn max_container_len(&self) -> Option<usize>;
+        fn max_depth(&self) -> Option<usize>;
+    }
+
+    // The options of a `Configuration` are consts, so every check of an option should get compiled away
+    impl<E, I, A, L, M, D> InternalConfig for Configuration<E, I, A, L, M, D>
+    where
+        E: InternalEndianConfig,
+        I: InternalIntEncodingConfig,
+        A: InternalArrayLengthConfig,
+        L: InternalLimitConfig,
+        M: InternalContainerLenConfig,
+        D: InternalDepthConfig,
+    {
+        #[inline(always)]
+        fn endian(&self) -> Endian {
+            E::ENDIAN
+        }
+
+        #[inline(always)]
+        fn int_encoding(&self) -> IntEncoding {
+            I::INT_ENCODING
+        }
+
+        #[inline(always)]
+        fn skip_fixed_array_length(&self) -> bool {
+            A::SKIP_FIXED_ARRAY_LENGTH
+        }
+
+        #[inline(always)]
+        fn limit(&self) -> Option<usize> {
+            L::LIMIT
+        }
+
+        #[inline(always)]
+        fn max_container_len(&self) -> Option<usize> {
+            M::MAX_CONTAINER_LEN
+        }
+
+        #[inline(always)]
+        fn max_depth(&self) -> Option<usize> {
+            D::MAX_DEPTH
+        }
+    }
 
     pub trait InternalEndianConfig {
         const ENDIAN: Endian;
     }
 
-    impl<E: InternalEndianConfig, I, A, L> InternalEndianConfig for Configuration<E, I, A, L> {
+    impl<E: InternalEndianConfig, I, A, L, M, D> InternalEndianConfig
+        for Configuration<E, I, A, L, M, D>
+    {
         const ENDIAN: Endian = E::ENDIAN;
     }
 
-    #[derive(PartialEq, Eq)]
-    pub enum Endian {
-        Little,
-        Big,
-    }
-
     pub trait InternalIntEncodingConfig {
         const INT_ENCODING: IntEncoding;
     }
 
-    impl<E, I: InternalIntEncodingConfig, A, L> InternalIntEncodingConfig
-        for Configuration<E, I, A, L>
+    impl<E, I: InternalIntEncodingConfig, A, L, M, D> InternalIntEncodingConfig
+        for Configuration<E, I, A, L, M, D>
     {
         const INT_ENCODING: IntEncoding = I::INT_ENCODING;
     }
 
-    #[derive(PartialEq, Eq)]
-    pub enum IntEncoding {
-        Fixed,
-        Variable,
-    }
-
     pub trait InternalArrayLengthConfig {
         const SKIP_FIXED_ARRAY_LENGTH: bool;
     }
 
-    impl<E, I, A: InternalArrayLengthConfig, L> InternalArrayLengthConfig
-        for Configuration<E, I, A, L>
+    impl<E, I, A: InternalArrayLengthConfig, L, M, D> InternalArrayLengthConfig
+        for Configuration<E, I, A, L, M, D>
     {
         const SKIP_FIXED_ARRAY_LENGTH: bool = A::SKIP_FIXED_ARRAY_LENGTH;
     }
@@ -312,7 +596,29 @@ mod internal {
         const LIMIT: Option<usize>;
     }
 
-    impl<E, I, A, L: InternalLimitConfig> InternalLimitConfig for Configuration<E, I, A, L> {
+    impl<E, I, A, L: InternalLimitConfig, M, D> InternalLimitConfig
+        for Configuration<E, I, A, L, M, D>
+    {
         const LIMIT: Option<usize> = L::LIMIT;
     }
+
+    pub trait InternalContainerLenConfig {
+        const MAX_CONTAINER_LEN: Option<usize>;
+    }
+
+    impl<E, I, A, L, M: InternalContainerLenConfig, D> InternalContainerLenConfig
+        for Configuration<E, I, A, L, M, D>
+    {
+        const MAX_CONTAINER_LEN: Option<usize> = M::MAX_CONTAINER_LEN;
+    }
+
+    pub trait InternalDepthConfig {
+        const MAX_DEPTH: Option<usize>;
+    }
+
+    impl<E, I, A, L, M, D: InternalDepthConfig> InternalDepthConfig
+        for Configuration<E, I, A, L, M, D>
+    {
+        const MAX_DEPTH: Option<usize> = D::MAX_DEPTH;
+    }
 }
@@ -24,6 +24,7 @@ pub struct DecoderImpl<R, C: Config> {
     reader: R,
     config: C,
     bytes_read: usize,
+    depth: usize,
 }
 
 impl<R: Reader, C: Config> DecoderImpl<R, C> {
@@ -33,6 +34,7 @@ impl<R: Reader, C: Config> DecoderImpl<R, C> {
             reader,
             config,
             bytes_read: 0,
+            depth: 0,
         }
     }
 }
@@ -60,10 +62,14 @@ impl<R: Reader, C: Config> Decoder for DecoderImpl<R, C> {
         &self.config
     }
 
+    fn offset(&self) -> usize {
+        self.reader.offset()
+    }
+
     #[inline]
     fn claim_bytes_read(&mut self, n: usize) -> Result<(), DecodeError> {
-        // C::LIMIT is a const so this check should get compiled away
-        if let Some(limit) = C::LIMIT {
+        // The limit of a `Configuration` is a const so this check should get compiled away
+        if let Some(limit) = self.config.limit() {
             // Make sure we don't accidentally overflow `bytes_read`
             self.bytes_read = self
                 .bytes_read
@@ -81,10 +87,30 @@ impl<R: Reader, C: Config> Decoder for DecoderImpl<R, C> {
 
     #[inline]
     fn unclaim_bytes_read(&mut self, n: usize) {
-        // C::LIMIT is a const so this check should get compiled away
-        if C::LIMIT.is_some() {
+        // The limit of a `Configuration` is a const so this check should get compiled away
+        if self.config.limit().is_some() {
             // We should always be claiming more than we unclaim, so this should never underflow
             self.bytes_read -= n;
         }
     }
+
+    #[inline]
+    fn enter_depth(&mut self) -> Result<(), DecodeError> {
+        // The max depth of a `Configuration` is a const so this check should get compiled away
+        if let Some(max_depth) = self.config.max_depth() {
+            if self.depth >= max_depth {
+                return Err(DecodeError::DepthLimitExceeded);
+            }
+            self.depth += 1;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn leave_depth(&mut self) {
+        // The max depth of a `Configuration` is a const so this check should get compiled away
+        if self.config.max_depth().is_some() {
+            self.depth = self.depth.saturating_sub(1);
+        }
+    }
 }
@@ -3,10 +3,7 @@ use super::{
     BorrowDecode, BorrowDecoder, Decode, Decoder,
 };
 use crate::{
-    config::{
-        Endian, IntEncoding, InternalArrayLengthConfig, InternalEndianConfig,
-        InternalIntEncodingConfig,
-    },
+    config::{Endian, IntEncoding, InternalConfig},
     error::{DecodeError, IntegerType},
 };
 use core::{
@@ -57,14 +54,16 @@ impl Decode for NonZeroU8 {
 impl Decode for u16 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         decoder.claim_bytes_read(2)?;
-        match D::C::INT_ENCODING {
+        match decoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_u16(decoder.reader(), D::C::ENDIAN)
+                let endian = decoder.config().endian();
+                crate::varint::varint_decode_u16(decoder.reader(), endian)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_decode_u16(decoder.reader()),
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 2];
                 decoder.reader().read(&mut bytes)?;
-                Ok(match D::C::ENDIAN {
+                Ok(match decoder.config().endian() {
                     Endian::Little => u16::from_le_bytes(bytes),
                     Endian::Big => u16::from_be_bytes(bytes),
                 })
@@ -84,14 +83,16 @@ impl Decode for NonZeroU16 {
 impl Decode for u32 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         decoder.claim_bytes_read(4)?;
-        match D::C::INT_ENCODING {
+        match decoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_u32(decoder.reader(), D::C::ENDIAN)
+                let endian = decoder.config().endian();
+                crate::varint::varint_decode_u32(decoder.reader(), endian)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_decode_u32(decoder.reader()),
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 4];
                 decoder.reader().read(&mut bytes)?;
-                Ok(match D::C::ENDIAN {
+                Ok(match decoder.config().endian() {
                     Endian::Little => u32::from_le_bytes(bytes),
                     Endian::Big => u32::from_be_bytes(bytes),
                 })
@@ -111,14 +112,16 @@ impl Decode for NonZeroU32 {
 impl Decode for u64 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         decoder.claim_bytes_read(8)?;
-        match D::C::INT_ENCODING {
+        match decoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_u64(decoder.reader(), D::C::ENDIAN)
+                let endian = decoder.config().endian();
+                crate::varint::varint_decode_u64(decoder.reader(), endian)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_decode_u64(decoder.reader()),
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 8];
                 decoder.reader().read(&mut bytes)?;
-                Ok(match D::C::ENDIAN {
+                Ok(match decoder.config().endian() {
                     Endian::Little => u64::from_le_bytes(bytes),
                     Endian::Big => u64::from_be_bytes(bytes),
                 })
@@ -138,14 +141,16 @@ impl Decode for NonZeroU64 {
 impl Decode for u128 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         decoder.claim_bytes_read(16)?;
-        match D::C::INT_ENCODING {
+        match decoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_u128(decoder.reader(), D::C::ENDIAN)
+                let endian = decoder.config().endian();
+                crate::varint::varint_decode_u128(decoder.reader(), endian)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_decode_u128(decoder.reader()),
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 16];
                 decoder.reader().read(&mut bytes)?;
-                Ok(match D::C::ENDIAN {
+                Ok(match decoder.config().endian() {
                     Endian::Little => u128::from_le_bytes(bytes),
                     Endian::Big => u128::from_be_bytes(bytes),
                 })
@@ -165,14 +170,16 @@ impl Decode for NonZeroU128 {
 impl Decode for usize {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         decoder.claim_bytes_read(8)?;
-        match D::C::INT_ENCODING {
+        match decoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_usize(decoder.reader(), D::C::ENDIAN)
+                let endian = decoder.config().endian();
+                crate::varint::varint_decode_usize(decoder.reader(), endian)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_decode_usize(decoder.reader()),
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 8];
                 decoder.reader().read(&mut bytes)?;
-                Ok(match D::C::ENDIAN {
+                Ok(match decoder.config().endian() {
                     Endian::Little => u64::from_le_bytes(bytes),
                     Endian::Big => u64::from_be_bytes(bytes),
                 } as usize)
@@ -209,14 +216,16 @@ impl Decode for NonZeroI8 {
 impl Decode for i16 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         decoder.claim_bytes_read(2)?;
-        match D::C::INT_ENCODING {
+        match decoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_i16(decoder.reader(), D::C::ENDIAN)
+                let endian = decoder.config().endian();
+                crate::varint::varint_decode_i16(decoder.reader(), endian)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_decode_i16(decoder.reader()),
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 2];
                 decoder.reader().read(&mut bytes)?;
-                Ok(match D::C::ENDIAN {
+                Ok(match decoder.config().endian() {
                     Endian::Little => i16::from_le_bytes(bytes),
                     Endian::Big => i16::from_be_bytes(bytes),
                 })
@@ -236,14 +245,16 @@ impl Decode for NonZeroI16 {
 impl Decode for i32 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         decoder.claim_bytes_read(4)?;
-        match D::C::INT_ENCODING {
+        match decoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_i32(decoder.reader(), D::C::ENDIAN)
+                let endian = decoder.config().endian();
+                crate::varint::varint_decode_i32(decoder.reader(), endian)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_decode_i32(decoder.reader()),
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 4];
                 decoder.reader().read(&mut bytes)?;
-                Ok(match D::C::ENDIAN {
+                Ok(match decoder.config().endian() {
                     Endian::Little => i32::from_le_bytes(bytes),
                     Endian::Big => i32::from_be_bytes(bytes),
                 })
@@ -263,14 +274,16 @@ impl Decode for NonZeroI32 {
 impl Decode for i64 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         decoder.claim_bytes_read(8)?;
-        match D::C::INT_ENCODING {
+        match decoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_i64(decoder.reader(), D::C::ENDIAN)
+                let endian = decoder.config().endian();
+                crate::varint::varint_decode_i64(decoder.reader(), endian)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_decode_i64(decoder.reader()),
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 8];
                 decoder.reader().read(&mut bytes)?;
-                Ok(match D::C::ENDIAN {
+                Ok(match decoder.config().endian() {
                     Endian::Little => i64::from_le_bytes(bytes),
                     Endian::Big => i64::from_be_bytes(bytes),
                 })
@@ -290,14 +303,16 @@ impl Decode for NonZeroI64 {
 impl Decode for i128 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         decoder.claim_bytes_read(16)?;
-        match D::C::INT_ENCODING {
+        match decoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_i128(decoder.reader(), D::C::ENDIAN)
+                let endian = decoder.config().endian();
+                crate::varint::varint_decode_i128(decoder.reader(), endian)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_decode_i128(decoder.reader()),
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 16];
                 decoder.reader().read(&mut bytes)?;
-                Ok(match D::C::ENDIAN {
+                Ok(match decoder.config().endian() {
                     Endian::Little => i128::from_le_bytes(bytes),
                     Endian::Big => i128::from_be_bytes(bytes),
                 })
@@ -317,14 +332,16 @@ impl Decode for NonZeroI128 {
 impl Decode for isize {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         decoder.claim_bytes_read(8)?;
-        match D::C::INT_ENCODING {
+        match decoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_decode_isize(decoder.reader(), D::C::ENDIAN)
+                let endian = decoder.config().endian();
+                crate::varint::varint_decode_isize(decoder.reader(), endian)
             }
+            IntEncoding::Leb128 => crate::varint::leb128_decode_isize(decoder.reader()),
             IntEncoding::Fixed => {
                 let mut bytes = [0u8; 8];
                 decoder.reader().read(&mut bytes)?;
-                Ok(match D::C::ENDIAN {
+                Ok(match decoder.config().endian() {
                     Endian::Little => i64::from_le_bytes(bytes),
                     Endian::Big => i64::from_be_bytes(bytes),
                 } as isize)
@@ -346,7 +363,7 @@ impl Decode for f32 {
         decoder.claim_bytes_read(4)?;
         let mut bytes = [0u8; 4];
         decoder.reader().read(&mut bytes)?;
-        Ok(match D::C::ENDIAN {
+        Ok(match decoder.config().endian() {
             Endian::Little => f32::from_le_bytes(bytes),
             Endian::Big => f32::from_be_bytes(bytes),
         })
@@ -358,7 +375,7 @@ impl Decode for f64 {
         decoder.claim_bytes_read(8)?;
         let mut bytes = [0u8; 8];
         decoder.reader().read(&mut bytes)?;
-        Ok(match D::C::ENDIAN {
+        Ok(match decoder.config().endian() {
             Endian::Little => f64::from_le_bytes(bytes),
             Endian::Big => f64::from_be_bytes(bytes),
         })
@@ -438,8 +455,8 @@ where
     T: Decode + Sized + 'static,
 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
-        if !D::C::SKIP_FIXED_ARRAY_LENGTH {
-            let length = super::decode_slice_len(decoder)?;
+        if !decoder.config().skip_fixed_array_length() {
+            let length = u64::decode(decoder)? as usize;
             if length != N {
                 return Err(DecodeError::ArrayLengthMismatch {
                     found: length,
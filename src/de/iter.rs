@@ -0,0 +1,65 @@
+use super::{BorrowDecode, Decoder, DecoderImpl};
+use crate::{config::Config, de::read::SliceReader, error::DecodeError};
+use core::marker::PhantomData;
+
+/// An iterator that decodes values of type `D` from a slice of back-to-back encoded values, until the end of the slice is reached.
+///
+/// This is returned by [decode_iter]. See its documentation for more information.
+///
+/// [decode_iter]: ../fn.decode_iter.html
+pub struct DecodeIter<'a, D, C> {
+    slice: &'a [u8],
+    config: C,
+    done: bool,
+    _d: PhantomData<fn() -> D>,
+}
+
+impl<'a, D, C> DecodeIter<'a, D, C> {
+    pub(crate) fn new(slice: &'a [u8], config: C) -> Self {
+        Self {
+            slice,
+            config,
+            done: false,
+            _d: PhantomData,
+        }
+    }
+
+    /// Return the bytes that have not been decoded yet.
+    ///
+    /// If the iterator stopped because of an error, this starts at the value that could not be decoded.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.slice
+    }
+}
+
+/// The error that is returned when a value that is encoded as 0 bytes is decoded from a stream of values
+pub(crate) const ZERO_SIZED_VALUE: &str =
+    "values that are encoded as 0 bytes can not be decoded from a stream of values";
+
+impl<'a, D: BorrowDecode<'a>, C: Config> Iterator for DecodeIter<'a, D, C> {
+    type Item = Result<D, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.slice.is_empty() {
+            return None;
+        }
+        let mut decoder = DecoderImpl::<_, C>::new(SliceReader::new(self.slice), self.config);
+        match D::borrow_decode(&mut decoder) {
+            // A value that is encoded as 0 bytes would be decoded forever
+            Ok(_) if decoder.offset() == 0 => {
+                self.done = true;
+                Some(Err(DecodeError::Other(ZERO_SIZED_VALUE)))
+            }
+            Ok(value) => {
+                self.slice = &self.slice[decoder.offset()..];
+                Some(Ok(value))
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl<'a, D: BorrowDecode<'a>, C: Config> core::iter::FusedIterator for DecodeIter<'a, D, C> {}
@@ -4,10 +4,12 @@ mod decoder;
 mod impl_core;
 mod impl_tuples;
 mod impls;
+mod iter;
+mod record;
 
 use self::read::{BorrowReader, Reader};
 use crate::{
-    config::{Config, InternalLimitConfig},
+    config::{Config, InternalConfig},
     error::DecodeError,
     utils::Sealed,
 };
@@ -15,6 +17,10 @@ use crate::{
 pub mod read;
 
 pub use self::decoder::DecoderImpl;
+pub use self::iter::DecodeIter;
+#[cfg(feature = "std")]
+pub(crate) use self::iter::ZERO_SIZED_VALUE;
+pub use self::record::Record;
 
 /// Trait that makes a type able to be decoded, akin to serde's `DeserializeOwned` trait.
 ///
@@ -48,10 +54,13 @@ pub use self::decoder::DecoderImpl;
 ///     fn decode<D: bincode::de::Decoder>(
 ///         decoder: &mut D,
 ///     ) -> core::result::Result<Self, bincode::error::DecodeError> {
-///         Ok(Self {
+///         bincode::de::Decoder::enter_depth(decoder)?;
+///         let result = Ok(Self {
 ///             x: bincode::Decode::decode(decoder)?,
 ///             y: bincode::Decode::decode(decoder)?,
-///         })
+///         });
+///         bincode::de::Decoder::leave_depth(decoder);
+///         result
 ///     }
 /// }
 /// ```
@@ -92,6 +101,48 @@ impl<'de, T: Decode> BorrowDecode<'de> for T {
     }
 }
 
+/// Decode a type that is encoded with a version number in front of it.
+///
+/// This trait is implemented by the `Decode` derive for types with `#[bincode(version = N)]`. The `Decode` implementation of these types reads the version number, and calls [decode_versioned] with it. If the version is older than `N`, the type that is set by `#[bincode(migrate_from = "OldType")]` is decoded instead and converted with `From<OldType>`. This older type must have a version as well, so older versions are migrated through the whole chain of types.
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// #[derive(bincode::Encode, bincode::Decode)]
+/// #[bincode(version = 1)]
+/// struct UserV1 {
+///     name: [u8; 4],
+/// }
+///
+/// #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+/// #[bincode(version = 2, migrate_from = "UserV1")]
+/// struct User {
+///     name: [u8; 4],
+///     admin: bool,
+/// }
+///
+/// impl From<UserV1> for User {
+///     fn from(user: UserV1) -> Self {
+///         User { name: user.name, admin: false }
+///     }
+/// }
+///
+/// let config = bincode::config::standard();
+/// let mut bytes = [0u8; 16];
+/// let len = bincode::encode_into_slice(UserV1 { name: *b"jane" }, &mut bytes, config).unwrap();
+/// let (user, _): (User, usize) = bincode::decode_from_slice(&bytes[..len], config).unwrap();
+/// assert_eq!(user, User { name: *b"jane", admin: false });
+/// # }
+/// ```
+///
+/// [decode_versioned]: #tymethod.decode_versioned
+pub trait DecodeVersioned: Sized {
+    /// Decode the value after its version number, which was encoded as `version`.
+    ///
+    /// Returns `DecodeError::UnknownVersion` if neither this type, nor any of the types it migrates from, has this version.
+    fn decode_versioned<D: Decoder>(decoder: &mut D, version: u32) -> Result<Self, DecodeError>;
+}
+
 /// Any source that can decode basic types. This type is most notably implemented for [Decoder].
 pub trait Decoder: Sealed {
     /// The concrete [Reader] type
@@ -106,6 +157,9 @@ pub trait Decoder: Sealed {
     /// Returns a reference to the config
     fn config(&self) -> &Self::C;
 
+    /// Returns the amount of bytes that have been read from the reader so far, see [Reader::offset]
+    fn offset(&self) -> usize;
+
     /// Claim that `n` bytes are going to be read from the decoder.
     /// This can be used to validate `Configuration::Limit<N>()`.
     fn claim_bytes_read(&mut self, n: usize) -> Result<(), DecodeError>;
@@ -113,7 +167,7 @@ pub trait Decoder: Sealed {
     /// Claim that we're going to read a container which contains `len` entries of `T`.
     /// This will correctly handle overflowing if `len * size_of::<T>() > usize::max_value`
     fn claim_container_read<T>(&mut self, len: usize) -> Result<(), DecodeError> {
-        if <Self::C as InternalLimitConfig>::LIMIT.is_some() {
+        if self.config().limit().is_some() {
             match len.checked_mul(core::mem::size_of::<T>()) {
                 Some(val) => self.claim_bytes_read(val),
                 None => Err(DecodeError::LimitExceeded),
@@ -164,6 +218,40 @@ pub trait Decoder: Sealed {
     /// }
     /// ```
     fn unclaim_bytes_read(&mut self, n: usize);
+
+    /// Notify the decoder that a nested value is going to be decoded. This should be paired with a call to [leave_depth] once the nested value is decoded.
+    ///
+    /// Returns `DecodeError::DepthLimitExceeded` if this exceeds the maximum depth of the configuration. This is used to prevent recursive types from overflowing the stack. If an error is returned while decoding the nested value, [leave_depth] does not need to be called.
+    ///
+    /// ```
+    /// # use bincode::de::{Decode, Decoder};
+    /// # use bincode::error::DecodeError;
+    /// /// A chain of links, encoded as a `true` for every link followed by a `false`.
+    /// struct Chain {
+    ///     links: usize,
+    /// }
+    ///
+    /// impl Decode for Chain {
+    ///     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+    ///         decoder.enter_depth()?;
+    ///         let links = if bool::decode(decoder)? {
+    ///             Chain::decode(decoder)?.links + 1
+    ///         } else {
+    ///             0
+    ///         };
+    ///         decoder.leave_depth();
+    ///         Ok(Chain { links })
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [leave_depth]: #tymethod.leave_depth
+    fn enter_depth(&mut self) -> Result<(), DecodeError>;
+
+    /// Notify the decoder that a nested value has been decoded. See [enter_depth] for more information.
+    ///
+    /// [enter_depth]: #tymethod.enter_depth
+    fn leave_depth(&mut self);
 }
 
 /// Any source that can decode basic types. This type is most notably implemented for [Decoder].
@@ -177,7 +265,7 @@ pub trait BorrowDecoder<'de>: Decoder {
     fn borrow_reader(&mut self) -> &mut Self::BR;
 }
 
-impl<'a, T> Decoder for &'a mut T
+impl<T> Decoder for &mut T
 where
     T: Decoder,
 {
@@ -193,6 +281,10 @@ where
         T::config(self)
     }
 
+    fn offset(&self) -> usize {
+        T::offset(self)
+    }
+
     #[inline]
     fn claim_bytes_read(&mut self, n: usize) -> Result<(), DecodeError> {
         T::claim_bytes_read(self, n)
@@ -202,9 +294,19 @@ where
     fn unclaim_bytes_read(&mut self, n: usize) {
         T::unclaim_bytes_read(self, n)
     }
+
+    #[inline]
+    fn enter_depth(&mut self) -> Result<(), DecodeError> {
+        T::enter_depth(self)
+    }
+
+    #[inline]
+    fn leave_depth(&mut self) {
+        T::leave_depth(self)
+    }
 }
 
-impl<'a, 'de, T> BorrowDecoder<'de> for &'a mut T
+impl<'de, T> BorrowDecoder<'de> for &mut T
 where
     T: BorrowDecoder<'de>,
 {
@@ -236,5 +338,21 @@ pub(crate) fn decode_option_variant<D: Decoder>(
 /// Decodes the length of any slice, container, etc from the decoder
 #[inline]
 pub(crate) fn decode_slice_len<D: Decoder>(decoder: &mut D) -> Result<usize, DecodeError> {
-    u64::decode(decoder).map(|v| v as usize)
+    let len = u64::decode(decoder)?;
+    check_slice_len(decoder, len)
+}
+
+/// Checks a decoded length of a slice, container, etc against the max container len of the config
+#[inline]
+pub(crate) fn check_slice_len<D: Decoder>(decoder: &D, len: u64) -> Result<usize, DecodeError> {
+    // The max container len of a `Configuration` is a const so this check should get compiled away
+    if let Some(allowed) = decoder.config().max_container_len() {
+        if len > allowed as u64 {
+            return Err(DecodeError::ContainerLengthExceeded {
+                found: usize::try_from(len).unwrap_or(usize::MAX),
+                allowed,
+            });
+        }
+    }
+    Ok(len as usize)
 }
@@ -29,9 +29,17 @@ pub trait Reader {
     /// must be provided so that subsequent reads or peek-reads do not return the same bytes
     #[inline]
     fn consume(&mut self, _: usize) {}
+
+    /// Returns the amount of bytes that have been read or consumed from this reader so far.
+    ///
+    /// This is used to report the offset at which a decode error occured, and to find the end of structs with `#[bincode(default_trailing)]`. The default implementation returns 0. [decode_from_reader](../../fn.decode_from_reader.html) wraps its reader in an [OffsetReader], readers that do not keep track of this and are passed to a [DecoderImpl](../struct.DecoderImpl.html) directly should be wrapped in one as well.
+    #[inline]
+    fn offset(&self) -> usize {
+        0
+    }
 }
 
-impl<'a, T> Reader for &'a mut T
+impl<T> Reader for &mut T
 where
     T: Reader,
 {
@@ -49,6 +57,11 @@ where
     fn consume(&mut self, n: usize) {
         (*self).consume(n)
     }
+
+    #[inline]
+    fn offset(&self) -> usize {
+        (**self).offset()
+    }
 }
 
 /// A reader for borrowed data. Implementors of this must also implement the [Reader] trait. See the module documentation for more information.
@@ -62,12 +75,16 @@ pub trait BorrowReader<'storage>: Reader {
 /// A reader type for `&[u8]` slices. Implements both [Reader] and [BorrowReader], and thus can be used for borrowed data.
 pub struct SliceReader<'storage> {
     pub(crate) slice: &'storage [u8],
+    offset: usize,
 }
 
 impl<'storage> SliceReader<'storage> {
     /// Constructs a slice reader
     pub fn new(bytes: &'storage [u8]) -> SliceReader<'storage> {
-        SliceReader { slice: bytes }
+        SliceReader {
+            slice: bytes,
+            offset: 0,
+        }
     }
 }
 
@@ -80,6 +97,7 @@ impl<'storage> Reader for SliceReader<'storage> {
         let (read_slice, remaining) = self.slice.split_at(bytes.len());
         bytes.copy_from_slice(read_slice);
         self.slice = remaining;
+        self.offset += bytes.len();
 
         Ok(())
     }
@@ -91,7 +109,14 @@ impl<'storage> Reader for SliceReader<'storage> {
 
     #[inline]
     fn consume(&mut self, n: usize) {
-        self.slice = self.slice.get(n..).unwrap_or_default();
+        let n = n.min(self.slice.len());
+        self.slice = &self.slice[n..];
+        self.offset += n;
+    }
+
+    #[inline]
+    fn offset(&self) -> usize {
+        self.offset
     }
 }
 
@@ -103,6 +128,66 @@ impl<'storage> BorrowReader<'storage> for SliceReader<'storage> {
         }
         let (read_slice, remaining) = self.slice.split_at(length);
         self.slice = remaining;
+        self.offset += length;
         Ok(read_slice)
     }
 }
+
+/// A reader that keeps track of the amount of bytes that have been read from the inner reader.
+///
+/// [decode_from_reader](../../fn.decode_from_reader.html) wraps its reader in an `OffsetReader`. When creating a [DecoderImpl](../struct.DecoderImpl.html) directly, wrap a reader that does not implement [Reader::offset], e.g. `std::io::BufReader` or a custom reader, in an `OffsetReader` so decode errors report the correct offset and structs with `#[bincode(default_trailing)]` or `#[bincode(tagged)]` can be decoded.
+pub struct OffsetReader<R> {
+    reader: R,
+    offset: usize,
+}
+
+impl<R> OffsetReader<R> {
+    /// Create a new `OffsetReader` that reads from the given reader
+    pub fn new(reader: R) -> Self {
+        Self { reader, offset: 0 }
+    }
+
+    /// Return a reference to the inner reader
+    pub fn inner(&self) -> &R {
+        &self.reader
+    }
+
+    /// Return the inner reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Reader> Reader for OffsetReader<R> {
+    #[inline(always)]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        self.reader.read(bytes)?;
+        self.offset += bytes.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn peek_read(&self, n: usize) -> Option<&[u8]> {
+        self.reader.peek_read(n)
+    }
+
+    #[inline]
+    fn consume(&mut self, n: usize) {
+        self.reader.consume(n);
+        self.offset += n;
+    }
+
+    #[inline]
+    fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'storage, R: BorrowReader<'storage>> BorrowReader<'storage> for OffsetReader<R> {
+    #[inline(always)]
+    fn take_bytes(&mut self, length: usize) -> Result<&'storage [u8], DecodeError> {
+        let bytes = self.reader.take_bytes(length)?;
+        self.offset += length;
+        Ok(bytes)
+    }
+}
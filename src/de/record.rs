@@ -0,0 +1,108 @@
+use super::{read::Reader, Decode, Decoder};
+use crate::error::DecodeError;
+
+/// A length-prefixed record, used by the `Decode` and `BorrowDecode` derives for structs with `#[bincode(default_trailing)]`.
+///
+/// The fields of the record are decoded from the decoder directly. A field that starts after the end of the record was not written by the encoder, e.g. because the encoder used an older version of the type, and should be set to its default value. Any bytes that are left at the end of the record, e.g. fields added by a newer version of the type, are skipped by [finish].
+///
+/// ```
+/// # use bincode::{Decode, de::{Decoder, Record}, error::DecodeError};
+/// struct Settings {
+///     volume: u8,
+///     muted: bool,
+/// }
+///
+/// impl Decode for Settings {
+///     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+///         let record = Record::begin(decoder)?;
+///         let volume = if record.is_finished(decoder) { 0 } else { u8::decode(decoder)? };
+///         let muted = if record.is_finished(decoder) { false } else { bool::decode(decoder)? };
+///         record.finish(decoder)?;
+///         Ok(Settings { volume, muted })
+///     }
+/// }
+///
+/// // A record of 1 byte, written before `muted` was added
+/// let (settings, _): (Settings, usize) =
+///     bincode::decode_from_slice(&[1, 50], bincode::config::standard()).unwrap();
+/// assert_eq!(settings.volume, 50);
+/// assert!(!settings.muted);
+/// ```
+///
+/// [finish]: #method.finish
+pub struct Record {
+    start: usize,
+    end: usize,
+}
+
+impl Record {
+    /// Read the length of the record, which is encoded as a `u64` in front of its fields.
+    ///
+    /// Returns `DecodeError::Other` if the reader of the decoder does not keep track of its [offset](../read/trait.Reader.html#method.offset).
+    pub fn begin<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = u64::decode(decoder)?;
+        let start = decoder.offset();
+        if start == 0 {
+            // The length takes up at least 1 byte, so the reader does not keep track of its offset
+            return Err(DecodeError::Other(
+                "the reader does not keep track of its offset, wrap it in an `OffsetReader`",
+            ));
+        }
+        Ok(Self {
+            start,
+            end: start.saturating_add(usize::try_from(len).unwrap_or(usize::MAX)),
+        })
+    }
+
+    /// Returns `true` if all bytes of the record have been read.
+    pub fn is_finished<D: Decoder>(&self, decoder: &D) -> bool {
+        decoder.offset() >= self.end
+    }
+
+    /// Skip the bytes of the record that have not been read.
+    ///
+    /// Returns `DecodeError::RecordLengthExceeded` if more bytes were read than the length of the record.
+    pub fn finish<D: Decoder>(self, decoder: &mut D) -> Result<(), DecodeError> {
+        let mut remaining = self.claim_remaining(decoder)?;
+        let mut buffer = [0u8; 64];
+        while remaining > 0 {
+            let len = remaining.min(buffer.len());
+            decoder.reader().read(&mut buffer[..len])?;
+            remaining -= len;
+        }
+        Ok(())
+    }
+
+    /// Read the bytes of the record that have not been read, instead of skipping them like [finish](#method.finish).
+    #[cfg(feature = "alloc")]
+    pub(crate) fn finish_into_vec<D: Decoder>(
+        self,
+        decoder: &mut D,
+    ) -> Result<alloc::vec::Vec<u8>, DecodeError> {
+        let mut remaining = self.claim_remaining(decoder)?;
+        // The length of the record is not trusted, so the bytes are read in chunks instead of
+        // allocating the full length up front.
+        let mut bytes = alloc::vec::Vec::new();
+        let mut buffer = [0u8; 64];
+        while remaining > 0 {
+            let len = remaining.min(buffer.len());
+            decoder.reader().read(&mut buffer[..len])?;
+            bytes.extend_from_slice(&buffer[..len]);
+            remaining -= len;
+        }
+        Ok(bytes)
+    }
+
+    fn claim_remaining<D: Decoder>(&self, decoder: &mut D) -> Result<usize, DecodeError> {
+        let offset = decoder.offset();
+        if offset > self.end {
+            return Err(DecodeError::RecordLengthExceeded {
+                length: self.end - self.start,
+                read: offset - self.start,
+            });
+        }
+        let remaining = self.end - offset;
+        decoder.claim_bytes_read(remaining)?;
+        Ok(remaining)
+    }
+}
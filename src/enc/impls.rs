@@ -1,9 +1,6 @@
 use super::{write::Writer, Encode, Encoder};
 use crate::{
-    config::{
-        Endian, IntEncoding, InternalArrayLengthConfig, InternalEndianConfig,
-        InternalIntEncodingConfig,
-    },
+    config::{Endian, IntEncoding, InternalConfig},
     error::EncodeError,
 };
 use core::{
@@ -49,11 +46,13 @@ impl Encode for NonZeroU8 {
 
 impl Encode for u16 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        match E::C::INT_ENCODING {
+        match encoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_encode_u16(encoder.writer(), E::C::ENDIAN, *self)
+                let endian = encoder.config().endian();
+                crate::varint::varint_encode_u16(encoder.writer(), endian, *self)
             }
-            IntEncoding::Fixed => match E::C::ENDIAN {
+            IntEncoding::Leb128 => crate::varint::leb128_encode_u16(encoder.writer(), *self),
+            IntEncoding::Fixed => match encoder.config().endian() {
                 Endian::Big => encoder.writer().write(&self.to_be_bytes()),
                 Endian::Little => encoder.writer().write(&self.to_le_bytes()),
             },
@@ -69,11 +68,13 @@ impl Encode for NonZeroU16 {
 
 impl Encode for u32 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        match E::C::INT_ENCODING {
+        match encoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_encode_u32(encoder.writer(), E::C::ENDIAN, *self)
+                let endian = encoder.config().endian();
+                crate::varint::varint_encode_u32(encoder.writer(), endian, *self)
             }
-            IntEncoding::Fixed => match E::C::ENDIAN {
+            IntEncoding::Leb128 => crate::varint::leb128_encode_u32(encoder.writer(), *self),
+            IntEncoding::Fixed => match encoder.config().endian() {
                 Endian::Big => encoder.writer().write(&self.to_be_bytes()),
                 Endian::Little => encoder.writer().write(&self.to_le_bytes()),
             },
@@ -89,11 +90,13 @@ impl Encode for NonZeroU32 {
 
 impl Encode for u64 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        match E::C::INT_ENCODING {
+        match encoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_encode_u64(encoder.writer(), E::C::ENDIAN, *self)
+                let endian = encoder.config().endian();
+                crate::varint::varint_encode_u64(encoder.writer(), endian, *self)
             }
-            IntEncoding::Fixed => match E::C::ENDIAN {
+            IntEncoding::Leb128 => crate::varint::leb128_encode_u64(encoder.writer(), *self),
+            IntEncoding::Fixed => match encoder.config().endian() {
                 Endian::Big => encoder.writer().write(&self.to_be_bytes()),
                 Endian::Little => encoder.writer().write(&self.to_le_bytes()),
             },
@@ -109,11 +112,13 @@ impl Encode for NonZeroU64 {
 
 impl Encode for u128 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        match E::C::INT_ENCODING {
+        match encoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_encode_u128(encoder.writer(), E::C::ENDIAN, *self)
+                let endian = encoder.config().endian();
+                crate::varint::varint_encode_u128(encoder.writer(), endian, *self)
             }
-            IntEncoding::Fixed => match E::C::ENDIAN {
+            IntEncoding::Leb128 => crate::varint::leb128_encode_u128(encoder.writer(), *self),
+            IntEncoding::Fixed => match encoder.config().endian() {
                 Endian::Big => encoder.writer().write(&self.to_be_bytes()),
                 Endian::Little => encoder.writer().write(&self.to_le_bytes()),
             },
@@ -129,11 +134,13 @@ impl Encode for NonZeroU128 {
 
 impl Encode for usize {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        match E::C::INT_ENCODING {
+        match encoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_encode_usize(encoder.writer(), E::C::ENDIAN, *self)
+                let endian = encoder.config().endian();
+                crate::varint::varint_encode_usize(encoder.writer(), endian, *self)
             }
-            IntEncoding::Fixed => match E::C::ENDIAN {
+            IntEncoding::Leb128 => crate::varint::leb128_encode_usize(encoder.writer(), *self),
+            IntEncoding::Fixed => match encoder.config().endian() {
                 Endian::Big => encoder.writer().write(&self.to_be_bytes()),
                 Endian::Little => encoder.writer().write(&self.to_le_bytes()),
             },
@@ -161,11 +168,13 @@ impl Encode for NonZeroI8 {
 
 impl Encode for i16 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        match E::C::INT_ENCODING {
+        match encoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_encode_i16(encoder.writer(), E::C::ENDIAN, *self)
+                let endian = encoder.config().endian();
+                crate::varint::varint_encode_i16(encoder.writer(), endian, *self)
             }
-            IntEncoding::Fixed => match E::C::ENDIAN {
+            IntEncoding::Leb128 => crate::varint::leb128_encode_i16(encoder.writer(), *self),
+            IntEncoding::Fixed => match encoder.config().endian() {
                 Endian::Big => encoder.writer().write(&self.to_be_bytes()),
                 Endian::Little => encoder.writer().write(&self.to_le_bytes()),
             },
@@ -181,11 +190,13 @@ impl Encode for NonZeroI16 {
 
 impl Encode for i32 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        match E::C::INT_ENCODING {
+        match encoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_encode_i32(encoder.writer(), E::C::ENDIAN, *self)
+                let endian = encoder.config().endian();
+                crate::varint::varint_encode_i32(encoder.writer(), endian, *self)
             }
-            IntEncoding::Fixed => match E::C::ENDIAN {
+            IntEncoding::Leb128 => crate::varint::leb128_encode_i32(encoder.writer(), *self),
+            IntEncoding::Fixed => match encoder.config().endian() {
                 Endian::Big => encoder.writer().write(&self.to_be_bytes()),
                 Endian::Little => encoder.writer().write(&self.to_le_bytes()),
             },
@@ -201,11 +212,13 @@ impl Encode for NonZeroI32 {
 
 impl Encode for i64 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        match E::C::INT_ENCODING {
+        match encoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_encode_i64(encoder.writer(), E::C::ENDIAN, *self)
+                let endian = encoder.config().endian();
+                crate::varint::varint_encode_i64(encoder.writer(), endian, *self)
             }
-            IntEncoding::Fixed => match E::C::ENDIAN {
+            IntEncoding::Leb128 => crate::varint::leb128_encode_i64(encoder.writer(), *self),
+            IntEncoding::Fixed => match encoder.config().endian() {
                 Endian::Big => encoder.writer().write(&self.to_be_bytes()),
                 Endian::Little => encoder.writer().write(&self.to_le_bytes()),
             },
@@ -221,11 +234,13 @@ impl Encode for NonZeroI64 {
 
 impl Encode for i128 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        match E::C::INT_ENCODING {
+        match encoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_encode_i128(encoder.writer(), E::C::ENDIAN, *self)
+                let endian = encoder.config().endian();
+                crate::varint::varint_encode_i128(encoder.writer(), endian, *self)
             }
-            IntEncoding::Fixed => match E::C::ENDIAN {
+            IntEncoding::Leb128 => crate::varint::leb128_encode_i128(encoder.writer(), *self),
+            IntEncoding::Fixed => match encoder.config().endian() {
                 Endian::Big => encoder.writer().write(&self.to_be_bytes()),
                 Endian::Little => encoder.writer().write(&self.to_le_bytes()),
             },
@@ -241,11 +256,13 @@ impl Encode for NonZeroI128 {
 
 impl Encode for isize {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        match E::C::INT_ENCODING {
+        match encoder.config().int_encoding() {
             IntEncoding::Variable => {
-                crate::varint::varint_encode_isize(encoder.writer(), E::C::ENDIAN, *self)
+                let endian = encoder.config().endian();
+                crate::varint::varint_encode_isize(encoder.writer(), endian, *self)
             }
-            IntEncoding::Fixed => match E::C::ENDIAN {
+            IntEncoding::Leb128 => crate::varint::leb128_encode_isize(encoder.writer(), *self),
+            IntEncoding::Fixed => match encoder.config().endian() {
                 Endian::Big => encoder.writer().write(&self.to_be_bytes()),
                 Endian::Little => encoder.writer().write(&self.to_le_bytes()),
             },
@@ -261,7 +278,7 @@ impl Encode for NonZeroIsize {
 
 impl Encode for f32 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        match E::C::ENDIAN {
+        match encoder.config().endian() {
             Endian::Big => encoder.writer().write(&self.to_be_bytes()),
             Endian::Little => encoder.writer().write(&self.to_le_bytes()),
         }
@@ -270,7 +287,7 @@ impl Encode for f32 {
 
 impl Encode for f64 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        match E::C::ENDIAN {
+        match encoder.config().endian() {
             Endian::Big => encoder.writer().write(&self.to_be_bytes()),
             Endian::Little => encoder.writer().write(&self.to_le_bytes()),
         }
@@ -356,7 +373,7 @@ where
     T: Encode,
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
-        if !E::C::SKIP_FIXED_ARRAY_LENGTH {
+        if !encoder.config().skip_fixed_array_length() {
             super::encode_slice_len(encoder, N)?;
         }
         for item in self.iter() {
@@ -474,7 +491,7 @@ where
     }
 }
 
-impl<'a, T> Encode for &'a T
+impl<T> Encode for &T
 where
     T: Encode,
 {
@@ -0,0 +1,478 @@
+use crate::config::{Config, IntEncoding, InternalArrayLengthConfig, InternalIntEncodingConfig};
+use core::{
+    cell::{Cell, RefCell},
+    marker::PhantomData,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+    },
+    ops::{Bound, Range, RangeInclusive},
+    time::Duration,
+};
+
+/// Types whose encoded size has a known upper bound, regardless of their value.
+///
+/// This trait will be automatically implemented if you enable the `derive` feature and add `#[derive(bincode::MaxSize)]` to your type. The derived size of a struct is the sum of its fields, the derived size of an enum is the size of the variant index plus the size of its largest variant.
+///
+/// Use [max_encoded_size] to get the maximum size for a given configuration. This is a `const fn`, so it can be used to size a buffer at compile time:
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// #[derive(bincode::Encode, bincode::MaxSize)]
+/// struct Packet {
+///     id: u32,
+///     payload: [u8; 16],
+///     checksum: Option<u16>,
+/// }
+///
+/// const CONFIG: bincode::config::Configuration = bincode::config::standard();
+/// const MAX_SIZE: usize = bincode::max_encoded_size::<Packet, _>(CONFIG);
+///
+/// let mut buffer = [0u8; MAX_SIZE];
+/// let packet = Packet {
+///     id: u32::MAX,
+///     payload: [0xFF; 16],
+///     checksum: Some(u16::MAX),
+/// };
+/// let len = bincode::encode_into_slice(packet, &mut buffer, CONFIG).unwrap();
+/// assert_eq!(len, MAX_SIZE);
+/// # }
+/// ```
+///
+/// Types that can grow indefinitely, like `Vec<T>` or `String`, do not implement this trait.
+///
+/// [max_encoded_size]: ../fn.max_encoded_size.html
+pub trait MaxSize {
+    /// The maximum encoded size of this type in every configuration.
+    const MAX_SIZE: MaxSizes;
+}
+
+/// The maximum encoded size of a type for each of the configuration options that influence it.
+///
+/// The endianness and the limit of a configuration never change the encoded size, so these are not tracked.
+///
+/// When implementing [MaxSize] manually, build this by combining the sizes of the fields of your type:
+///
+/// ```
+/// use bincode::enc::{MaxSize, MaxSizes};
+///
+/// struct Color {
+///     r: u8,
+///     g: u8,
+///     b: u8,
+///     alpha: Option<u8>,
+/// }
+///
+/// impl MaxSize for Color {
+///     const MAX_SIZE: MaxSizes = MaxSizes::new(3).add(<Option<u8> as MaxSize>::MAX_SIZE);
+/// }
+///
+/// let config = bincode::config::standard();
+/// assert_eq!(bincode::max_encoded_size::<Color, _>(config), 5);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MaxSizes {
+    /// The sizes, indexed by `[int_encoding][skip_fixed_array_length]`
+    sizes: [[usize; 2]; 3],
+}
+
+impl MaxSizes {
+    /// A size of zero bytes in every configuration.
+    pub const ZERO: MaxSizes = MaxSizes::new(0);
+
+    /// A size of `size` bytes in every configuration.
+    pub const fn new(size: usize) -> Self {
+        Self {
+            sizes: [[size; 2]; 3],
+        }
+    }
+
+    /// An integer that is `fixed` bytes with fixed int encoding and at most `variable` bytes with variable int encoding. With LEB128 every byte holds 7 of its `fixed * 8` bits.
+    const fn int(fixed: usize, variable: usize) -> Self {
+        let leb128 = (fixed * 8).div_ceil(7);
+        Self {
+            sizes: [[fixed; 2], [variable; 2], [leb128; 2]],
+        }
+    }
+
+    /// The length prefix of a fixed array with `len` items. This is encoded as an `u64`, unless the array length is skipped.
+    const fn array_len(len: usize) -> Self {
+        let variable = if len <= 250 {
+            1
+        } else if len <= u16::MAX as usize {
+            3
+        } else if len as u64 <= u32::MAX as u64 {
+            5
+        } else {
+            9
+        };
+        let mut leb128 = 1;
+        while leb128 < 10 && (len as u64) >> (7 * leb128) != 0 {
+            leb128 += 1;
+        }
+        Self {
+            sizes: [[8, 0], [variable, 0], [leb128, 0]],
+        }
+    }
+
+    /// The size of `self` followed by `other`.
+    pub const fn add(self, other: MaxSizes) -> Self {
+        let mut sizes = self.sizes;
+        let mut i = 0;
+        while i < sizes.len() {
+            let mut j = 0;
+            while j < sizes[i].len() {
+                sizes[i][j] = sizes[i][j].saturating_add(other.sizes[i][j]);
+                j += 1;
+            }
+            i += 1;
+        }
+        Self { sizes }
+    }
+
+    /// The largest of `self` and `other` in every configuration.
+    pub const fn max(self, other: MaxSizes) -> Self {
+        let mut sizes = self.sizes;
+        let mut i = 0;
+        while i < sizes.len() {
+            let mut j = 0;
+            while j < sizes[i].len() {
+                if other.sizes[i][j] > sizes[i][j] {
+                    sizes[i][j] = other.sizes[i][j];
+                }
+                j += 1;
+            }
+            i += 1;
+        }
+        Self { sizes }
+    }
+
+    /// The size of `count` consecutive values of `self`.
+    pub const fn repeat(self, count: usize) -> Self {
+        let mut sizes = self.sizes;
+        let mut i = 0;
+        while i < sizes.len() {
+            let mut j = 0;
+            while j < sizes[i].len() {
+                sizes[i][j] = sizes[i][j].saturating_mul(count);
+                j += 1;
+            }
+            i += 1;
+        }
+        Self { sizes }
+    }
+
+    /// The maximum size in bytes for the given configuration `C`.
+    ///
+    /// The options of `C` have to be known at compile time, so `C` can be a [Configuration](crate::config::Configuration) but not a [DynConfig](crate::config::DynConfig).
+    pub const fn get<C>(&self) -> usize
+    where
+        C: Config + InternalIntEncodingConfig + InternalArrayLengthConfig,
+    {
+        let int_encoding = match C::INT_ENCODING {
+            IntEncoding::Fixed => 0,
+            IntEncoding::Variable => 1,
+            IntEncoding::Leb128 => 2,
+        };
+        self.sizes[int_encoding][C::SKIP_FIXED_ARRAY_LENGTH as usize]
+    }
+}
+
+impl MaxSize for () {
+    const MAX_SIZE: MaxSizes = MaxSizes::ZERO;
+}
+
+impl<T> MaxSize for PhantomData<T> {
+    const MAX_SIZE: MaxSizes = MaxSizes::ZERO;
+}
+
+impl MaxSize for bool {
+    const MAX_SIZE: MaxSizes = MaxSizes::new(1);
+}
+
+impl MaxSize for u8 {
+    const MAX_SIZE: MaxSizes = MaxSizes::new(1);
+}
+
+impl MaxSize for NonZeroU8 {
+    const MAX_SIZE: MaxSizes = u8::MAX_SIZE;
+}
+
+impl MaxSize for u16 {
+    const MAX_SIZE: MaxSizes = MaxSizes::int(2, 3);
+}
+
+impl MaxSize for NonZeroU16 {
+    const MAX_SIZE: MaxSizes = u16::MAX_SIZE;
+}
+
+impl MaxSize for u32 {
+    const MAX_SIZE: MaxSizes = MaxSizes::int(4, 5);
+}
+
+impl MaxSize for NonZeroU32 {
+    const MAX_SIZE: MaxSizes = u32::MAX_SIZE;
+}
+
+impl MaxSize for u64 {
+    const MAX_SIZE: MaxSizes = MaxSizes::int(8, 9);
+}
+
+impl MaxSize for NonZeroU64 {
+    const MAX_SIZE: MaxSizes = u64::MAX_SIZE;
+}
+
+impl MaxSize for u128 {
+    const MAX_SIZE: MaxSizes = MaxSizes::int(16, 17);
+}
+
+impl MaxSize for NonZeroU128 {
+    const MAX_SIZE: MaxSizes = u128::MAX_SIZE;
+}
+
+impl MaxSize for usize {
+    const MAX_SIZE: MaxSizes = u64::MAX_SIZE;
+}
+
+impl MaxSize for NonZeroUsize {
+    const MAX_SIZE: MaxSizes = usize::MAX_SIZE;
+}
+
+impl MaxSize for i8 {
+    const MAX_SIZE: MaxSizes = MaxSizes::new(1);
+}
+
+impl MaxSize for NonZeroI8 {
+    const MAX_SIZE: MaxSizes = i8::MAX_SIZE;
+}
+
+impl MaxSize for i16 {
+    const MAX_SIZE: MaxSizes = u16::MAX_SIZE;
+}
+
+impl MaxSize for NonZeroI16 {
+    const MAX_SIZE: MaxSizes = i16::MAX_SIZE;
+}
+
+impl MaxSize for i32 {
+    const MAX_SIZE: MaxSizes = u32::MAX_SIZE;
+}
+
+impl MaxSize for NonZeroI32 {
+    const MAX_SIZE: MaxSizes = i32::MAX_SIZE;
+}
+
+impl MaxSize for i64 {
+    const MAX_SIZE: MaxSizes = u64::MAX_SIZE;
+}
+
+impl MaxSize for NonZeroI64 {
+    const MAX_SIZE: MaxSizes = i64::MAX_SIZE;
+}
+
+impl MaxSize for i128 {
+    const MAX_SIZE: MaxSizes = u128::MAX_SIZE;
+}
+
+impl MaxSize for NonZeroI128 {
+    const MAX_SIZE: MaxSizes = i128::MAX_SIZE;
+}
+
+impl MaxSize for isize {
+    const MAX_SIZE: MaxSizes = i64::MAX_SIZE;
+}
+
+impl MaxSize for NonZeroIsize {
+    const MAX_SIZE: MaxSizes = isize::MAX_SIZE;
+}
+
+impl MaxSize for f32 {
+    const MAX_SIZE: MaxSizes = MaxSizes::new(4);
+}
+
+impl MaxSize for f64 {
+    const MAX_SIZE: MaxSizes = MaxSizes::new(8);
+}
+
+impl MaxSize for char {
+    // chars are encoded as utf-8
+    const MAX_SIZE: MaxSizes = MaxSizes::new(4);
+}
+
+impl<T, const N: usize> MaxSize for [T; N]
+where
+    T: MaxSize,
+{
+    const MAX_SIZE: MaxSizes = MaxSizes::array_len(N).add(T::MAX_SIZE.repeat(N));
+}
+
+impl<T> MaxSize for Option<T>
+where
+    T: MaxSize,
+{
+    const MAX_SIZE: MaxSizes = u8::MAX_SIZE.add(T::MAX_SIZE);
+}
+
+impl<T, U> MaxSize for Result<T, U>
+where
+    T: MaxSize,
+    U: MaxSize,
+{
+    const MAX_SIZE: MaxSizes = u8::MAX_SIZE.add(T::MAX_SIZE.max(U::MAX_SIZE));
+}
+
+impl<T> MaxSize for Cell<T>
+where
+    T: MaxSize,
+{
+    const MAX_SIZE: MaxSizes = T::MAX_SIZE;
+}
+
+impl<T> MaxSize for RefCell<T>
+where
+    T: MaxSize,
+{
+    const MAX_SIZE: MaxSizes = T::MAX_SIZE;
+}
+
+impl MaxSize for Duration {
+    const MAX_SIZE: MaxSizes = u64::MAX_SIZE.add(u32::MAX_SIZE);
+}
+
+impl<T> MaxSize for Range<T>
+where
+    T: MaxSize,
+{
+    const MAX_SIZE: MaxSizes = T::MAX_SIZE.repeat(2);
+}
+
+impl<T> MaxSize for RangeInclusive<T>
+where
+    T: MaxSize,
+{
+    const MAX_SIZE: MaxSizes = T::MAX_SIZE.repeat(2);
+}
+
+impl<T> MaxSize for Bound<T>
+where
+    T: MaxSize,
+{
+    const MAX_SIZE: MaxSizes = u32::MAX_SIZE.add(T::MAX_SIZE);
+}
+
+impl<T> MaxSize for &T
+where
+    T: MaxSize,
+{
+    const MAX_SIZE: MaxSizes = T::MAX_SIZE;
+}
+
+impl<A> MaxSize for (A,)
+where
+    A: MaxSize,
+{
+    const MAX_SIZE: MaxSizes = A::MAX_SIZE;
+}
+
+impl<A, B> MaxSize for (A, B)
+where
+    A: MaxSize,
+    B: MaxSize,
+{
+    const MAX_SIZE: MaxSizes = A::MAX_SIZE.add(B::MAX_SIZE);
+}
+
+impl<A, B, C> MaxSize for (A, B, C)
+where
+    A: MaxSize,
+    B: MaxSize,
+    C: MaxSize,
+{
+    const MAX_SIZE: MaxSizes = A::MAX_SIZE.add(B::MAX_SIZE).add(C::MAX_SIZE);
+}
+
+impl<A, B, C, D> MaxSize for (A, B, C, D)
+where
+    A: MaxSize,
+    B: MaxSize,
+    C: MaxSize,
+    D: MaxSize,
+{
+    const MAX_SIZE: MaxSizes = A::MAX_SIZE
+        .add(B::MAX_SIZE)
+        .add(C::MAX_SIZE)
+        .add(D::MAX_SIZE);
+}
+
+impl<A, B, C, D, E> MaxSize for (A, B, C, D, E)
+where
+    A: MaxSize,
+    B: MaxSize,
+    C: MaxSize,
+    D: MaxSize,
+    E: MaxSize,
+{
+    const MAX_SIZE: MaxSizes = A::MAX_SIZE
+        .add(B::MAX_SIZE)
+        .add(C::MAX_SIZE)
+        .add(D::MAX_SIZE)
+        .add(E::MAX_SIZE);
+}
+
+impl<A, B, C, D, E, F> MaxSize for (A, B, C, D, E, F)
+where
+    A: MaxSize,
+    B: MaxSize,
+    C: MaxSize,
+    D: MaxSize,
+    E: MaxSize,
+    F: MaxSize,
+{
+    const MAX_SIZE: MaxSizes = A::MAX_SIZE
+        .add(B::MAX_SIZE)
+        .add(C::MAX_SIZE)
+        .add(D::MAX_SIZE)
+        .add(E::MAX_SIZE)
+        .add(F::MAX_SIZE);
+}
+
+impl<A, B, C, D, E, F, G> MaxSize for (A, B, C, D, E, F, G)
+where
+    A: MaxSize,
+    B: MaxSize,
+    C: MaxSize,
+    D: MaxSize,
+    E: MaxSize,
+    F: MaxSize,
+    G: MaxSize,
+{
+    const MAX_SIZE: MaxSizes = A::MAX_SIZE
+        .add(B::MAX_SIZE)
+        .add(C::MAX_SIZE)
+        .add(D::MAX_SIZE)
+        .add(E::MAX_SIZE)
+        .add(F::MAX_SIZE)
+        .add(G::MAX_SIZE);
+}
+
+impl<A, B, C, D, E, F, G, H> MaxSize for (A, B, C, D, E, F, G, H)
+where
+    A: MaxSize,
+    B: MaxSize,
+    C: MaxSize,
+    D: MaxSize,
+    E: MaxSize,
+    F: MaxSize,
+    G: MaxSize,
+    H: MaxSize,
+{
+    const MAX_SIZE: MaxSizes = A::MAX_SIZE
+        .add(B::MAX_SIZE)
+        .add(C::MAX_SIZE)
+        .add(D::MAX_SIZE)
+        .add(E::MAX_SIZE)
+        .add(F::MAX_SIZE)
+        .add(G::MAX_SIZE)
+        .add(H::MAX_SIZE);
+}
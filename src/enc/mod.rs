@@ -3,6 +3,7 @@
 mod encoder;
 mod impl_tuples;
 mod impls;
+mod max_size;
 
 use self::write::Writer;
 use crate::{config::Config, error::EncodeError, utils::Sealed};
@@ -10,6 +11,7 @@ use crate::{config::Config, error::EncodeError, utils::Sealed};
 pub mod write;
 
 pub use self::encoder::EncoderImpl;
+pub use self::max_size::{MaxSize, MaxSizes};
 
 /// Any source that can be encoded. This trait should be implemented for all types that you want to be able to use with any of the `encode_with` methods.
 ///
@@ -47,7 +49,6 @@ pub use self::encoder::EncoderImpl;
 /// ```
 ///
 /// From here you can add/remove fields, or add custom logic.
-
 pub trait Encode {
     /// Encode a given type.
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError>;
@@ -68,7 +69,7 @@ pub trait Encoder: Sealed {
     fn config(&self) -> &Self::C;
 }
 
-impl<'a, T> Encoder for &'a mut T
+impl<T> Encoder for &mut T
 where
     T: Encoder,
 {
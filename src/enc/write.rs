@@ -10,6 +10,29 @@ use crate::error::EncodeError;
 pub trait Writer {
     /// Write `bytes` to the underlying writer. Exactly `bytes.len()` bytes must be written, or else an error should be returned.
     fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError>;
+
+    /// If this writer only counts the amount of bytes that are written to it, like [SizeWriter], count `n` more bytes and return `true`. Otherwise nothing happens and `false` is returned.
+    ///
+    /// Length-prefixed values, like structs with `#[bincode(default_trailing)]`, have to be encoded once to find their length. This lets them skip encoding their content a second time when only the size is needed.
+    #[inline]
+    fn count_only(&mut self, _n: usize) -> bool {
+        false
+    }
+}
+
+impl<T> Writer for &mut T
+where
+    T: Writer,
+{
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        (**self).write(bytes)
+    }
+
+    #[inline]
+    fn count_only(&mut self, n: usize) -> bool {
+        (**self).count_only(n)
+    }
 }
 
 /// A helper struct that implements `Writer` for a `&[u8]` slice.
@@ -58,3 +81,35 @@ impl<'storage> Writer for SliceWriter<'storage> {
         Ok(())
     }
 }
+
+/// A writer that only counts the amount of bytes that are written to it. This is used by [encoded_size] to calculate the size of a value without having to allocate a buffer for it.
+///
+/// ```
+/// use bincode::enc::write::{Writer, SizeWriter};
+///
+/// let mut writer = SizeWriter::default();
+/// writer.write(&[1, 2, 3, 4, 5]).unwrap();
+///
+/// assert_eq!(writer.bytes_written, 5);
+/// ```
+///
+/// [encoded_size]: ../../fn.encoded_size.html
+#[derive(Default)]
+pub struct SizeWriter {
+    /// The amount of bytes that were written so far
+    pub bytes_written: usize,
+}
+
+impl Writer for SizeWriter {
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.bytes_written += bytes.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn count_only(&mut self, n: usize) -> bool {
+        self.bytes_written += n;
+        true
+    }
+}
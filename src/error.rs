@@ -73,6 +73,21 @@ pub enum DecodeError {
     /// The given configuration limit was exceeded
     LimitExceeded,
 
+    /// The decoder tried to decode a container with `found` entries, but the configuration only allows containers with up to `allowed` entries. See [with_max_container_len].
+    ///
+    /// [with_max_container_len]: ../config/struct.Configuration.html#method.with_max_container_len
+    ContainerLengthExceeded {
+        /// The length of the container found in the binary format.
+        found: usize,
+        /// The maximum container length of the configuration.
+        allowed: usize,
+    },
+
+    /// The decoder tried to decode a value that is nested deeper than the maximum depth of the configuration. See [with_max_depth].
+    ///
+    /// [with_max_depth]: ../config/struct.Configuration.html#method.with_max_depth
+    DepthLimitExceeded,
+
     /// Invalid type was found. The decoder tried to read type `expected`, but found type `found` instead.
     InvalidIntegerType {
         /// The type that was being read from the reader
@@ -81,6 +96,14 @@ pub enum DecodeError {
         found: IntegerType,
     },
 
+    /// The decoder tried to decode an integer of type `expected` with [with_leb128_int_encoding], but the encoded value is too large for that type.
+    ///
+    /// [with_leb128_int_encoding]: ../config/struct.Configuration.html#method.with_leb128_int_encoding
+    Leb128Overflow {
+        /// The type that was being read from the reader
+        expected: IntegerType,
+    },
+
     /// The decoder tried to decode any of the `NonZero*` types but the value is zero
     NonZeroTypeIsZero {
         /// The type that was being read from the reader
@@ -122,6 +145,47 @@ pub enum DecodeError {
         type_name: &'static str,
     },
 
+    /// A type with a `#[bincode(try_from = "T")]` attribute could not be converted from the decoded `T`.
+    ///
+    /// With the `alloc` feature, [ConversionFailedString] is returned instead, which contains the message of the conversion error.
+    ///
+    /// [ConversionFailedString]: #variant.ConversionFailedString
+    ConversionFailed {
+        /// The type that was being decoded
+        type_name: &'static str,
+    },
+
+    /// A type with a `#[bincode(try_from = "T")]` attribute could not be converted from the decoded `T`.
+    #[cfg(feature = "alloc")]
+    ConversionFailedString {
+        /// The type that was being decoded
+        type_name: &'static str,
+        /// The message of the conversion error
+        message: alloc::string::String,
+    },
+
+    /// The fields of a struct with `#[bincode(default_trailing)]` were longer than the length of its record. See [Record].
+    ///
+    /// [Record]: ../de/struct.Record.html
+    RecordLengthExceeded {
+        /// The length of the record
+        length: usize,
+        /// The amount of bytes that were read while decoding the fields of the record
+        read: usize,
+    },
+
+    /// The decoder tried to decode a type with `#[bincode(version = N)]`, but found a version that is not supported by this type. See [DecodeVersioned].
+    ///
+    /// [DecodeVersioned]: ../de/trait.DecodeVersioned.html
+    UnknownVersion {
+        /// The type that was being decoded
+        type_name: &'static str,
+        /// The version that was found
+        found: u32,
+        /// The current version of the type
+        current: u32,
+    },
+
     /// The decoder tried to decode a Duration and overflowed the number of seconds.
     InvalidDuration {
         /// The number of seconds in the duration.
@@ -153,10 +217,28 @@ pub enum DecodeError {
         inner: std::ffi::FromVecWithNulError,
     },
 
+    /// An uncommon error occured, see the inner text for more information
+    Other(&'static str),
+
     /// An uncommon error occured, see the inner text for more information
     #[cfg(feature = "alloc")]
     OtherString(alloc::string::String),
 
+    /// The `inner` error occured while decoding a field of a type with a derived `Decode` or `BorrowDecode` implementation.
+    ///
+    /// Use [without_context] to get the `inner` error.
+    ///
+    /// [without_context]: #method.without_context
+    #[cfg(feature = "alloc")]
+    WithContext {
+        /// The amount of bytes that were read when the `inner` error occured.
+        offset: usize,
+        /// The fields that were being decoded, from the outermost to the innermost type. Every entry is formatted as `Type.field` for structs or `Type::Variant.field` for enums, e.g. `["Message.header", "Header::V1.kind"]`.
+        path: alloc::vec::Vec<&'static str>,
+        /// The error that occured.
+        inner: alloc::boxed::Box<DecodeError>,
+    },
+
     #[cfg(feature = "serde")]
     /// A serde-specific error that occured while decoding.
     Serde(crate::features::serde::DecodeError),
@@ -170,7 +252,69 @@ impl core::fmt::Display for DecodeError {
 }
 
 impl DecodeError {
-    /// If the current error is `InvalidIntegerType`, change the `expected` and
+    /// Returns the error without any context. If this is a `DecodeError::WithContext`, this is the `inner` error, otherwise this is `self`.
+    pub fn without_context(&self) -> &DecodeError {
+        match self {
+            #[cfg(feature = "alloc")]
+            Self::WithContext { inner, .. } => inner,
+            other => other,
+        }
+    }
+
+    /// Add the field that was being decoded to the path of this error, wrapping it in `DecodeError::WithContext` if needed. `offset` is only used if the error does not have a context yet.
+    ///
+    /// This is used by the derive macros. Without the `alloc` feature this returns the error as is.
+    #[doc(hidden)]
+    pub fn with_context(self, offset: usize, field: &'static str) -> DecodeError {
+        #[cfg(feature = "alloc")]
+        {
+            match self {
+                Self::WithContext {
+                    offset,
+                    mut path,
+                    inner,
+                } => {
+                    path.insert(0, field);
+                    Self::WithContext {
+                        offset,
+                        path,
+                        inner,
+                    }
+                }
+                inner => Self::WithContext {
+                    offset,
+                    path: alloc::vec![field],
+                    inner: alloc::boxed::Box::new(inner),
+                },
+            }
+        }
+        #[cfg(not(feature = "alloc"))]
+        {
+            let _ = (offset, field);
+            self
+        }
+    }
+
+    /// Create a `DecodeError::ConversionFailedString` for `type_name` with the message of `error`.
+    ///
+    /// This is used by the derive macros. Without the `alloc` feature the message is discarded and a `DecodeError::ConversionFailed` is returned.
+    #[doc(hidden)]
+    pub fn conversion_failed<E: core::fmt::Display>(type_name: &'static str, error: E) -> Self {
+        #[cfg(feature = "alloc")]
+        {
+            Self::ConversionFailedString {
+                type_name,
+                message: alloc::string::ToString::to_string(&error),
+            }
+        }
+        #[cfg(not(feature = "alloc"))]
+        {
+            let _ = error;
+            Self::ConversionFailed { type_name }
+        }
+    }
+
+    /// If the current error is `InvalidIntegerType` or `Leb128Overflow`, change the `expected` and
     /// `found` values from `Ux` to `Ix`. This is needed to have correct error
     /// reporting in src/varint/decode_signed.rs since this calls
     /// src/varint/decode_unsigned.rs and needs to correct the `expected` and
@@ -181,6 +325,9 @@ impl DecodeError {
                 expected: expected.into_signed(),
                 found: found.into_signed(),
             },
+            Self::Leb128Overflow { expected } => Self::Leb128Overflow {
+                expected: expected.into_signed(),
+            },
             other => other,
         }
     }
@@ -199,7 +346,7 @@ pub enum AllowedEnumVariants {
 
 /// Integer types. Used by [DecodeError]. These types have no purpose other than being shown in errors.
 #[non_exhaustive]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[allow(missing_docs)]
 pub enum IntegerType {
     U8,
@@ -1,3 +1,5 @@
+#[cfg(feature = "alloc")]
+use crate::schema::{Definitions, Schema, Type};
 use crate::{de::Decode, enc::Encode};
 use core::sync::atomic::{
     AtomicBool, AtomicI16, AtomicI32, AtomicI64, AtomicI8, AtomicIsize, AtomicU16, AtomicU32,
@@ -168,3 +170,80 @@ impl Decode for AtomicIsize {
         Ok(AtomicIsize::new(Decode::decode(decoder)?))
     }
 }
+
+#[cfg(feature = "alloc")]
+impl Schema for AtomicBool {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::Bool
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Schema for AtomicU8 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::U8
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Schema for AtomicU16 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::U16
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Schema for AtomicU32 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::U32
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Schema for AtomicU64 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::U64
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Schema for AtomicUsize {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::U64
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Schema for AtomicI8 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::I8
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Schema for AtomicI16 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::I16
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Schema for AtomicI32 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::I32
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Schema for AtomicI64 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::I64
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Schema for AtomicIsize {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::I64
+    }
+}
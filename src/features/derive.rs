@@ -1,2 +1,2 @@
 #[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
-pub use bincode_derive::{BorrowDecode, Decode, Encode};
+pub use bincode_derive::{BorrowDecode, Decode, Encode, MaxSize, Schema};
@@ -1,7 +1,8 @@
 use crate::{
-    de::{Decode, Decoder},
-    enc::{self, Encode, Encoder},
+    de::{read::Reader, Decode, Decoder},
+    enc::{self, write::Writer, Encode, Encoder},
     error::{DecodeError, EncodeError},
+    schema::{Definitions, Schema, Type},
     Config,
 };
 #[cfg(feature = "atomic")]
@@ -46,6 +47,17 @@ pub fn encode_to_vec<E: enc::Encode, C: Config>(val: E, config: C) -> Result<Vec
     Ok(encoder.into_writer().inner)
 }
 
+/// Returns the slice as bytes if `T` is `u8`, so it can be written in one go.
+fn try_as_bytes<T>(slice: &[T]) -> Option<&[u8]> {
+    if crate::utils::type_equal::<T, u8>() {
+        // Safety: we know that T is a u8, so it is perfectly safe to
+        // translate a slice of T into a slice of u8
+        Some(unsafe { core::slice::from_raw_parts(slice.as_ptr() as *const u8, slice.len()) })
+    } else {
+        None
+    }
+}
+
 impl<T> Decode for BinaryHeap<T>
 where
     T: Decode + Ord,
@@ -154,6 +166,11 @@ where
     T: Decode,
 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        if crate::utils::type_equal::<T, u8>() {
+            // Decode `VecDeque<u8>` in one go, see `Vec<T>` below
+            return Vec::<T>::decode(decoder).map(VecDeque::from);
+        }
+
         let len = crate::de::decode_slice_len(decoder)?;
         decoder.claim_container_read::<T>(len)?;
 
@@ -175,6 +192,11 @@ where
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         crate::enc::encode_slice_len(encoder, self.len())?;
+        let (front, back) = self.as_slices();
+        if let (Some(front), Some(back)) = (try_as_bytes(front), try_as_bytes(back)) {
+            encoder.writer().write(front)?;
+            return encoder.writer().write(back);
+        }
         for item in self.iter() {
             item.encode(encoder)?;
         }
@@ -190,6 +212,19 @@ where
         let len = crate::de::decode_slice_len(decoder)?;
         decoder.claim_container_read::<T>(len)?;
 
+        // Optimize for `Vec<u8>`, which is also used by `String` and `Box<[u8]>`
+        if crate::utils::type_equal::<T, u8>() {
+            let mut vec = alloc::vec![0u8; len];
+            decoder.reader().read(&mut vec)?;
+            let mut vec = core::mem::ManuallyDrop::new(vec);
+
+            // Safety: we know that T is a u8, so it is perfectly safe to
+            // translate a Vec<u8> into a Vec<T>
+            let vec =
+                unsafe { Vec::from_raw_parts(vec.as_mut_ptr() as *mut T, len, vec.capacity()) };
+            return Ok(vec);
+        }
+
         let mut vec = Vec::with_capacity(len);
         for _ in 0..len {
             // See the documentation on `unclaim_bytes_read` as to why we're doing this here
@@ -207,6 +242,9 @@ where
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         crate::enc::encode_slice_len(encoder, self.len())?;
+        if let Some(bytes) = try_as_bytes(self) {
+            return encoder.writer().write(bytes);
+        }
         for item in self.iter() {
             item.encode(encoder)?;
         }
@@ -262,6 +300,9 @@ where
 {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         crate::enc::encode_slice_len(encoder, self.len())?;
+        if let Some(bytes) = try_as_bytes(self) {
+            return encoder.writer().write(bytes);
+        }
         for item in self.iter() {
             item.encode(encoder)?;
         }
@@ -342,3 +383,95 @@ where
         T::encode(self, encoder)
     }
 }
+
+impl<T> Schema for BinaryHeap<T>
+where
+    T: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        Type::Seq(Box::new(T::schema(definitions)))
+    }
+}
+
+impl<K, V> Schema for BTreeMap<K, V>
+where
+    K: Schema,
+    V: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        Type::Map {
+            key: Box::new(K::schema(definitions)),
+            value: Box::new(V::schema(definitions)),
+        }
+    }
+}
+
+impl<T> Schema for BTreeSet<T>
+where
+    T: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        Type::Seq(Box::new(T::schema(definitions)))
+    }
+}
+
+impl<T> Schema for VecDeque<T>
+where
+    T: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        Type::Seq(Box::new(T::schema(definitions)))
+    }
+}
+
+impl<T> Schema for Vec<T>
+where
+    T: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        Type::Seq(Box::new(T::schema(definitions)))
+    }
+}
+
+impl Schema for String {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::String
+    }
+}
+
+impl<T> Schema for Box<T>
+where
+    T: Schema + ?Sized,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        T::schema(definitions)
+    }
+}
+
+impl<'cow, T> Schema for Cow<'cow, T>
+where
+    T: Schema + ToOwned + ?Sized,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        T::schema(definitions)
+    }
+}
+
+impl<T> Schema for Rc<T>
+where
+    T: Schema + ?Sized,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        T::schema(definitions)
+    }
+}
+
+#[cfg(feature = "atomic")]
+impl<T> Schema for Arc<T>
+where
+    T: Schema + ?Sized,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        T::schema(definitions)
+    }
+}
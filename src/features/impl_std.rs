@@ -3,10 +3,14 @@ use crate::{
     de::{read::Reader, BorrowDecode, BorrowDecoder, Decode, Decoder, DecoderImpl},
     enc::{write::Writer, Encode, Encoder, EncoderImpl},
     error::{DecodeError, EncodeError},
+    schema::{
+        Definition, Definitions, EnumSchema, Field, Schema, StructLayout, StructSchema, Type,
+        Variant,
+    },
 };
 use core::time::Duration;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     ffi::{CStr, CString},
     io::Read,
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
@@ -30,13 +34,127 @@ pub fn decode_from_std_read<D: Decode, C: Config, R: std::io::Read>(
     D::decode(&mut decoder)
 }
 
-pub(crate) struct IoReader<R> {
+/// Decode values of type `D` from the given reader until the reader reaches its end. The reader can be any type that implements `std::io::Read`, e.g. `std::fs::File`.
+///
+/// The returned iterator stops when the reader reaches its end between two values. If the reader ends in the middle of a value, an error is returned whose [without_context] is `DecodeError::UnexpectedEnd`. After an error is returned, the iterator yields no more values.
+///
+/// The limit of the config (if any) is applied to every value separately. Types that are encoded as 0 bytes, like `()`, can not be decoded with this iterator, because the amount of values in the reader is unknown. Decoding them returns `DecodeError::Other`.
+///
+/// ```
+/// let config = bincode::config::standard();
+/// let mut file = Vec::new();
+/// for value in [(1u32, "a"), (2, "b"), (3, "c")] {
+///     bincode::encode_into_std_write(value, &mut file, config).unwrap();
+/// }
+///
+/// let values: Vec<(u32, String)> = bincode::decode_iter_from_std_read(&file[..], config)
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(values, [(1, "a".into()), (2, "b".into()), (3, "c".into())]);
+/// ```
+///
+/// See the [config] module for more information about config options.
+///
+/// [config]: config/index.html
+/// [without_context]: error/enum.DecodeError.html#method.without_context
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn decode_iter_from_std_read<D: Decode, C: Config, R: std::io::Read>(
+    src: R,
+    config: C,
+) -> DecodeStdReadIter<D, C, R> {
+    DecodeStdReadIter {
+        reader: src,
+        config,
+        done: false,
+        _d: core::marker::PhantomData,
+    }
+}
+
+/// An iterator that decodes values of type `D` from a `std::io::Read` until the reader reaches its end.
+///
+/// This is returned by [decode_iter_from_std_read]. See its documentation for more information.
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub struct DecodeStdReadIter<D, C, R> {
+    reader: R,
+    config: C,
+    done: bool,
+    _d: core::marker::PhantomData<fn() -> D>,
+}
+
+impl<D, C, R> DecodeStdReadIter<D, C, R> {
+    /// Return the inner reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Read the first byte of the next value, or `None` if the reader is at its end.
+    fn read_first_byte(&mut self) -> Result<Option<u8>, DecodeError>
+    where
+        R: std::io::Read,
+    {
+        let mut byte = [0u8];
+        loop {
+            match self.reader.read(&mut byte) {
+                Ok(0) => return Ok(None),
+                Ok(_) => return Ok(Some(byte[0])),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(_) => return Err(DecodeError::UnexpectedEnd),
+            }
+        }
+    }
+}
+
+impl<D: Decode, C: Config, R: std::io::Read> Iterator for DecodeStdReadIter<D, C, R> {
+    type Item = Result<D, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        // Read a single byte first, so the end of the reader can be told apart from a value that is cut off
+        let result = match self.read_first_byte() {
+            Ok(None) => {
+                self.done = true;
+                return None;
+            }
+            Ok(Some(first)) => {
+                let first = [first];
+                let reader = IoReader::new((&first[..]).chain(&mut self.reader));
+                let mut decoder = DecoderImpl::<_, C>::new(reader, self.config);
+                match D::decode(&mut decoder) {
+                    // A value that is encoded as 0 bytes would be decoded forever
+                    Ok(_) if decoder.offset() == 0 => {
+                        Err(DecodeError::Other(crate::de::ZERO_SIZED_VALUE))
+                    }
+                    result => result,
+                }
+            }
+            Err(e) => Err(e),
+        };
+        if result.is_err() {
+            self.done = true;
+        }
+        Some(result)
+    }
+}
+
+impl<D: Decode, C: Config, R: std::io::Read> core::iter::FusedIterator
+    for DecodeStdReadIter<D, C, R>
+{
+}
+
+/// A [Reader] for any type that implements `std::io::Read`.
+///
+/// Every read is forwarded to `std::io::Read::read_exact`, so this will never read more bytes from the inner reader than bincode requests. Consider wrapping the inner reader in a `std::io::BufReader` if it is slow to read from.
+pub struct IoReader<R> {
     reader: R,
+    offset: usize,
 }
 
 impl<R> IoReader<R> {
+    /// Create a new `IoReader` that reads from the given reader
     pub fn new(reader: R) -> Self {
-        Self { reader }
+        Self { reader, offset: 0 }
     }
 }
 
@@ -47,10 +165,18 @@ where
     #[inline(always)]
     fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
         match self.reader.read_exact(bytes) {
-            Ok(_) => Ok(()),
+            Ok(_) => {
+                self.offset += bytes.len();
+                Ok(())
+            }
             Err(_) => Err(DecodeError::UnexpectedEnd),
         }
     }
+
+    #[inline]
+    fn offset(&self) -> usize {
+        self.offset
+    }
 }
 
 impl<R> Reader for std::io::BufReader<R>
@@ -91,12 +217,14 @@ pub fn encode_into_std_write<E: Encode, C: Config, W: std::io::Write>(
     Ok(encoder.into_writer().bytes_written())
 }
 
-pub(crate) struct IoWriter<'a, W: std::io::Write> {
+/// A [Writer] for any type that implements `std::io::Write`.
+pub struct IoWriter<'a, W: std::io::Write> {
     writer: &'a mut W,
     bytes_written: usize,
 }
 
 impl<'a, W: std::io::Write> IoWriter<'a, W> {
+    /// Create a new `IoWriter` that writes to the given writer
     pub fn new(writer: &'a mut W) -> Self {
         Self {
             writer,
@@ -104,6 +232,7 @@ impl<'a, W: std::io::Write> IoWriter<'a, W> {
         }
     }
 
+    /// The amount of bytes that were written so far
     pub fn bytes_written(&self) -> usize {
         self.bytes_written
     }
@@ -123,7 +252,7 @@ impl<'storage, W: std::io::Write> Writer for IoWriter<'storage, W> {
     }
 }
 
-impl<'a> Encode for &'a CStr {
+impl Encode for &CStr {
     fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
         self.to_bytes_with_nul().encode(encoder)
     }
@@ -359,7 +488,7 @@ impl Decode for SocketAddrV6 {
 impl std::error::Error for EncodeError {}
 impl std::error::Error for DecodeError {}
 
-impl<K, V> Encode for HashMap<K, V>
+impl<K, V, S> Encode for HashMap<K, V, S>
 where
     K: Encode,
     V: Encode,
@@ -374,16 +503,18 @@ where
     }
 }
 
-impl<K, V> Decode for HashMap<K, V>
+impl<K, V, S> Decode for HashMap<K, V, S>
 where
     K: Decode + Eq + std::hash::Hash,
     V: Decode,
+    S: std::hash::BuildHasher + Default,
 {
     fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
         let len = crate::de::decode_slice_len(decoder)?;
         decoder.claim_container_read::<(K, V)>(len)?;
 
-        let mut map = HashMap::with_capacity(len);
+        let hash_builder: S = Default::default();
+        let mut map = HashMap::with_capacity_and_hasher(len, hash_builder);
         for _ in 0..len {
             // See the documentation on `unclaim_bytes_read` as to why we're doing this here
             decoder.unclaim_bytes_read(core::mem::size_of::<(K, V)>());
@@ -395,3 +526,198 @@ where
         Ok(map)
     }
 }
+
+impl<T, S> Encode for HashSet<T, S>
+where
+    T: Encode,
+{
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        crate::enc::encode_slice_len(encoder, self.len())?;
+        for item in self.iter() {
+            item.encode(encoder)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, S> Decode for HashSet<T, S>
+where
+    T: Decode + Eq + std::hash::Hash,
+    S: std::hash::BuildHasher + Default,
+{
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let len = crate::de::decode_slice_len(decoder)?;
+        decoder.claim_container_read::<T>(len)?;
+
+        let hash_builder: S = Default::default();
+        let mut set = HashSet::with_capacity_and_hasher(len, hash_builder);
+        for _ in 0..len {
+            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+            decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+
+            let key = T::decode(decoder)?;
+            set.insert(key);
+        }
+        Ok(set)
+    }
+}
+
+impl Schema for CStr {
+    fn schema(_: &mut Definitions) -> Type {
+        // Encoded as the bytes with the nul terminator
+        Type::Seq(std::boxed::Box::new(Type::U8))
+    }
+}
+
+impl Schema for CString {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::Seq(std::boxed::Box::new(Type::U8))
+    }
+}
+
+impl<T> Schema for Mutex<T>
+where
+    T: Schema + ?Sized,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        T::schema(definitions)
+    }
+}
+
+impl<T> Schema for RwLock<T>
+where
+    T: Schema + ?Sized,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        T::schema(definitions)
+    }
+}
+
+impl Schema for SystemTime {
+    fn schema(definitions: &mut Definitions) -> Type {
+        // Encoded as the duration since the unix epoch
+        Duration::schema(definitions)
+    }
+}
+
+impl Schema for Path {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::String
+    }
+}
+
+impl Schema for PathBuf {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::String
+    }
+}
+
+impl Schema for IpAddr {
+    fn schema(definitions: &mut Definitions) -> Type {
+        definitions.define(core::any::type_name::<Self>(), |definitions| {
+            Definition::Enum(EnumSchema {
+                version: None,
+                tag_type: Type::U32,
+                variants: std::vec![
+                    Variant::new(
+                        "V4",
+                        0,
+                        std::vec![Field::new("0", Ipv4Addr::schema(definitions))]
+                    ),
+                    Variant::new(
+                        "V6",
+                        1,
+                        std::vec![Field::new("0", Ipv6Addr::schema(definitions))]
+                    ),
+                ],
+            })
+        })
+    }
+}
+
+impl Schema for Ipv4Addr {
+    fn schema(definitions: &mut Definitions) -> Type {
+        <[u8; 4]>::schema(definitions)
+    }
+}
+
+impl Schema for Ipv6Addr {
+    fn schema(definitions: &mut Definitions) -> Type {
+        <[u8; 16]>::schema(definitions)
+    }
+}
+
+impl Schema for SocketAddr {
+    fn schema(definitions: &mut Definitions) -> Type {
+        definitions.define(core::any::type_name::<Self>(), |definitions| {
+            Definition::Enum(EnumSchema {
+                version: None,
+                tag_type: Type::U32,
+                variants: std::vec![
+                    Variant::new(
+                        "V4",
+                        0,
+                        std::vec![Field::new("0", SocketAddrV4::schema(definitions))]
+                    ),
+                    Variant::new(
+                        "V6",
+                        1,
+                        std::vec![Field::new("0", SocketAddrV6::schema(definitions))]
+                    ),
+                ],
+            })
+        })
+    }
+}
+
+impl Schema for SocketAddrV4 {
+    fn schema(definitions: &mut Definitions) -> Type {
+        definitions.define(core::any::type_name::<Self>(), |definitions| {
+            Definition::Struct(StructSchema {
+                version: None,
+                layout: StructLayout::Sequential,
+                fields: std::vec![
+                    Field::new("ip", Ipv4Addr::schema(definitions)),
+                    Field::new("port", Type::U16),
+                ],
+            })
+        })
+    }
+}
+
+impl Schema for SocketAddrV6 {
+    fn schema(definitions: &mut Definitions) -> Type {
+        definitions.define(core::any::type_name::<Self>(), |definitions| {
+            Definition::Struct(StructSchema {
+                version: None,
+                layout: StructLayout::Sequential,
+                fields: std::vec![
+                    Field::new("ip", Ipv6Addr::schema(definitions)),
+                    Field::new("port", Type::U16),
+                ],
+            })
+        })
+    }
+}
+
+impl<K, V, S> Schema for HashMap<K, V, S>
+where
+    K: Schema,
+    V: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        Type::Map {
+            key: std::boxed::Box::new(K::schema(definitions)),
+            value: std::boxed::Box::new(V::schema(definitions)),
+        }
+    }
+}
+
+impl<T, S> Schema for HashSet<T, S>
+where
+    T: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        Type::Seq(std::boxed::Box::new(T::schema(definitions)))
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{
+    config::Config,
+    de::{
+        read::{Reader, SliceReader},
+        Decode, DecoderImpl,
+    },
+    enc::Encode,
+    error::{DecodeError, EncodeError},
+};
+use tokio_incl::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// The maximum amount of bytes that [decode_from_async_read] keeps in memory for a single value, if the configuration has no limit.
+const DEFAULT_ASYNC_READ_LIMIT: usize = 16 * 1024 * 1024;
+
+/// The amount of bytes that [decode_from_async_read] reads at once. A value only takes up memory once its bytes have been received, even if it starts with a large length.
+const ASYNC_READ_CHUNK_SIZE: usize = 4096;
+
+/// Decode type `D` from the given async reader with the given `Config`. The reader can be any type that implements `tokio::io::AsyncRead`, e.g. a `tokio::net::TcpStream`.
+///
+/// Like [decode_from_std_read], only the bytes that belong to the decoded value are read from `src`, so this can be called repeatedly on the same reader to decode consecutive values.
+///
+/// **Note:** bincode's decoders are synchronous. The bytes of the value are read into a buffer, and every time the decoder reaches the end of that buffer, the bytes that it asked for are read from `src` and the value is decoded again from the start. A value that takes `k` separate reads to decode, e.g. a `Vec<u32>` with `k` elements, is decoded `k` times, so **the cost grows quadratically** with the size of such values. A `String` or `Vec<u8>` takes a single read. Send large values as frames with the [framing] module instead, which receives a whole frame before decoding it.
+///
+/// The bytes of the value are kept in memory until the value is complete. `DecodeError::LimitExceeded` is returned when a value needs more bytes than the limit of the configuration, or more than 16 MiB if the configuration has no limit.
+///
+/// See the [config] module for more information about config options.
+///
+/// [config]: config/index.html
+/// [decode_from_std_read]: fn.decode_from_std_read.html
+/// [framing]: framing/index.html
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub async fn decode_from_async_read<D: Decode, C: Config, R: AsyncRead + Unpin>(
+    src: &mut R,
+    config: C,
+) -> Result<D, DecodeError> {
+    let limit = config.limit().unwrap_or(DEFAULT_ASYNC_READ_LIMIT);
+    // The bytes of the value that have been read from `src` so far
+    let mut buffer = std::vec::Vec::new();
+    loop {
+        let mut needed = 0;
+        match decode_prefix::<D, C>(&buffer, config, &mut needed) {
+            Ok(value) => return Ok(value),
+            Err(e)
+                if matches!(e.without_context(), DecodeError::UnexpectedEnd)
+                    && needed > buffer.len() => {}
+            Err(e) => return Err(e),
+        }
+        if needed > limit {
+            return Err(DecodeError::LimitExceeded);
+        }
+        while buffer.len() < needed {
+            let start = buffer.len();
+            buffer.resize(needed.min(start + ASYNC_READ_CHUNK_SIZE), 0);
+            src.read_exact(&mut buffer[start..])
+                .await
+                .map_err(|_| DecodeError::UnexpectedEnd)?;
+        }
+    }
+}
+
+/// Decode `D` from the start of `bytes`.
+///
+/// If `bytes` ends in the middle of the value, `needed` is set to the amount of bytes that are needed to get past the read that failed.
+fn decode_prefix<D: Decode, C: Config>(
+    bytes: &[u8],
+    config: C,
+    needed: &mut usize,
+) -> Result<D, DecodeError> {
+    let mut reader = PartialReader {
+        reader: SliceReader::new(bytes),
+        needed: 0,
+    };
+    let mut decoder = DecoderImpl::<_, C>::new(&mut reader, config);
+    let result = D::decode(&mut decoder);
+    *needed = reader.needed;
+    result
+}
+
+/// A reader for the bytes of a value that have been received so far, which remembers how many bytes a read past its end needed
+struct PartialReader<'a> {
+    reader: SliceReader<'a>,
+    needed: usize,
+}
+
+impl Reader for PartialReader<'_> {
+    #[inline]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        let result = self.reader.read(bytes);
+        if result.is_err() {
+            self.needed = self.reader.offset() + bytes.len();
+        }
+        result
+    }
+
+    #[inline]
+    fn peek_read(&self, n: usize) -> Option<&[u8]> {
+        self.reader.peek_read(n)
+    }
+
+    #[inline]
+    fn consume(&mut self, n: usize) {
+        self.reader.consume(n)
+    }
+
+    #[inline]
+    fn offset(&self) -> usize {
+        self.reader.offset()
+    }
+}
+
+/// Encode the given value into any type that implements `tokio::io::AsyncWrite`, e.g. a `tokio::net::TcpStream`, with the given `Config`.
+/// Returns the amount of bytes that have been written. The writer is not flushed.
+///
+/// The value is encoded into a buffer first, which is then written to `dst`.
+///
+/// See the [config] module for more information.
+///
+/// [config]: config/index.html
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub async fn encode_into_async_write<E: Encode, C: Config, W: AsyncWrite + Unpin>(
+    val: E,
+    dst: &mut W,
+    config: C,
+) -> Result<usize, EncodeError> {
+    let buffer = crate::encode_to_vec(val, config)?;
+    let mut index = 0;
+    while index < buffer.len() {
+        match dst.write(&buffer[index..]).await {
+            Ok(0) => {
+                return Err(EncodeError::Io {
+                    error: std::io::ErrorKind::WriteZero.into(),
+                    index,
+                })
+            }
+            Ok(n) => index += n,
+            Err(error) => return Err(EncodeError::Io { error, index }),
+        }
+    }
+    Ok(index)
+}
@@ -1,7 +1,5 @@
 #[cfg(feature = "atomic")]
 mod atomic;
-#[cfg(feature = "atomic")]
-pub use self::atomic::*;
 
 #[cfg(feature = "alloc")]
 mod impl_alloc;
@@ -13,6 +11,11 @@ mod impl_std;
 #[cfg(feature = "std")]
 pub use self::impl_std::*;
 
+#[cfg(feature = "tokio")]
+mod impl_tokio;
+#[cfg(feature = "tokio")]
+pub use self::impl_tokio::*;
+
 #[cfg(feature = "derive")]
 mod derive;
 #[cfg(feature = "derive")]
@@ -27,6 +27,22 @@ pub(super) struct SerdeDecoder<'a, 'de, DE: BorrowDecoder<'de>> {
     pub(super) pd: PhantomData<&'de ()>,
 }
 
+impl<'a, 'de, DE: BorrowDecoder<'de>> SerdeDecoder<'a, 'de, DE> {
+    /// Call `f` one level of nesting deeper. See `Decoder::enter_depth` for more information.
+    fn nested<T>(
+        self,
+        f: impl FnOnce(SerdeDecoder<'_, 'de, DE>) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError> {
+        self.de.enter_depth()?;
+        let result = f(SerdeDecoder {
+            de: &mut *self.de,
+            pd: PhantomData,
+        })?;
+        self.de.leave_depth();
+        Ok(result)
+    }
+}
+
 impl<'a, 'de, DE: BorrowDecoder<'de>> Deserializer<'de> for SerdeDecoder<'a, 'de, DE> {
     type Error = DecodeError;
 
@@ -207,7 +223,7 @@ impl<'a, 'de, DE: BorrowDecoder<'de>> Deserializer<'de> for SerdeDecoder<'a, 'de
     where
         V: serde_incl::de::Visitor<'de>,
     {
-        visitor.visit_newtype_struct(self)
+        self.nested(|de| visitor.visit_newtype_struct(de))
     }
 
     fn deserialize_seq<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
@@ -215,10 +231,11 @@ impl<'a, 'de, DE: BorrowDecoder<'de>> Deserializer<'de> for SerdeDecoder<'a, 'de
         V: serde_incl::de::Visitor<'de>,
     {
         let len = u32::decode(&mut self.de)?;
-        self.deserialize_tuple(len as usize, visitor)
+        let len = crate::de::check_slice_len(&self.de, len.into())?;
+        self.deserialize_tuple(len, visitor)
     }
 
-    fn deserialize_tuple<V>(mut self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: serde_incl::de::Visitor<'de>,
     {
@@ -254,9 +271,11 @@ impl<'a, 'de, DE: BorrowDecoder<'de>> Deserializer<'de> for SerdeDecoder<'a, 'de
             }
         }
 
-        visitor.visit_seq(Access {
-            deserializer: &mut self,
-            len,
+        self.nested(|mut de| {
+            visitor.visit_seq(Access {
+                deserializer: &mut de,
+                len,
+            })
         })
     }
 
@@ -322,11 +341,13 @@ impl<'a, 'de, DE: BorrowDecoder<'de>> Deserializer<'de> for SerdeDecoder<'a, 'de
             }
         }
 
-        let len = usize::decode(&mut self.de)?;
+        let len = crate::de::decode_slice_len(&mut self.de)?;
 
-        visitor.visit_map(Access {
-            deserializer: &mut self,
-            len,
+        self.nested(|mut de| {
+            visitor.visit_map(Access {
+                deserializer: &mut de,
+                len,
+            })
         })
     }
 
@@ -351,7 +372,7 @@ impl<'a, 'de, DE: BorrowDecoder<'de>> Deserializer<'de> for SerdeDecoder<'a, 'de
     where
         V: serde_incl::de::Visitor<'de>,
     {
-        visitor.visit_enum(self)
+        self.nested(|de| visitor.visit_enum(de))
     }
 
     fn deserialize_identifier<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
@@ -20,8 +20,7 @@ where
     let mut decoder = crate::de::DecoderImpl::new(reader, config);
     let serde_decoder = SerdeDecoder { de: &mut decoder };
     let result = T::deserialize(serde_decoder)?;
-    let bytes_read = slice.len() - decoder.reader().slice.len();
-    Ok((result, bytes_read))
+    Ok((result, decoder.offset()))
 }
 
 /// Decode an owned type from the given `std::io::Read`.
@@ -39,6 +38,8 @@ pub fn decode_from_std_read<D: DeserializeOwned, C: Config, R: std::io::Read>(
 
 /// Attempt to decode a given type `D` from the given [Reader].
 ///
+/// The reader is wrapped in an [OffsetReader](crate::de::read::OffsetReader), so the offsets of decode errors are correct for readers that do not implement [Reader::offset].
+///
 /// See the [config] module for more information on configurations.
 ///
 /// [config]: config/index.html
@@ -46,6 +47,7 @@ pub fn decode_from_reader<D: DeserializeOwned, R: Reader, C: Config>(
     reader: R,
     config: C,
 ) -> Result<D, DecodeError> {
+    let reader = crate::de::read::OffsetReader::new(reader);
     let mut decoder = crate::de::DecoderImpl::<_, C>::new(reader, config);
     let serde_decoder = SerdeDecoder { de: &mut decoder };
     D::deserialize(serde_decoder)
@@ -55,6 +57,19 @@ pub(crate) struct SerdeDecoder<'a, DE: Decoder> {
     pub(crate) de: &'a mut DE,
 }
 
+impl<'a, DE: Decoder> SerdeDecoder<'a, DE> {
+    /// Call `f` one level of nesting deeper. See `Decoder::enter_depth` for more information.
+    fn nested<T>(
+        self,
+        f: impl FnOnce(SerdeDecoder<'_, DE>) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError> {
+        self.de.enter_depth()?;
+        let result = f(SerdeDecoder { de: &mut *self.de })?;
+        self.de.leave_depth();
+        Ok(result)
+    }
+}
+
 impl<'a, 'de, DE: Decoder> Deserializer<'de> for SerdeDecoder<'a, DE> {
     type Error = DecodeError;
 
@@ -250,7 +265,7 @@ impl<'a, 'de, DE: Decoder> Deserializer<'de> for SerdeDecoder<'a, DE> {
     where
         V: serde_incl::de::Visitor<'de>,
     {
-        visitor.visit_newtype_struct(self)
+        self.nested(|de| visitor.visit_newtype_struct(de))
     }
 
     fn deserialize_seq<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
@@ -258,10 +273,11 @@ impl<'a, 'de, DE: Decoder> Deserializer<'de> for SerdeDecoder<'a, DE> {
         V: serde_incl::de::Visitor<'de>,
     {
         let len = u32::decode(&mut self.de)?;
-        self.deserialize_tuple(len as usize, visitor)
+        let len = crate::de::check_slice_len(&self.de, len.into())?;
+        self.deserialize_tuple(len, visitor)
     }
 
-    fn deserialize_tuple<V>(mut self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: serde_incl::de::Visitor<'de>,
     {
@@ -296,9 +312,11 @@ impl<'a, 'de, DE: Decoder> Deserializer<'de> for SerdeDecoder<'a, DE> {
             }
         }
 
-        visitor.visit_seq(Access {
-            deserializer: &mut self,
-            len,
+        self.nested(|mut de| {
+            visitor.visit_seq(Access {
+                deserializer: &mut de,
+                len,
+            })
         })
     }
 
@@ -362,11 +380,13 @@ impl<'a, 'de, DE: Decoder> Deserializer<'de> for SerdeDecoder<'a, DE> {
             }
         }
 
-        let len = usize::decode(&mut self.de)?;
+        let len = crate::de::decode_slice_len(&mut self.de)?;
 
-        visitor.visit_map(Access {
-            deserializer: &mut self,
-            len,
+        self.nested(|mut de| {
+            visitor.visit_map(Access {
+                deserializer: &mut de,
+                len,
+            })
         })
     }
 
@@ -391,7 +411,7 @@ impl<'a, 'de, DE: Decoder> Deserializer<'de> for SerdeDecoder<'a, DE> {
     where
         V: serde_incl::de::Visitor<'de>,
     {
-        visitor.visit_enum(self)
+        self.nested(|de| visitor.visit_enum(de))
     }
 
     fn deserialize_identifier<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
@@ -0,0 +1,104 @@
+use super::{check_frame_len, FrameWriter};
+use crate::{
+    config::Config,
+    de::{read::SliceReader, Decode, DecoderImpl},
+    enc::{write::Writer, Encode},
+    error::{DecodeError, EncodeError},
+};
+use core::marker::PhantomData;
+use tokio_util_incl::{
+    bytes::BytesMut,
+    codec::{Decoder, Encoder},
+};
+
+/// A `tokio_util` codec that encodes and decodes length-delimited frames. See the [module documentation] for the format of a frame.
+///
+/// This decodes frames into values of `T`, and encodes any value that implements [Encode]. Errors are returned as `std::io::Error` with `ErrorKind::InvalidData`.
+///
+/// ```
+/// use bincode::framing::Codec;
+/// use tokio_util_incl::{bytes::BytesMut, codec::{Decoder, Encoder}};
+///
+/// let mut codec = Codec::<(u32, String), _>::new(bincode::config::standard());
+/// let mut buffer = BytesMut::new();
+/// codec.encode((5u32, "Hello"), &mut buffer).unwrap();
+///
+/// let decoded = codec.decode(&mut buffer).unwrap();
+/// assert_eq!(decoded, Some((5, String::from("Hello"))));
+/// assert!(buffer.is_empty());
+/// ```
+///
+/// [module documentation]: index.html
+/// [Encode]: ../enc/trait.Encode.html
+pub struct Codec<T, C> {
+    config: C,
+    _t: PhantomData<fn() -> T>,
+}
+
+impl<T, C: Config> Codec<T, C> {
+    /// Create a new codec with the given config.
+    pub fn new(config: C) -> Self {
+        Self {
+            config,
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<T, C: Config> Clone for Codec<T, C> {
+    fn clone(&self) -> Self {
+        Self::new(self.config)
+    }
+}
+
+impl<E: Encode, T, C: Config> Encoder<E> for Codec<T, C> {
+    type Error = std::io::Error;
+
+    fn encode(&mut self, item: E, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        FrameWriter::new(BytesMutWriter(dst), self.config)
+            .write_frame(item)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        Ok(())
+    }
+}
+
+impl<T: Decode, C: Config> Decoder for Codec<T, C> {
+    type Item = T;
+    type Error = std::io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<T>, Self::Error> {
+        let invalid_data = |e| std::io::Error::new(std::io::ErrorKind::InvalidData, e);
+
+        let (len, prefix_len) = match crate::decode_from_slice::<u64, C>(src, self.config) {
+            Ok((len, prefix_len)) => (
+                usize::try_from(len).map_err(|_| invalid_data(DecodeError::LimitExceeded))?,
+                prefix_len,
+            ),
+            Err(e) if matches!(e.without_context(), DecodeError::UnexpectedEnd) => return Ok(None),
+            Err(e) => return Err(invalid_data(e)),
+        };
+        check_frame_len(&self.config, len).map_err(invalid_data)?;
+        let frame_len = prefix_len
+            .checked_add(len)
+            .ok_or(DecodeError::LimitExceeded)
+            .map_err(invalid_data)?;
+        if src.len() < frame_len {
+            return Ok(None);
+        }
+
+        let frame = src.split_to(frame_len);
+        let reader = SliceReader::new(&frame[prefix_len..]);
+        let mut decoder = DecoderImpl::<_, C>::new(reader, self.config);
+        T::decode(&mut decoder).map(Some).map_err(invalid_data)
+    }
+}
+
+struct BytesMutWriter<'a>(&'a mut BytesMut);
+
+impl Writer for BytesMutWriter<'_> {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.0.extend_from_slice(bytes);
+        Ok(())
+    }
+}
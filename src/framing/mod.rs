@@ -0,0 +1,193 @@
+//! Length-delimited framing for streams of bincode values.
+//!
+//! Bincode values do not carry their own length, so when multiple values are sent over a stream (e.g. a `TcpStream`) the receiver can not tell where one value ends without decoding it. This module prefixes every encoded value with its length, so values can be read from and written to a stream one frame at a time.
+//!
+//! A frame consists of:
+//! - The length of the encoded value in bytes, encoded as a `u64` with the int encoding of the configuration.
+//! - The encoded value.
+//!
+//! If the configuration has a limit (see [with_limit]), frames that are larger than this limit are rejected before any of their content is read.
+//!
+//! ```
+//! # #[cfg(feature = "std")]
+//! # {
+//! use bincode::framing::{FrameReader, FrameWriter};
+//!
+//! let config = bincode::config::standard().with_limit::<1024>();
+//!
+//! let mut stream = Vec::new();
+//! let mut writer = FrameWriter::from_std_write(&mut stream, config);
+//! writer.write_frame((5u32, "Hello")).unwrap();
+//! writer.write_frame((10u32, "world")).unwrap();
+//!
+//! let mut reader = FrameReader::from_std_read(&stream[..], config);
+//! let first: (u32, String) = reader.read_frame().unwrap();
+//! let second: (u32, String) = reader.read_frame().unwrap();
+//! assert_eq!(first, (5, String::from("Hello")));
+//! assert_eq!(second, (10, String::from("world")));
+//! # }
+//! ```
+//!
+//! With the `tokio_util` feature, [Codec] implements `tokio_util::codec::{Encoder, Decoder}` with the same frame format.
+//!
+//! [with_limit]: ../config/struct.Configuration.html#method.with_limit
+
+#[cfg(feature = "tokio_util")]
+mod codec;
+#[cfg(feature = "tokio_util")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio_util")))]
+pub use self::codec::Codec;
+
+use crate::{
+    config::Config,
+    de::{read::Reader, Decode, DecoderImpl},
+    enc::{write::Writer, Encode, EncoderImpl},
+    error::{DecodeError, EncodeError},
+};
+
+/// Writes values as length-delimited frames to a [Writer]. See the [module documentation] for more information.
+///
+/// [module documentation]: index.html
+pub struct FrameWriter<W, C> {
+    writer: W,
+    config: C,
+}
+
+impl<W: Writer, C: Config> FrameWriter<W, C> {
+    /// Create a new `FrameWriter` that writes frames to the given writer.
+    pub fn new(writer: W, config: C) -> Self {
+        Self { writer, config }
+    }
+
+    /// Encode the given value as a single frame. Returns the amount of bytes that have been written, including the length prefix.
+    ///
+    /// The value is encoded twice: once to determine its length, and once to write it.
+    pub fn write_frame<E: Encode>(&mut self, val: E) -> Result<usize, EncodeError> {
+        let len = crate::encoded_size(&val, self.config)?;
+        let mut encoder = EncoderImpl::<_, C>::new(&mut self.writer, self.config);
+        crate::enc::encode_slice_len(&mut encoder, len)?;
+        val.encode(&mut encoder)?;
+        Ok(crate::encoded_size(len as u64, self.config)? + len)
+    }
+
+    /// Return the inner writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, W: std::io::Write, C: Config> FrameWriter<crate::IoWriter<'a, W>, C> {
+    /// Create a new `FrameWriter` that writes frames to any type that implements `std::io::Write`, e.g. a `std::net::TcpStream`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn from_std_write(writer: &'a mut W, config: C) -> Self {
+        Self::new(crate::IoWriter::new(writer), config)
+    }
+}
+
+/// Reads length-delimited frames from a [Reader] and decodes them. See the [module documentation] for more information.
+///
+/// [module documentation]: index.html
+pub struct FrameReader<R, C> {
+    reader: R,
+    config: C,
+}
+
+impl<R: Reader, C: Config> FrameReader<R, C> {
+    /// Create a new `FrameReader` that reads frames from the given reader.
+    pub fn new(reader: R, config: C) -> Self {
+        Self { reader, config }
+    }
+
+    /// Read the next frame and decode it as `D`.
+    ///
+    /// If the frame is larger than the limit of the configuration, `DecodeError::LimitExceeded` is returned. If `D` does not use all bytes of the frame, or if the frame could not be decoded as `D`, the remaining bytes are skipped so the next frame can be read.
+    pub fn read_frame<D: Decode>(&mut self) -> Result<D, DecodeError> {
+        let mut decoder = DecoderImpl::<_, C>::new(&mut self.reader, self.config);
+        let len =
+            usize::try_from(u64::decode(&mut decoder)?).map_err(|_| DecodeError::LimitExceeded)?;
+        check_frame_len(&self.config, len)?;
+
+        let mut frame = FrameContentReader {
+            reader: &mut self.reader,
+            len,
+            remaining: len,
+        };
+        let mut decoder = DecoderImpl::<_, C>::new(&mut frame, self.config);
+        let result = D::decode(&mut decoder);
+        // Skip the rest of the frame even if it could not be decoded, so the next frame can still be read
+        frame.skip_remaining()?;
+        result
+    }
+
+    /// Return the inner reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read, C: Config> FrameReader<crate::IoReader<R>, C> {
+    /// Create a new `FrameReader` that reads frames from any type that implements `std::io::Read`, e.g. a `std::net::TcpStream`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn from_std_read(reader: R, config: C) -> Self {
+        Self::new(crate::IoReader::new(reader), config)
+    }
+}
+
+/// Returns `DecodeError::LimitExceeded` if a frame of `len` bytes is larger than the limit of `config`
+pub(crate) fn check_frame_len<C: Config>(config: &C, len: usize) -> Result<(), DecodeError> {
+    match config.limit() {
+        Some(limit) if len > limit => Err(DecodeError::LimitExceeded),
+        _ => Ok(()),
+    }
+}
+
+/// A reader that can read at most `remaining` bytes from the inner reader
+struct FrameContentReader<R> {
+    reader: R,
+    len: usize,
+    remaining: usize,
+}
+
+impl<R: Reader> FrameContentReader<R> {
+    fn skip_remaining(&mut self) -> Result<(), DecodeError> {
+        let mut buffer = [0u8; 64];
+        while self.remaining > 0 {
+            let len = self.remaining.min(buffer.len());
+            self.read(&mut buffer[..len])?;
+        }
+        Ok(())
+    }
+}
+
+impl<R: Reader> Reader for FrameContentReader<R> {
+    #[inline]
+    fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+        if bytes.len() > self.remaining {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+        self.reader.read(bytes)?;
+        self.remaining -= bytes.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn peek_read(&self, n: usize) -> Option<&[u8]> {
+        if n > self.remaining {
+            return None;
+        }
+        self.reader.peek_read(n)
+    }
+
+    #[inline]
+    fn consume(&mut self, n: usize) {
+        self.reader.consume(n);
+        self.remaining -= n;
+    }
+
+    #[inline]
+    fn offset(&self) -> usize {
+        self.len - self.remaining
+    }
+}
@@ -17,10 +17,13 @@
 //!
 //! |Name  |Default?|Supported types for Encode/Decode|Enabled methods                                                  |Other|
 //! |------|--------|-----------------------------------------|-----------------------------------------------------------------|-----|
-//! |std   | Yes    |`HashMap`|`decode_from_std_read` and `encode_into_std_write`|
-//! |alloc | Yes    |All common containers in alloc, like `Vec`, `String`, `Box`|`encode_to_vec`|
+//! |std   | Yes    |`HashMap` and `HashSet`|`decode_from_std_read` and `encode_into_std_write`|
+//! |alloc | Yes    |All common containers in alloc, like `Vec`, `String`, `Box`|`encode_to_vec` and `schema_of`|
 //! |atomic| Yes    |All `Atomic*` integer types, e.g. `AtomicUsize`, and `AtomicBool`||
-//! |derive| Yes    |||Enables the `BorrowDecode`, `Decode` and `Encode` derive macros|
+//! |derive| Yes    |||Enables the `BorrowDecode`, `Decode`, `Encode`, `MaxSize` and `Schema` derive macros|
+//! |tokio | No     ||`decode_from_async_read` and `encode_into_async_write`|
+//! |tokio_util| No  ||[framing::Codec], a `tokio_util` codec for length-delimited frames|
+//! |cli   | No     |||Builds the `bincode-inspect` binary, which prints the values in a file as JSON or as an annotated hexdump, using the [schema] of their type|
 //! |serde | No     |`Compat` and `BorrowCompat`, which will work for all types that implement serde's traits|serde-specific encode/decode functions in the [serde] module|Note: There are several [known issues](serde/index.html#known-issues) when using serde and bincode|
 //!
 //! # Which functions to use
@@ -33,6 +36,15 @@
 //! |you're working with in-memory buffers|[`encode_to_vec`]|[`decode_from_slice`]|
 //! |You want to use a custom [Reader](de::read::Reader) and [writer](enc::write::Writer)|[`encode_into_writer`]|[`decode_from_reader`]|
 //! |You're working with pre-allocated buffers or on embedded targets|[`encode_into_slice`]|[`decode_from_slice`]|
+//! |You're reading a stream of back-to-back encoded values||[`decode_iter`] or [`decode_iter_from_std_read`]|
+//!
+//! If you need to know how large a buffer should be before encoding into it, use [`encoded_size`]. If your type implements [`MaxSize`], [`max_encoded_size`] gives you an upper bound at compile time.
+//!
+//! To describe the bytes that a type produces to other tools, use [`schema_of`] with a type that implements [`Schema`]. [`compat::check`] compares the schemas of two versions of a type, and reports the changes that break decoding existing data.
+//!
+//! To look inside encoded data without its Rust type, decode it into a [`value::Value`] with the schema of the type.
+//!
+//! Every function takes a config, usually [`config::standard()`]. If the options of the config are only known at runtime, e.g. from a config file, use a [`config::DynConfig`].
 //!
 //! **Note:** If you're using `serde`, use `bincode::serde::...` instead of `bincode::...`
 //!
@@ -86,15 +98,29 @@ pub(crate) mod varint;
 
 use de::{read::Reader, Decoder};
 use enc::write::Writer;
+// Nothing is re-exported when none of the features that add functions are enabled
+#[allow(unused_imports)]
 pub use features::*;
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod compat;
 pub mod config;
 pub mod de;
 pub mod enc;
 pub mod error;
+pub mod framing;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod schema;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod value;
 
 pub use de::{BorrowDecode, Decode};
-pub use enc::Encode;
+pub use enc::{Encode, MaxSize};
+#[cfg(feature = "alloc")]
+pub use schema::{schema_of, Schema};
 
 use config::Config;
 
@@ -129,6 +155,61 @@ pub fn encode_into_writer<E: enc::Encode, W: Writer, C: Config>(
     Ok(())
 }
 
+/// Calculate the amount of bytes the given value will take up when encoded with the given config, without writing it anywhere.
+///
+/// This is useful for pre-allocating buffers before calling e.g. [encode_into_slice]. The returned size will always match the amount of bytes [encode_into_slice] would write with the same config.
+///
+/// ```
+/// let config = bincode::config::standard();
+/// let value = (5u32, 1000u32, [0u8; 4]);
+///
+/// let size = bincode::encoded_size(&value, config).unwrap();
+/// assert_eq!(size, 1 + 3 + 4);
+///
+/// let mut slice = [0u8; 8];
+/// assert_eq!(bincode::encode_into_slice(&value, &mut slice, config).unwrap(), size);
+/// ```
+///
+/// See the [config] module for more information on configurations.
+///
+/// [config]: config/index.html
+pub fn encoded_size<E: enc::Encode, C: Config>(
+    val: E,
+    config: C,
+) -> Result<usize, error::EncodeError> {
+    let writer = enc::write::SizeWriter::default();
+    let mut encoder = enc::EncoderImpl::<_, C>::new(writer, config);
+    val.encode(&mut encoder)?;
+    Ok(encoder.into_writer().bytes_written)
+}
+
+/// Return the maximum amount of bytes that any value of type `T` can take up when encoded with the given config.
+///
+/// This is a `const fn`, so it can be used to declare a buffer that fits any value of `T`:
+///
+/// ```
+/// const CONFIG: bincode::config::Configuration = bincode::config::standard();
+/// const MAX_SIZE: usize = bincode::max_encoded_size::<(u32, Option<char>), _>(CONFIG);
+///
+/// let mut slice = [0u8; MAX_SIZE];
+/// let len = bincode::encode_into_slice((u32::MAX, Some('\u{10FFFF}')), &mut slice, CONFIG).unwrap();
+/// assert_eq!(len, MAX_SIZE);
+/// ```
+///
+/// See the [MaxSize] trait for more information.
+///
+/// The options of `C` have to be known at compile time, so this can not be used with a [DynConfig](config::DynConfig).
+///
+/// [MaxSize]: enc/trait.MaxSize.html
+pub const fn max_encoded_size<T, C>(config: C) -> usize
+where
+    T: enc::MaxSize,
+    C: Config + config::InternalIntEncodingConfig + config::InternalArrayLengthConfig,
+{
+    let _ = config;
+    T::MAX_SIZE.get::<C>()
+}
+
 /// Attempt to decode a given type `D` from the given slice.
 ///
 /// See the [config] module for more information on configurations.
@@ -141,12 +222,44 @@ pub fn decode_from_slice<'a, D: de::BorrowDecode<'a>, C: Config>(
     let reader = de::read::SliceReader::new(src);
     let mut decoder = de::DecoderImpl::<_, C>::new(reader, config);
     let result = D::borrow_decode(&mut decoder)?;
-    let bytes_read = src.len() - decoder.reader().slice.len();
-    Ok((result, bytes_read))
+    Ok((result, decoder.offset()))
+}
+
+/// Decode values of type `D` from a slice that contains multiple back-to-back encoded values.
+///
+/// The returned iterator yields a value for every encoded value in the slice, and stops at the end of the slice. If the slice ends in the middle of a value, an error is returned whose [without_context] is `DecodeError::UnexpectedEnd`. After an error is returned, the iterator yields no more values.
+///
+/// The limit of the config (if any) is applied to every value separately. Types that are encoded as 0 bytes, like `()`, can not be decoded with this iterator, because the amount of values in the slice is unknown. Decoding them returns `DecodeError::Other`.
+///
+/// ```
+/// let config = bincode::config::standard();
+/// let mut slice = [0u8; 100];
+/// let mut len = 0;
+/// for value in ["Hello", "world"] {
+///     len += bincode::encode_into_slice(value, &mut slice[len..], config).unwrap();
+/// }
+///
+/// let values: Vec<&str> = bincode::decode_iter(&slice[..len], config)
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(values, ["Hello", "world"]);
+/// ```
+///
+/// See the [config] module for more information on configurations.
+///
+/// [config]: config/index.html
+/// [without_context]: error/enum.DecodeError.html#method.without_context
+pub fn decode_iter<'a, D: de::BorrowDecode<'a>, C: Config>(
+    src: &'a [u8],
+    config: C,
+) -> de::DecodeIter<'a, D, C> {
+    de::DecodeIter::new(src, config)
 }
 
 /// Attempt to decode a given type `D` from the given [Reader].
 ///
+/// The reader is wrapped in an [OffsetReader](de::read::OffsetReader), so the offsets of decode errors are correct for readers that do not implement [Reader::offset], like `std::io::BufReader`.
+///
 /// See the [config] module for more information on configurations.
 ///
 /// [config]: config/index.html
@@ -154,6 +267,7 @@ pub fn decode_from_reader<D: de::Decode, R: Reader, C: Config>(
     reader: R,
     config: C,
 ) -> Result<D, error::DecodeError> {
+    let reader = de::read::OffsetReader::new(reader);
     let mut decoder = de::DecoderImpl::<_, C>::new(reader, config);
     D::decode(&mut decoder)
 }
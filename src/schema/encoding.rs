@@ -0,0 +1,307 @@
+use super::{
+    Definition, Definitions, Endian, EnumSchema, Field, IntEncoding, StructLayout, StructSchema,
+    Type, TypeSchema, Variant,
+};
+use crate::{
+    de::{Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{AllowedEnumVariants, DecodeError, EncodeError},
+};
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+
+impl Encode for TypeSchema {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.endian.encode(encoder)?;
+        self.int_encoding.encode(encoder)?;
+        self.skip_fixed_array_length.encode(encoder)?;
+        self.root.encode(encoder)?;
+        self.definitions.encode(encoder)
+    }
+}
+
+impl Decode for TypeSchema {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self {
+            endian: Endian::decode(decoder)?,
+            int_encoding: IntEncoding::decode(decoder)?,
+            skip_fixed_array_length: bool::decode(decoder)?,
+            root: Type::decode(decoder)?,
+            definitions: Definitions::decode(decoder)?,
+        })
+    }
+}
+
+impl Encode for Endian {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        match self {
+            Endian::Little => 0u32.encode(encoder),
+            Endian::Big => 1u32.encode(encoder),
+        }
+    }
+}
+
+impl Decode for Endian {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        match u32::decode(decoder)? {
+            0 => Ok(Endian::Little),
+            1 => Ok(Endian::Big),
+            found => Err(unexpected_variant::<Self>(found, 1)),
+        }
+    }
+}
+
+impl Encode for IntEncoding {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        match self {
+            IntEncoding::Fixed => 0u32.encode(encoder),
+            IntEncoding::Variable => 1u32.encode(encoder),
+            IntEncoding::Leb128 => 2u32.encode(encoder),
+        }
+    }
+}
+
+impl Decode for IntEncoding {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        match u32::decode(decoder)? {
+            0 => Ok(IntEncoding::Fixed),
+            1 => Ok(IntEncoding::Variable),
+            2 => Ok(IntEncoding::Leb128),
+            found => Err(unexpected_variant::<Self>(found, 2)),
+        }
+    }
+}
+
+impl Encode for Type {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        match self {
+            Type::Unit => 0u32.encode(encoder),
+            Type::Bool => 1u32.encode(encoder),
+            Type::U8 => 2u32.encode(encoder),
+            Type::U16 => 3u32.encode(encoder),
+            Type::U32 => 4u32.encode(encoder),
+            Type::U64 => 5u32.encode(encoder),
+            Type::U128 => 6u32.encode(encoder),
+            Type::I8 => 7u32.encode(encoder),
+            Type::I16 => 8u32.encode(encoder),
+            Type::I32 => 9u32.encode(encoder),
+            Type::I64 => 10u32.encode(encoder),
+            Type::I128 => 11u32.encode(encoder),
+            Type::F32 => 12u32.encode(encoder),
+            Type::F64 => 13u32.encode(encoder),
+            Type::Char => 14u32.encode(encoder),
+            Type::String => 15u32.encode(encoder),
+            Type::Option(ty) => {
+                16u32.encode(encoder)?;
+                ty.encode(encoder)
+            }
+            Type::Seq(ty) => {
+                17u32.encode(encoder)?;
+                ty.encode(encoder)
+            }
+            Type::Map { key, value } => {
+                18u32.encode(encoder)?;
+                key.encode(encoder)?;
+                value.encode(encoder)
+            }
+            Type::Array { item, len } => {
+                19u32.encode(encoder)?;
+                item.encode(encoder)?;
+                len.encode(encoder)
+            }
+            Type::Tuple(types) => {
+                20u32.encode(encoder)?;
+                types.encode(encoder)
+            }
+            Type::Named(name) => {
+                21u32.encode(encoder)?;
+                name.encode(encoder)
+            }
+            Type::Opaque(name) => {
+                22u32.encode(encoder)?;
+                name.encode(encoder)
+            }
+        }
+    }
+}
+
+impl Decode for Type {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        // Types can be nested indefinitely, so they count towards the max depth
+        decoder.enter_depth()?;
+        let result = match u32::decode(decoder)? {
+            0 => Type::Unit,
+            1 => Type::Bool,
+            2 => Type::U8,
+            3 => Type::U16,
+            4 => Type::U32,
+            5 => Type::U64,
+            6 => Type::U128,
+            7 => Type::I8,
+            8 => Type::I16,
+            9 => Type::I32,
+            10 => Type::I64,
+            11 => Type::I128,
+            12 => Type::F32,
+            13 => Type::F64,
+            14 => Type::Char,
+            15 => Type::String,
+            16 => Type::Option(Box::decode(decoder)?),
+            17 => Type::Seq(Box::decode(decoder)?),
+            18 => Type::Map {
+                key: Box::decode(decoder)?,
+                value: Box::decode(decoder)?,
+            },
+            19 => Type::Array {
+                item: Box::decode(decoder)?,
+                len: usize::decode(decoder)?,
+            },
+            20 => Type::Tuple(Vec::decode(decoder)?),
+            21 => Type::Named(String::decode(decoder)?),
+            22 => Type::Opaque(String::decode(decoder)?),
+            found => return Err(unexpected_variant::<Self>(found, 22)),
+        };
+        decoder.leave_depth();
+        Ok(result)
+    }
+}
+
+impl Encode for Definitions {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.definitions.encode(encoder)
+    }
+}
+
+impl Decode for Definitions {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self {
+            definitions: BTreeMap::decode(decoder)?,
+            pending: Default::default(),
+        })
+    }
+}
+
+impl Encode for Definition {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        match self {
+            Definition::Struct(schema) => {
+                0u32.encode(encoder)?;
+                schema.encode(encoder)
+            }
+            Definition::Enum(schema) => {
+                1u32.encode(encoder)?;
+                schema.encode(encoder)
+            }
+        }
+    }
+}
+
+impl Decode for Definition {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        match u32::decode(decoder)? {
+            0 => Ok(Definition::Struct(StructSchema::decode(decoder)?)),
+            1 => Ok(Definition::Enum(EnumSchema::decode(decoder)?)),
+            found => Err(unexpected_variant::<Self>(found, 1)),
+        }
+    }
+}
+
+impl Encode for StructSchema {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.version.encode(encoder)?;
+        self.layout.encode(encoder)?;
+        self.fields.encode(encoder)
+    }
+}
+
+impl Decode for StructSchema {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self {
+            version: Option::decode(decoder)?,
+            layout: StructLayout::decode(decoder)?,
+            fields: Vec::decode(decoder)?,
+        })
+    }
+}
+
+impl Encode for StructLayout {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        match self {
+            StructLayout::Sequential => 0u32.encode(encoder),
+            StructLayout::DefaultTrailing => 1u32.encode(encoder),
+            StructLayout::Tagged => 2u32.encode(encoder),
+        }
+    }
+}
+
+impl Decode for StructLayout {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        match u32::decode(decoder)? {
+            0 => Ok(StructLayout::Sequential),
+            1 => Ok(StructLayout::DefaultTrailing),
+            2 => Ok(StructLayout::Tagged),
+            found => Err(unexpected_variant::<Self>(found, 2)),
+        }
+    }
+}
+
+impl Encode for Field {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.name.encode(encoder)?;
+        self.tag.encode(encoder)?;
+        self.ty.encode(encoder)
+    }
+}
+
+impl Decode for Field {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self {
+            name: String::decode(decoder)?,
+            tag: Option::decode(decoder)?,
+            ty: Type::decode(decoder)?,
+        })
+    }
+}
+
+impl Encode for EnumSchema {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.version.encode(encoder)?;
+        self.tag_type.encode(encoder)?;
+        self.variants.encode(encoder)
+    }
+}
+
+impl Decode for EnumSchema {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self {
+            version: Option::decode(decoder)?,
+            tag_type: Type::decode(decoder)?,
+            variants: Vec::decode(decoder)?,
+        })
+    }
+}
+
+impl Encode for Variant {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.name.encode(encoder)?;
+        self.index.encode(encoder)?;
+        self.fields.encode(encoder)
+    }
+}
+
+impl Decode for Variant {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self {
+            name: String::decode(decoder)?,
+            index: u64::decode(decoder)?,
+            fields: Vec::decode(decoder)?,
+        })
+    }
+}
+
+fn unexpected_variant<T>(found: u32, max: u32) -> DecodeError {
+    DecodeError::UnexpectedVariant {
+        allowed: AllowedEnumVariants::Range { min: 0, max },
+        found,
+        type_name: core::any::type_name::<T>(),
+    }
+}
@@ -0,0 +1,477 @@
+use super::{
+    Definition, Definitions, EnumSchema, Field, Schema, StructLayout, StructSchema, Type, Variant,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::{
+    cell::{Cell, RefCell},
+    marker::PhantomData,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+        NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+    },
+    ops::{Bound, Range, RangeInclusive},
+    time::Duration,
+};
+
+impl Schema for () {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::Unit
+    }
+}
+
+impl<T> Schema for PhantomData<T> {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::Unit
+    }
+}
+
+impl Schema for bool {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::Bool
+    }
+}
+
+impl Schema for u8 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::U8
+    }
+}
+
+impl Schema for NonZeroU8 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::U8
+    }
+}
+
+impl Schema for u16 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::U16
+    }
+}
+
+impl Schema for NonZeroU16 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::U16
+    }
+}
+
+impl Schema for u32 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::U32
+    }
+}
+
+impl Schema for NonZeroU32 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::U32
+    }
+}
+
+impl Schema for u64 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::U64
+    }
+}
+
+impl Schema for NonZeroU64 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::U64
+    }
+}
+
+impl Schema for u128 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::U128
+    }
+}
+
+impl Schema for NonZeroU128 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::U128
+    }
+}
+
+impl Schema for usize {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::U64
+    }
+}
+
+impl Schema for NonZeroUsize {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::U64
+    }
+}
+
+impl Schema for i8 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::I8
+    }
+}
+
+impl Schema for NonZeroI8 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::I8
+    }
+}
+
+impl Schema for i16 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::I16
+    }
+}
+
+impl Schema for NonZeroI16 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::I16
+    }
+}
+
+impl Schema for i32 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::I32
+    }
+}
+
+impl Schema for NonZeroI32 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::I32
+    }
+}
+
+impl Schema for i64 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::I64
+    }
+}
+
+impl Schema for NonZeroI64 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::I64
+    }
+}
+
+impl Schema for i128 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::I128
+    }
+}
+
+impl Schema for NonZeroI128 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::I128
+    }
+}
+
+impl Schema for isize {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::I64
+    }
+}
+
+impl Schema for NonZeroIsize {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::I64
+    }
+}
+
+impl Schema for f32 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::F32
+    }
+}
+
+impl Schema for f64 {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::F64
+    }
+}
+
+impl Schema for char {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::Char
+    }
+}
+
+impl<T> Schema for [T]
+where
+    T: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        Type::Seq(Box::new(T::schema(definitions)))
+    }
+}
+
+impl Schema for str {
+    fn schema(_: &mut Definitions) -> Type {
+        Type::String
+    }
+}
+
+impl<T, const N: usize> Schema for [T; N]
+where
+    T: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        Type::Array {
+            item: Box::new(T::schema(definitions)),
+            len: N,
+        }
+    }
+}
+
+impl<T> Schema for Option<T>
+where
+    T: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        Type::Option(Box::new(T::schema(definitions)))
+    }
+}
+
+impl<T, U> Schema for Result<T, U>
+where
+    T: Schema,
+    U: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        definitions.define(core::any::type_name::<Self>(), |definitions| {
+            Definition::Enum(EnumSchema {
+                version: None,
+                tag_type: Type::U8,
+                variants: vec![
+                    Variant::new("Ok", 0, vec![Field::new("0", T::schema(definitions))]),
+                    Variant::new("Err", 1, vec![Field::new("0", U::schema(definitions))]),
+                ],
+            })
+        })
+    }
+}
+
+impl<T> Schema for Cell<T>
+where
+    T: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        T::schema(definitions)
+    }
+}
+
+impl<T> Schema for RefCell<T>
+where
+    T: Schema + ?Sized,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        T::schema(definitions)
+    }
+}
+
+impl Schema for Duration {
+    fn schema(definitions: &mut Definitions) -> Type {
+        definitions.define(core::any::type_name::<Self>(), |_| {
+            Definition::Struct(StructSchema {
+                version: None,
+                layout: StructLayout::Sequential,
+                fields: vec![
+                    Field::new("secs", Type::U64),
+                    Field::new("nanos", Type::U32),
+                ],
+            })
+        })
+    }
+}
+
+impl<T> Schema for Range<T>
+where
+    T: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        definitions.define(core::any::type_name::<Self>(), range_definition::<T>)
+    }
+}
+
+impl<T> Schema for RangeInclusive<T>
+where
+    T: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        definitions.define(core::any::type_name::<Self>(), range_definition::<T>)
+    }
+}
+
+fn range_definition<T: Schema>(definitions: &mut Definitions) -> Definition {
+    let ty = T::schema(definitions);
+    Definition::Struct(StructSchema {
+        version: None,
+        layout: StructLayout::Sequential,
+        fields: vec![Field::new("start", ty.clone()), Field::new("end", ty)],
+    })
+}
+
+impl<T> Schema for Bound<T>
+where
+    T: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        definitions.define(core::any::type_name::<Self>(), |definitions| {
+            let ty = T::schema(definitions);
+            Definition::Enum(EnumSchema {
+                version: None,
+                tag_type: Type::U32,
+                variants: vec![
+                    Variant::new("Unbounded", 0, Vec::new()),
+                    Variant::new("Included", 1, vec![Field::new("0", ty.clone())]),
+                    Variant::new("Excluded", 2, vec![Field::new("0", ty)]),
+                ],
+            })
+        })
+    }
+}
+
+impl<T> Schema for &T
+where
+    T: Schema + ?Sized,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        T::schema(definitions)
+    }
+}
+
+impl<A> Schema for (A,)
+where
+    A: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        Type::Tuple(vec![A::schema(definitions)])
+    }
+}
+
+impl<A, B> Schema for (A, B)
+where
+    A: Schema,
+    B: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        Type::Tuple(vec![A::schema(definitions), B::schema(definitions)])
+    }
+}
+
+impl<A, B, C> Schema for (A, B, C)
+where
+    A: Schema,
+    B: Schema,
+    C: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        Type::Tuple(vec![
+            A::schema(definitions),
+            B::schema(definitions),
+            C::schema(definitions),
+        ])
+    }
+}
+
+impl<A, B, C, D> Schema for (A, B, C, D)
+where
+    A: Schema,
+    B: Schema,
+    C: Schema,
+    D: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        Type::Tuple(vec![
+            A::schema(definitions),
+            B::schema(definitions),
+            C::schema(definitions),
+            D::schema(definitions),
+        ])
+    }
+}
+
+impl<A, B, C, D, E> Schema for (A, B, C, D, E)
+where
+    A: Schema,
+    B: Schema,
+    C: Schema,
+    D: Schema,
+    E: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        Type::Tuple(vec![
+            A::schema(definitions),
+            B::schema(definitions),
+            C::schema(definitions),
+            D::schema(definitions),
+            E::schema(definitions),
+        ])
+    }
+}
+
+impl<A, B, C, D, E, F> Schema for (A, B, C, D, E, F)
+where
+    A: Schema,
+    B: Schema,
+    C: Schema,
+    D: Schema,
+    E: Schema,
+    F: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        Type::Tuple(vec![
+            A::schema(definitions),
+            B::schema(definitions),
+            C::schema(definitions),
+            D::schema(definitions),
+            E::schema(definitions),
+            F::schema(definitions),
+        ])
+    }
+}
+
+impl<A, B, C, D, E, F, G> Schema for (A, B, C, D, E, F, G)
+where
+    A: Schema,
+    B: Schema,
+    C: Schema,
+    D: Schema,
+    E: Schema,
+    F: Schema,
+    G: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        Type::Tuple(vec![
+            A::schema(definitions),
+            B::schema(definitions),
+            C::schema(definitions),
+            D::schema(definitions),
+            E::schema(definitions),
+            F::schema(definitions),
+            G::schema(definitions),
+        ])
+    }
+}
+
+impl<A, B, C, D, E, F, G, H> Schema for (A, B, C, D, E, F, G, H)
+where
+    A: Schema,
+    B: Schema,
+    C: Schema,
+    D: Schema,
+    E: Schema,
+    F: Schema,
+    G: Schema,
+    H: Schema,
+{
+    fn schema(definitions: &mut Definitions) -> Type {
+        Type::Tuple(vec![
+            A::schema(definitions),
+            B::schema(definitions),
+            C::schema(definitions),
+            D::schema(definitions),
+            E::schema(definitions),
+            F::schema(definitions),
+            G::schema(definitions),
+            H::schema(definitions),
+        ])
+    }
+}
@@ -0,0 +1,332 @@
+//! Describe the encoding of a type at runtime.
+//!
+//! The [Schema] trait describes the bytes that a type produces: its fields, enum variants and their tags, and the integer types that are used. This can be shared with other tools and languages that need to read or write the same data.
+//!
+//! [schema_of] combines the schema of a type with the configuration options that change how it is encoded:
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # {
+//! use bincode::schema::{Definition, Field, IntEncoding, StructLayout, Type};
+//!
+//! #[derive(bincode::Encode, bincode::Schema)]
+//! struct Packet {
+//!     id: u32,
+//!     payload: Vec<u8>,
+//! }
+//!
+//! let schema = bincode::schema_of::<Packet, _>(bincode::config::standard());
+//! assert_eq!(schema.int_encoding, IntEncoding::Variable);
+//!
+//! let name = match &schema.root {
+//!     Type::Named(name) => name,
+//!     _ => unreachable!(),
+//! };
+//! let definition = match schema.definitions.get(name) {
+//!     Some(Definition::Struct(definition)) => definition,
+//!     _ => unreachable!(),
+//! };
+//! assert_eq!(definition.layout, StructLayout::Sequential);
+//! assert_eq!(
+//!     definition.fields,
+//!     vec![
+//!         Field::new("id", Type::U32),
+//!         Field::new("payload", Type::Seq(Box::new(Type::U8))),
+//!     ]
+//! );
+//! # }
+//! ```
+//!
+//! Every type in this module implements [Encode] and [Decode], so a schema can be stored and shared like any other value.
+//!
+//! [Encode]: ../enc/trait.Encode.html
+//! [Decode]: ../de/trait.Decode.html
+
+mod encoding;
+mod impls;
+
+pub use crate::config::{Endian, IntEncoding};
+
+use crate::config::Config;
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
+
+/// Types that can describe their encoding as a [Type].
+///
+/// This trait is implemented for every type that bincode can encode, and will be automatically implemented if you enable the `derive` feature and add `#[derive(bincode::Schema)]` to your type.
+///
+/// Structs and enums are not described inline. They are added to the [Definitions] with [Definitions::define], and referred to by name with [Type::Named]. This allows recursive types like `struct Tree { children: Vec<Tree> }`:
+///
+/// ```
+/// use bincode::schema::{Definition, Definitions, Field, Schema, StructLayout, StructSchema, Type};
+///
+/// struct Tree {
+///     value: u32,
+///     children: Vec<Tree>,
+/// }
+///
+/// impl Schema for Tree {
+///     fn schema(definitions: &mut Definitions) -> Type {
+///         definitions.define(core::any::type_name::<Self>(), |definitions| {
+///             Definition::Struct(StructSchema {
+///                 version: None,
+///                 layout: StructLayout::Sequential,
+///                 fields: vec![
+///                     Field::new("value", u32::schema(definitions)),
+///                     Field::new("children", <Vec<Tree>>::schema(definitions)),
+///                 ],
+///             })
+///         })
+///     }
+/// }
+///
+/// let schema = bincode::schema_of::<Tree, _>(bincode::config::standard());
+/// let name = core::any::type_name::<Tree>();
+/// assert_eq!(schema.root, Type::Named(name.to_string()));
+/// assert!(schema.definitions.get(name).is_some());
+/// ```
+pub trait Schema {
+    /// Describe the encoding of this type, adding the structs and enums it contains to `definitions`.
+    fn schema(definitions: &mut Definitions) -> Type;
+}
+
+/// Describe the encoding of `T` with the given configuration.
+///
+/// See the [schema module](crate::schema) for more information.
+pub fn schema_of<T: Schema + ?Sized, C: Config>(config: C) -> TypeSchema {
+    let mut definitions = Definitions::new();
+    let root = T::schema(&mut definitions);
+    TypeSchema {
+        endian: config.endian(),
+        int_encoding: config.int_encoding(),
+        skip_fixed_array_length: config.skip_fixed_array_length(),
+        root,
+        definitions,
+    }
+}
+
+/// The encoding of a type with a specific configuration. This is created by [schema_of].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeSchema {
+    /// The byte order of integers and floats
+    pub endian: Endian,
+    /// The encoding of integers, including the lengths of collections and the tags of enums
+    pub int_encoding: IntEncoding,
+    /// If `true`, the length of a [Type::Array] is not encoded
+    pub skip_fixed_array_length: bool,
+    /// The type that was described
+    pub root: Type,
+    /// The structs and enums that are referred to by [Type::Named]
+    pub definitions: Definitions,
+}
+
+/// The encoding of a type.
+///
+/// Types that are encoded in the same way are described by the same variant. For example `usize` is always encoded as a `u64`, and `Vec<T>`, `VecDeque<T>` and `HashSet<T>` are all a [Type::Seq].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Type {
+    /// A type that is not encoded at all, like `()` or `PhantomData<T>`
+    Unit,
+    /// A `bool`, encoded as a single byte
+    Bool,
+    /// An `u8`
+    U8,
+    /// An `u16`
+    U16,
+    /// An `u32`
+    U32,
+    /// An `u64`, also used for `usize`
+    U64,
+    /// An `u128`
+    U128,
+    /// An `i8`
+    I8,
+    /// An `i16`
+    I16,
+    /// An `i32`
+    I32,
+    /// An `i64`, also used for `isize`
+    I64,
+    /// An `i128`
+    I128,
+    /// An `f32`
+    F32,
+    /// An `f64`
+    F64,
+    /// A `char`, encoded as 1 to 4 bytes of UTF-8
+    Char,
+    /// A string, encoded as its length followed by its UTF-8 bytes
+    String,
+    /// An `Option<T>`: a single byte, followed by the value if this byte is 1
+    Option(Box<Type>),
+    /// A sequence of values, encoded as the length followed by every value
+    Seq(Box<Type>),
+    /// A map, encoded as the length followed by every key and value
+    Map {
+        /// The type of the keys
+        key: Box<Type>,
+        /// The type of the values
+        value: Box<Type>,
+    },
+    /// An array with a fixed length. The length is encoded as an `u64` before the items, unless [TypeSchema::skip_fixed_array_length] is set.
+    Array {
+        /// The type of the items
+        item: Box<Type>,
+        /// The amount of items
+        len: usize,
+    },
+    /// A tuple, encoded as every value in order
+    Tuple(Vec<Type>),
+    /// A struct or enum in [Definitions], referred to by its name
+    Named(String),
+    /// A type that is encoded in a way that can not be described, e.g. by a custom function or by serde. Contains the name of the Rust type.
+    Opaque(String),
+}
+
+/// A struct or enum, see [Definitions].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Definition {
+    /// A struct
+    Struct(StructSchema),
+    /// An enum
+    Enum(EnumSchema),
+}
+
+/// The encoding of a struct.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructSchema {
+    /// The version that is encoded as an `u32` before the struct, set by `#[bincode(version = N)]`
+    pub version: Option<u32>,
+    /// How the fields are encoded
+    pub layout: StructLayout,
+    /// The fields that are encoded, in order. Tuple structs use the index of a field as its name.
+    pub fields: Vec<Field>,
+}
+
+/// How the fields of a struct are encoded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StructLayout {
+    /// Every field in order
+    Sequential,
+    /// Every field in order, prefixed with the length of the fields in bytes as an `u64`. Set by `#[bincode(default_trailing)]`.
+    DefaultTrailing,
+    /// The length of the fields in bytes as an `u64`, followed by the tag, length and value of every field. Set by `#[bincode(tagged)]`.
+    Tagged,
+}
+
+/// A field of a struct or enum variant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    /// The name of the field
+    pub name: String,
+    /// The tag of the field in a struct with [StructLayout::Tagged]
+    pub tag: Option<u32>,
+    /// The type of the field
+    pub ty: Type,
+}
+
+impl Field {
+    /// A field without a tag.
+    pub fn new(name: &str, ty: Type) -> Self {
+        Self {
+            name: name.to_owned(),
+            tag: None,
+            ty,
+        }
+    }
+}
+
+/// The encoding of an enum.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnumSchema {
+    /// The version that is encoded as an `u32` before the enum, set by `#[bincode(version = N)]`
+    pub version: Option<u32>,
+    /// The integer type of the variant index, one of [Type::U8], [Type::U16], [Type::U32] or [Type::U64]
+    pub tag_type: Type,
+    /// The variants of the enum
+    pub variants: Vec<Variant>,
+}
+
+/// A variant of an enum.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Variant {
+    /// The name of the variant
+    pub name: String,
+    /// The index that is encoded before the fields of this variant
+    pub index: u64,
+    /// The fields of this variant. Tuple variants use the index of a field as its name.
+    pub fields: Vec<Field>,
+}
+
+impl Variant {
+    /// A variant with the given fields.
+    pub fn new(name: &str, index: u64, fields: Vec<Field>) -> Self {
+        Self {
+            name: name.to_owned(),
+            index,
+            fields,
+        }
+    }
+}
+
+/// The structs and enums of a [TypeSchema], by name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Definitions {
+    definitions: BTreeMap<String, Definition>,
+    /// The names of the definitions that are being created by [Definitions::define]
+    pending: BTreeSet<String>,
+}
+
+impl Definitions {
+    /// Create an empty set of definitions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the definition that is created by `definition` as `name`, and return a [Type::Named] that refers to it.
+    ///
+    /// `definition` is only called if there is no definition with this name yet. The name is usually `core::any::type_name::<Self>()`.
+    pub fn define<F>(&mut self, name: &str, definition: F) -> Type
+    where
+        F: FnOnce(&mut Definitions) -> Definition,
+    {
+        if !self.definitions.contains_key(name) && self.pending.insert(name.to_owned()) {
+            let definition = definition(self);
+            self.pending.remove(name);
+            self.definitions.insert(name.to_owned(), definition);
+        }
+        Type::Named(name.to_owned())
+    }
+
+    /// Add `definition` as `name`, replacing any existing definition with this name.
+    pub fn insert(&mut self, name: &str, definition: Definition) {
+        self.definitions.insert(name.to_owned(), definition);
+    }
+
+    /// Get the definition with the given name.
+    pub fn get(&self, name: &str) -> Option<&Definition> {
+        self.definitions.get(name)
+    }
+
+    /// Iterate over all definitions, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Definition)> {
+        self.definitions
+            .iter()
+            .map(|(name, definition)| (name.as_str(), definition))
+    }
+
+    /// The amount of definitions.
+    pub fn len(&self) -> usize {
+        self.definitions.len()
+    }
+
+    /// Returns `true` if there are no definitions.
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+}
@@ -1,3 +1,44 @@
 pub trait Sealed {}
 
-impl<'a, T> Sealed for &'a mut T where T: Sealed {}
+impl<T> Sealed for &mut T where T: Sealed {}
+
+/// Returns `true` if `T` and `U` are the same type, ignoring lifetimes.
+///
+/// Unlike `TypeId::of`, `T` does not need to be `'static`. This is used to take a fast path for `u8` containers (e.g. `Vec<u8>`) without adding a `'static` bound to the generic `Vec<T>` implementations.
+///
+/// Because lifetimes are erased, this must only be used to compare against types that have no lifetimes, like `u8`.
+#[cfg(feature = "alloc")]
+pub(crate) fn type_equal<T: ?Sized, U: ?Sized + 'static>() -> bool {
+    use core::{any::TypeId, marker::PhantomData};
+
+    trait NonStaticAny {
+        fn get_type_id(&self) -> TypeId
+        where
+            Self: 'static;
+    }
+
+    impl<T: ?Sized> NonStaticAny for PhantomData<T> {
+        fn get_type_id(&self) -> TypeId
+        where
+            Self: 'static,
+        {
+            TypeId::of::<T>()
+        }
+    }
+
+    let phantom = PhantomData::<T>;
+    let phantom: &dyn NonStaticAny = &phantom;
+    // Safety: `get_type_id` does not use any data with the lifetime that is being extended here,
+    // it only looks at the type of `T`. The `TypeId` of a type does not depend on its lifetimes.
+    let phantom: &(dyn NonStaticAny + 'static) = unsafe { core::mem::transmute(phantom) };
+    phantom.get_type_id() == TypeId::of::<U>()
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_type_equal() {
+    assert!(type_equal::<u8, u8>());
+    assert!(!type_equal::<i8, u8>());
+    assert!(!type_equal::<&str, u8>());
+    assert!(type_equal::<&str, &'static str>());
+}
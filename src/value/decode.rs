@@ -0,0 +1,377 @@
+use super::{is_u8, Span, Value, TRAILING_BYTES, UNKNOWN_TAG};
+use crate::{
+    config::InternalConfig,
+    de::{decode_option_variant, decode_slice_len, read::Reader, Decode, Decoder, Record},
+    error::DecodeError,
+    schema::{Definition, EnumSchema, Field, StructLayout, StructSchema, Type, TypeSchema},
+};
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::Display;
+
+/// Decodes values of the types in `schema`, and optionally records the [Span] of every value.
+pub(super) struct ValueDecoder<'a> {
+    schema: &'a TypeSchema,
+    spans: Option<SpanRecorder>,
+}
+
+struct SpanRecorder {
+    spans: Vec<Span>,
+    /// The path of the value that is being decoded
+    path: String,
+}
+
+impl<'a> ValueDecoder<'a> {
+    pub(super) fn new(schema: &'a TypeSchema, record_spans: bool) -> Self {
+        Self {
+            schema,
+            spans: record_spans.then(|| SpanRecorder {
+                spans: Vec::new(),
+                path: String::new(),
+            }),
+        }
+    }
+
+    /// The recorded spans, in the order in which the values were started
+    pub(super) fn into_spans(self) -> Vec<Span> {
+        self.spans.map(|spans| spans.spans).unwrap_or_default()
+    }
+
+    pub(super) fn decode_type<D: Decoder>(
+        &mut self,
+        decoder: &mut D,
+        ty: &Type,
+    ) -> Result<Value, DecodeError> {
+        let start = decoder.offset();
+        let index = self.spans.as_mut().map(|spans| {
+            spans.spans.push(Span {
+                path: spans.path.clone(),
+                range: start..start,
+            });
+            spans.spans.len() - 1
+        });
+        let value = self.decode_type_inner(decoder, ty)?;
+        if let (Some(spans), Some(index)) = (self.spans.as_mut(), index) {
+            spans.spans[index].range.end = decoder.offset();
+        }
+        Ok(value)
+    }
+
+    /// Decode a value that is located at the current path followed by `segment`, e.g. `.field` or `[0]`
+    fn decode_at<D: Decoder>(
+        &mut self,
+        decoder: &mut D,
+        segment: impl Display,
+        ty: &Type,
+    ) -> Result<Value, DecodeError> {
+        let len = match self.spans.as_mut() {
+            Some(spans) => {
+                let len = spans.path.len();
+                spans.path.push_str(&segment.to_string());
+                Some(len)
+            }
+            None => None,
+        };
+        let value = self.decode_type(decoder, ty);
+        if let (Some(spans), Some(len)) = (self.spans.as_mut(), len) {
+            spans.path.truncate(len);
+        }
+        value
+    }
+
+    fn decode_type_inner<D: Decoder>(
+        &mut self,
+        decoder: &mut D,
+        ty: &Type,
+    ) -> Result<Value, DecodeError> {
+        Ok(match ty {
+            Type::Unit => Value::Unit,
+            Type::Bool => Value::Bool(bool::decode(decoder)?),
+            Type::U8 => Value::U8(u8::decode(decoder)?),
+            Type::U16 => Value::U16(u16::decode(decoder)?),
+            Type::U32 => Value::U32(u32::decode(decoder)?),
+            Type::U64 => Value::U64(u64::decode(decoder)?),
+            Type::U128 => Value::U128(u128::decode(decoder)?),
+            Type::I8 => Value::I8(i8::decode(decoder)?),
+            Type::I16 => Value::I16(i16::decode(decoder)?),
+            Type::I32 => Value::I32(i32::decode(decoder)?),
+            Type::I64 => Value::I64(i64::decode(decoder)?),
+            Type::I128 => Value::I128(i128::decode(decoder)?),
+            Type::F32 => Value::F32(f32::decode(decoder)?),
+            Type::F64 => Value::F64(f64::decode(decoder)?),
+            Type::Char => Value::Char(char::decode(decoder)?),
+            Type::String => Value::String(String::decode(decoder)?),
+            Type::Option(ty) => Value::Option(
+                match decode_option_variant(decoder, core::any::type_name::<Option<Value>>())? {
+                    Some(()) => Some(Box::new(self.decode_type(decoder, ty)?)),
+                    None => None,
+                },
+            ),
+            Type::Seq(item) if is_u8(item) => Value::Bytes(Vec::decode(decoder)?),
+            Type::Seq(item) => {
+                let len = decode_slice_len(decoder)?;
+                Value::Seq(self.decode_items(decoder, item, len)?)
+            }
+            Type::Array { item, len } => {
+                if !decoder.config().skip_fixed_array_length() {
+                    let found = u64::decode(decoder)? as usize;
+                    if found != *len {
+                        return Err(DecodeError::ArrayLengthMismatch {
+                            required: *len,
+                            found,
+                        });
+                    }
+                }
+                if is_u8(item) {
+                    decoder.claim_bytes_read(*len)?;
+                    let mut bytes = vec![0u8; *len];
+                    decoder.reader().read(&mut bytes)?;
+                    Value::Bytes(bytes)
+                } else {
+                    Value::Seq(self.decode_items(decoder, item, *len)?)
+                }
+            }
+            Type::Tuple(types) => Value::Seq(
+                types
+                    .iter()
+                    .enumerate()
+                    .map(|(index, ty)| self.decode_at(decoder, format_args!(".{}", index), ty))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Type::Map { key, value } => {
+                let len = decode_slice_len(decoder)?;
+                decoder.claim_container_read::<(Value, Value)>(len)?;
+
+                let mut entries = Vec::with_capacity(len);
+                for index in 0..len {
+                    // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+                    decoder.unclaim_bytes_read(core::mem::size_of::<(Value, Value)>());
+
+                    let key = self.decode_at(decoder, format_args!("[{}].key", index), key)?;
+                    let value =
+                        self.decode_at(decoder, format_args!("[{}].value", index), value)?;
+                    entries.push((key, value));
+                }
+                Value::Map(entries)
+            }
+            Type::Named(name) => {
+                decoder.enter_depth()?;
+                let value = match self.schema.definitions.get(name) {
+                    Some(Definition::Struct(definition)) => {
+                        self.decode_struct(decoder, name, definition)?
+                    }
+                    Some(Definition::Enum(definition)) => {
+                        self.decode_enum(decoder, name, definition)?
+                    }
+                    None => {
+                        return Err(DecodeError::OtherString(format!(
+                            "The schema has no definition of `{}`",
+                            name
+                        )))
+                    }
+                };
+                decoder.leave_depth();
+                value
+            }
+            Type::Opaque(name) => {
+                return Err(DecodeError::OtherString(format!(
+                    "Can not decode `{}`, because its encoding is not described by the schema",
+                    name
+                )))
+            }
+        })
+    }
+
+    fn decode_items<D: Decoder>(
+        &mut self,
+        decoder: &mut D,
+        item: &Type,
+        len: usize,
+    ) -> Result<Vec<Value>, DecodeError> {
+        decoder.claim_container_read::<Value>(len)?;
+
+        let mut items = Vec::with_capacity(len);
+        for index in 0..len {
+            // See the documentation on `unclaim_bytes_read` as to why we're doing this here
+            decoder.unclaim_bytes_read(core::mem::size_of::<Value>());
+
+            items.push(self.decode_at(decoder, format_args!("[{}]", index), item)?);
+        }
+        Ok(items)
+    }
+
+    fn decode_struct<D: Decoder>(
+        &mut self,
+        decoder: &mut D,
+        name: &str,
+        definition: &StructSchema,
+    ) -> Result<Value, DecodeError> {
+        decode_version(decoder, name, definition.version)?;
+        let fields = match definition.layout {
+            StructLayout::Sequential => self.decode_fields(decoder, "", &definition.fields)?,
+            StructLayout::DefaultTrailing => {
+                let record = Record::begin(decoder)?;
+                let mut fields = Vec::new();
+                for field in &definition.fields {
+                    if record.is_finished(decoder) {
+                        break;
+                    }
+                    let value =
+                        self.decode_at(decoder, format_args!(".{}", field.name), &field.ty)?;
+                    fields.push((field.name.clone(), value));
+                }
+                let rest = self.record_raw(decoder, TRAILING_BYTES, false, |decoder| {
+                    record.finish_into_vec(decoder)
+                })?;
+                if !rest.is_empty() {
+                    fields.push((TRAILING_BYTES.to_string(), Value::Raw(rest)));
+                }
+                fields
+            }
+            StructLayout::Tagged => {
+                let record = Record::begin(decoder)?;
+                let mut fields = Vec::new();
+                while !record.is_finished(decoder) {
+                    let tag = u32::decode(decoder)?;
+                    let entry = Record::begin(decoder)?;
+                    let field = definition
+                        .fields
+                        .iter()
+                        .find(|field| field.tag == Some(tag));
+                    match field {
+                        Some(field) if !matches!(field.ty, Type::Opaque(_)) => {
+                            let value = self.decode_at(
+                                decoder,
+                                format_args!(".{}", field.name),
+                                &field.ty,
+                            )?;
+                            if !entry.finish_into_vec(decoder)?.is_empty() {
+                                return Err(DecodeError::OtherString(format!(
+                                    "The entry of `{}.{}` is longer than its value",
+                                    name, field.name
+                                )));
+                            }
+                            fields.push((field.name.clone(), value));
+                        }
+                        Some(field) => {
+                            let bytes = self.record_raw(decoder, &field.name, true, |decoder| {
+                                entry.finish_into_vec(decoder)
+                            })?;
+                            fields.push((field.name.clone(), Value::Raw(bytes)));
+                        }
+                        None => {
+                            let field_name = format!("{}{}", UNKNOWN_TAG, tag);
+                            let bytes = self.record_raw(decoder, &field_name, true, |decoder| {
+                                entry.finish_into_vec(decoder)
+                            })?;
+                            fields.push((field_name, Value::Raw(bytes)));
+                        }
+                    }
+                }
+                record.finish(decoder)?;
+                fields
+            }
+        };
+        Ok(Value::Struct(fields))
+    }
+
+    fn decode_enum<D: Decoder>(
+        &mut self,
+        decoder: &mut D,
+        name: &str,
+        definition: &EnumSchema,
+    ) -> Result<Value, DecodeError> {
+        decode_version(decoder, name, definition.version)?;
+        let index = match definition.tag_type {
+            Type::U8 => u64::from(u8::decode(decoder)?),
+            Type::U16 => u64::from(u16::decode(decoder)?),
+            Type::U32 => u64::from(u32::decode(decoder)?),
+            Type::U64 => u64::decode(decoder)?,
+            ref ty => {
+                return Err(DecodeError::OtherString(format!(
+                    "`{}` has an invalid tag type {:?}",
+                    name, ty
+                )))
+            }
+        };
+        let variant = definition
+            .variants
+            .iter()
+            .find(|variant| variant.index == index)
+            .ok_or_else(|| {
+                DecodeError::OtherString(format!("`{}` has no variant with index {}", name, index))
+            })?;
+        let prefix = format!("::{}", variant.name);
+        Ok(Value::Enum {
+            variant: variant.name.clone(),
+            fields: self.decode_fields(decoder, &prefix, &variant.fields)?,
+        })
+    }
+
+    fn decode_fields<D: Decoder>(
+        &mut self,
+        decoder: &mut D,
+        prefix: &str,
+        fields: &[Field],
+    ) -> Result<Vec<(String, Value)>, DecodeError> {
+        fields
+            .iter()
+            .map(|field| {
+                let value = self.decode_at(
+                    decoder,
+                    format_args!("{}.{}", prefix, field.name),
+                    &field.ty,
+                )?;
+                Ok((field.name.clone(), value))
+            })
+            .collect()
+    }
+
+    /// Read bytes that are not described by the schema with `read`, and record them as the field `field_name`. If `keep_empty` is not set, no span is recorded if there are no bytes.
+    fn record_raw<D, F>(
+        &mut self,
+        decoder: &mut D,
+        field_name: &str,
+        keep_empty: bool,
+        read: F,
+    ) -> Result<Vec<u8>, DecodeError>
+    where
+        D: Decoder,
+        F: FnOnce(&mut D) -> Result<Vec<u8>, DecodeError>,
+    {
+        let start = decoder.offset();
+        let bytes = read(decoder)?;
+        if let Some(spans) = self.spans.as_mut() {
+            if keep_empty || !bytes.is_empty() {
+                spans.spans.push(Span {
+                    path: format!("{}.{}", spans.path, field_name),
+                    range: start..decoder.offset(),
+                });
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+fn decode_version<D: Decoder>(
+    decoder: &mut D,
+    name: &str,
+    version: Option<u32>,
+) -> Result<(), DecodeError> {
+    if let Some(current) = version {
+        let found = u32::decode(decoder)?;
+        if found != current {
+            // Older versions are migrated by the type, which is not described by the schema
+            return Err(DecodeError::OtherString(format!(
+                "Can not decode version {} of `{}`, the schema describes version {}",
+                found, name, current
+            )));
+        }
+    }
+    Ok(())
+}
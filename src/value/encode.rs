@@ -0,0 +1,290 @@
+use super::{is_u8, Value, TRAILING_BYTES, UNKNOWN_TAG};
+use crate::{
+    config::InternalConfig,
+    enc::{encode_slice_len, write::SizeWriter, write::Writer, Encode, Encoder, EncoderImpl},
+    error::EncodeError,
+    schema::{Definition, EnumSchema, Field, StructLayout, StructSchema, Type, TypeSchema},
+};
+use alloc::{format, string::String};
+
+pub(super) fn encode_type<E: Encoder>(
+    encoder: &mut E,
+    schema: &TypeSchema,
+    ty: &Type,
+    value: &Value,
+) -> Result<(), EncodeError> {
+    match (ty, value) {
+        (_, Value::Raw(bytes)) => encoder.writer().write(bytes),
+        (Type::Unit, Value::Unit) => Ok(()),
+        (Type::Bool, Value::Bool(value)) => value.encode(encoder),
+        (Type::U8, Value::U8(value)) => value.encode(encoder),
+        (Type::U16, Value::U16(value)) => value.encode(encoder),
+        (Type::U32, Value::U32(value)) => value.encode(encoder),
+        (Type::U64, Value::U64(value)) => value.encode(encoder),
+        (Type::U128, Value::U128(value)) => value.encode(encoder),
+        (Type::I8, Value::I8(value)) => value.encode(encoder),
+        (Type::I16, Value::I16(value)) => value.encode(encoder),
+        (Type::I32, Value::I32(value)) => value.encode(encoder),
+        (Type::I64, Value::I64(value)) => value.encode(encoder),
+        (Type::I128, Value::I128(value)) => value.encode(encoder),
+        (Type::F32, Value::F32(value)) => value.encode(encoder),
+        (Type::F64, Value::F64(value)) => value.encode(encoder),
+        (Type::Char, Value::Char(value)) => value.encode(encoder),
+        (Type::String, Value::String(value)) => value.encode(encoder),
+        (Type::Option(ty), Value::Option(value)) => match value {
+            Some(value) => {
+                1u8.encode(encoder)?;
+                encode_type(encoder, schema, ty, value)
+            }
+            None => 0u8.encode(encoder),
+        },
+        (Type::Seq(item), Value::Bytes(bytes)) if is_u8(item) => bytes.as_slice().encode(encoder),
+        (Type::Seq(item), Value::Seq(items)) => {
+            encode_slice_len(encoder, items.len())?;
+            for value in items {
+                encode_type(encoder, schema, item, value)?;
+            }
+            Ok(())
+        }
+        (Type::Array { item, len }, Value::Bytes(bytes)) if is_u8(item) && bytes.len() == *len => {
+            if !encoder.config().skip_fixed_array_length() {
+                encode_slice_len(encoder, *len)?;
+            }
+            encoder.writer().write(bytes)
+        }
+        (Type::Array { item, len }, Value::Seq(items)) if items.len() == *len => {
+            if !encoder.config().skip_fixed_array_length() {
+                encode_slice_len(encoder, *len)?;
+            }
+            for value in items {
+                encode_type(encoder, schema, item, value)?;
+            }
+            Ok(())
+        }
+        (Type::Tuple(types), Value::Seq(items)) if items.len() == types.len() => {
+            for (ty, value) in types.iter().zip(items) {
+                encode_type(encoder, schema, ty, value)?;
+            }
+            Ok(())
+        }
+        (Type::Map { key, value }, Value::Map(entries)) => {
+            encode_slice_len(encoder, entries.len())?;
+            for (key_value, value_value) in entries {
+                encode_type(encoder, schema, key, key_value)?;
+                encode_type(encoder, schema, value, value_value)?;
+            }
+            Ok(())
+        }
+        (Type::Named(name), value) => match schema.definitions.get(name) {
+            Some(Definition::Struct(definition)) => {
+                encode_struct(encoder, schema, name, definition, value)
+            }
+            Some(Definition::Enum(definition)) => {
+                encode_enum(encoder, schema, name, definition, value)
+            }
+            None => Err(EncodeError::OtherString(format!(
+                "The schema has no definition of `{}`",
+                name
+            ))),
+        },
+        (ty, value) => Err(EncodeError::OtherString(format!(
+            "Can not encode {:?} as {:?}",
+            value, ty
+        ))),
+    }
+}
+
+fn encode_struct<E: Encoder>(
+    encoder: &mut E,
+    schema: &TypeSchema,
+    name: &str,
+    definition: &StructSchema,
+    value: &Value,
+) -> Result<(), EncodeError> {
+    let values = match value {
+        Value::Struct(values) => values,
+        value => {
+            return Err(EncodeError::OtherString(format!(
+                "Can not encode {:?} as the struct `{}`",
+                value, name
+            )))
+        }
+    };
+    if let Some(version) = definition.version {
+        version.encode(encoder)?;
+    }
+    match definition.layout {
+        StructLayout::Sequential => {
+            encode_fields(encoder, schema, name, &definition.fields, values, false)
+        }
+        StructLayout::DefaultTrailing => {
+            let len = encoded_size(encoder, |encoder| {
+                encode_fields(encoder, schema, name, &definition.fields, values, true)
+            })?;
+            (len as u64).encode(encoder)?;
+            if encoder.writer().count_only(len) {
+                return Ok(());
+            }
+            encode_fields(encoder, schema, name, &definition.fields, values, true)
+        }
+        StructLayout::Tagged => {
+            let len = encoded_size(encoder, |encoder| {
+                encode_entries(encoder, schema, name, &definition.fields, values)
+            })?;
+            (len as u64).encode(encoder)?;
+            if encoder.writer().count_only(len) {
+                return Ok(());
+            }
+            encode_entries(encoder, schema, name, &definition.fields, values)
+        }
+    }
+}
+
+fn encode_enum<E: Encoder>(
+    encoder: &mut E,
+    schema: &TypeSchema,
+    name: &str,
+    definition: &EnumSchema,
+    value: &Value,
+) -> Result<(), EncodeError> {
+    let (variant, values) = match value {
+        Value::Enum { variant, fields } => (variant, fields),
+        value => {
+            return Err(EncodeError::OtherString(format!(
+                "Can not encode {:?} as the enum `{}`",
+                value, name
+            )))
+        }
+    };
+    let variant = definition
+        .variants
+        .iter()
+        .find(|candidate| candidate.name == *variant)
+        .ok_or_else(|| {
+            EncodeError::OtherString(format!("`{}` has no variant `{}`", name, variant))
+        })?;
+    if let Some(version) = definition.version {
+        version.encode(encoder)?;
+    }
+    let index = variant.index;
+    match definition.tag_type {
+        Type::U8 => u8::try_from(index).ok().map(|index| index.encode(encoder)),
+        Type::U16 => u16::try_from(index).ok().map(|index| index.encode(encoder)),
+        Type::U32 => u32::try_from(index).ok().map(|index| index.encode(encoder)),
+        Type::U64 => Some(index.encode(encoder)),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        EncodeError::OtherString(format!(
+            "The index {} of `{}::{}` does not fit in {:?}",
+            index, name, variant.name, definition.tag_type
+        ))
+    })??;
+    let name = format!("{}::{}", name, variant.name);
+    encode_fields(encoder, schema, &name, &variant.fields, values, false)
+}
+
+/// Encode the fields of a struct or enum variant in order. If `trailing` is set, the values can end before the fields, and can be followed by the bytes at the end of a record.
+fn encode_fields<E: Encoder>(
+    encoder: &mut E,
+    schema: &TypeSchema,
+    name: &str,
+    fields: &[Field],
+    values: &[(String, Value)],
+    trailing: bool,
+) -> Result<(), EncodeError> {
+    for (index, (field_name, value)) in values.iter().enumerate() {
+        match fields.get(index) {
+            Some(field) if field.name == *field_name => {
+                encode_type(encoder, schema, &field.ty, value)?
+            }
+            _ if trailing && field_name == TRAILING_BYTES && index + 1 == values.len() => {
+                encode_raw(encoder, name, field_name, value)?
+            }
+            _ => {
+                return Err(EncodeError::OtherString(format!(
+                    "`{}` has no field `{}` at position {}",
+                    name, field_name, index
+                )))
+            }
+        }
+    }
+    if !trailing && values.len() < fields.len() {
+        return Err(EncodeError::OtherString(format!(
+            "The field `{}` of `{}` is missing",
+            fields[values.len()].name,
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Encode the entries of a struct with [StructLayout::Tagged].
+fn encode_entries<E: Encoder>(
+    encoder: &mut E,
+    schema: &TypeSchema,
+    name: &str,
+    fields: &[Field],
+    values: &[(String, Value)],
+) -> Result<(), EncodeError> {
+    for (field_name, value) in values {
+        let field = fields.iter().find(|field| field.name == *field_name);
+        let tag = match field {
+            Some(field) => field.tag,
+            None => field_name
+                .strip_prefix(UNKNOWN_TAG)
+                .and_then(|tag| tag.parse::<u32>().ok()),
+        }
+        .ok_or_else(|| {
+            EncodeError::OtherString(format!("`{}` has no tagged field `{}`", name, field_name))
+        })?;
+        tag.encode(encoder)?;
+        match field {
+            Some(field) => {
+                let len = encoded_size(encoder, |encoder| {
+                    encode_type(encoder, schema, &field.ty, value)
+                })?;
+                (len as u64).encode(encoder)?;
+                if !encoder.writer().count_only(len) {
+                    encode_type(encoder, schema, &field.ty, value)?;
+                }
+            }
+            None => {
+                let len = encoded_size(encoder, |encoder| {
+                    encode_raw(encoder, name, field_name, value)
+                })?;
+                (len as u64).encode(encoder)?;
+                encode_raw(encoder, name, field_name, value)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn encode_raw<E: Encoder>(
+    encoder: &mut E,
+    name: &str,
+    field_name: &str,
+    value: &Value,
+) -> Result<(), EncodeError> {
+    match value {
+        Value::Raw(bytes) => encoder.writer().write(bytes),
+        value => Err(EncodeError::OtherString(format!(
+            "`{}.{}` is not described by the schema and can only be encoded from raw bytes, found {:?}",
+            name, field_name, value
+        ))),
+    }
+}
+
+/// The amount of bytes that `encode` writes with the config of `encoder`
+///
+/// After writing this length, check [Writer::count_only] before encoding the value itself. If `encoder` is only computing a size as well, this avoids encoding nested records an exponential amount of times.
+fn encoded_size<E, F>(encoder: &E, encode: F) -> Result<usize, EncodeError>
+where
+    E: Encoder,
+    F: FnOnce(&mut EncoderImpl<SizeWriter, E::C>) -> Result<(), EncodeError>,
+{
+    let mut size_encoder = EncoderImpl::<_, E::C>::new(SizeWriter::default(), *encoder.config());
+    encode(&mut size_encoder)?;
+    Ok(size_encoder.into_writer().bytes_written)
+}
@@ -0,0 +1,211 @@
+//! Decode and encode data without the Rust types that it was encoded with.
+//!
+//! A [Value] is a tree of integers, strings, sequences, structs and enums. [decode_from_slice] reads a [Value] from encoded bytes, using a [TypeSchema] to know which type comes next. [encode_to_vec] writes it back, producing the same bytes that were decoded:
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # {
+//! use bincode::value::Value;
+//!
+//! #[derive(bincode::Encode, bincode::Schema)]
+//! struct Packet {
+//!     id: u32,
+//!     payload: Vec<u8>,
+//! }
+//!
+//! let config = bincode::config::standard();
+//! let schema = bincode::schema_of::<Packet, _>(config);
+//! let bytes = bincode::encode_to_vec(
+//!     Packet {
+//!         id: 5,
+//!         payload: vec![1, 2, 3],
+//!     },
+//!     config,
+//! )
+//! .unwrap();
+//!
+//! let (value, len) = bincode::value::decode_from_slice(&bytes, &schema, config).unwrap();
+//! assert_eq!(len, bytes.len());
+//! assert_eq!(
+//!     value,
+//!     Value::Struct(vec![
+//!         ("id".to_string(), Value::U32(5)),
+//!         ("payload".to_string(), Value::Bytes(vec![1, 2, 3])),
+//!     ])
+//! );
+//!
+//! assert_eq!(bincode::value::encode_to_vec(&value, &schema, config).unwrap(), bytes);
+//! # }
+//! ```
+//!
+//! The integer encoding, endianness and array lengths of the config are used. The configuration options of the [TypeSchema] are ignored, so the same schema can be used to read data that was encoded with a different config.
+//!
+//! Types that are described as [Type::Opaque] can not be decoded, because their encoding is unknown. The only exception is a field of a struct with `#[bincode(tagged)]`, which is decoded as [Value::Raw].
+
+mod decode;
+mod encode;
+
+use crate::{
+    config::Config,
+    de::{read::SliceReader, Decoder, DecoderImpl},
+    enc::{Encoder, EncoderImpl},
+    error::{DecodeError, EncodeError},
+    features::VecWriter,
+    schema::{Type, TypeSchema},
+};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::ops::Range;
+
+/// A value of any type that can be described by a [Type].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// A [Type::Unit]
+    Unit,
+    /// A [Type::Bool]
+    Bool(bool),
+    /// A [Type::U8]
+    U8(u8),
+    /// A [Type::U16]
+    U16(u16),
+    /// A [Type::U32]
+    U32(u32),
+    /// A [Type::U64]
+    U64(u64),
+    /// A [Type::U128]
+    U128(u128),
+    /// A [Type::I8]
+    I8(i8),
+    /// A [Type::I16]
+    I16(i16),
+    /// A [Type::I32]
+    I32(i32),
+    /// A [Type::I64]
+    I64(i64),
+    /// A [Type::I128]
+    I128(i128),
+    /// A [Type::F32]
+    F32(f32),
+    /// A [Type::F64]
+    F64(f64),
+    /// A [Type::Char]
+    Char(char),
+    /// A [Type::String]
+    String(String),
+    /// A [Type::Seq] or [Type::Array] of [Type::U8]
+    Bytes(Vec<u8>),
+    /// A [Type::Option]
+    Option(Option<Box<Value>>),
+    /// A [Type::Seq], [Type::Array] or [Type::Tuple]
+    Seq(Vec<Value>),
+    /// A [Type::Map], with its keys and values in the order in which they were encoded
+    Map(Vec<(Value, Value)>),
+    /// A struct, with the names and values of its fields in the order in which they were encoded.
+    ///
+    /// A struct with `#[bincode(default_trailing)]` only has the fields that were encoded. Any bytes that were left at the end of its record are added as a field named `..` with a [Value::Raw].
+    ///
+    /// An entry of a struct with `#[bincode(tagged)]` with an unknown tag is added as a field named `#` followed by the tag, e.g. `#7`, with a [Value::Raw].
+    Struct(Vec<(String, Value)>),
+    /// A variant of an enum
+    Enum {
+        /// The name of the variant
+        variant: String,
+        /// The names and values of the fields of the variant
+        fields: Vec<(String, Value)>,
+    },
+    /// Bytes that are not described by the schema. These are encoded as they are, regardless of the type.
+    Raw(Vec<u8>),
+}
+
+/// The location of a [Value] in the encoded data, see [decode_with_spans].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Span {
+    /// The path to the value from the root value, e.g. `.items[2].name`, `::Variant.0` or `[0].key` for the key of the first entry of a map. This is empty for the root value.
+    pub path: String,
+    /// The offsets of the bytes of the value, including the lengths, tags and versions that are encoded before it
+    pub range: Range<usize>,
+}
+
+/// Decode a [Value] of the root type of `schema` from the given decoder.
+pub fn decode<D: Decoder>(decoder: &mut D, schema: &TypeSchema) -> Result<Value, DecodeError> {
+    decode::ValueDecoder::new(schema, false).decode_type(decoder, &schema.root)
+}
+
+/// Decode a [Value] of the root type of `schema` from the given decoder, and return the [Span] of every value that it contains.
+///
+/// There is a span for the root value and for every value in it, in the order in which they appear in the encoded data. A value comes before the values it contains, e.g. a struct comes before its fields. The offsets are the [offset](crate::de::Decoder::offset) of the decoder.
+///
+/// ```
+/// use bincode::value::Span;
+///
+/// let config = bincode::config::standard();
+/// let schema = bincode::schema_of::<(u8, Vec<u32>), _>(config);
+/// let bytes = bincode::encode_to_vec((1u8, vec![1000u32]), config).unwrap();
+///
+/// let mut decoder = bincode::de::DecoderImpl::new(bincode::de::read::SliceReader::new(&bytes), config);
+/// let (_, spans) = bincode::value::decode_with_spans(&mut decoder, &schema).unwrap();
+/// let span = |path: &str, range| Span { path: path.to_string(), range };
+/// assert_eq!(
+///     spans,
+///     vec![
+///         span("", 0..5),
+///         span(".0", 0..1),
+///         span(".1", 1..5),
+///         span(".1[0]", 2..5),
+///     ]
+/// );
+/// ```
+pub fn decode_with_spans<D: Decoder>(
+    decoder: &mut D,
+    schema: &TypeSchema,
+) -> Result<(Value, Vec<Span>), DecodeError> {
+    let mut value_decoder = decode::ValueDecoder::new(schema, true);
+    let value = value_decoder.decode_type(decoder, &schema.root)?;
+    Ok((value, value_decoder.into_spans()))
+}
+
+/// Encode a [Value] of the root type of `schema` into the given encoder.
+///
+/// Returns `EncodeError::OtherString` if `value` does not match the schema.
+pub fn encode<E: Encoder>(
+    value: &Value,
+    schema: &TypeSchema,
+    encoder: &mut E,
+) -> Result<(), EncodeError> {
+    encode::encode_type(encoder, schema, &schema.root, value)
+}
+
+/// Decode a [Value] of the root type of `schema` from the given slice. Returns the value and the amount of bytes that were read.
+///
+/// See the [module documentation](crate::value) for more information.
+pub fn decode_from_slice<C: Config>(
+    src: &[u8],
+    schema: &TypeSchema,
+    config: C,
+) -> Result<(Value, usize), DecodeError> {
+    let mut decoder = DecoderImpl::<_, C>::new(SliceReader::new(src), config);
+    let value = decode(&mut decoder, schema)?;
+    Ok((value, decoder.offset()))
+}
+
+/// Encode a [Value] of the root type of `schema` into a `Vec<u8>`.
+///
+/// See the [module documentation](crate::value) for more information.
+pub fn encode_to_vec<C: Config>(
+    value: &Value,
+    schema: &TypeSchema,
+    config: C,
+) -> Result<Vec<u8>, EncodeError> {
+    let mut encoder = EncoderImpl::<_, C>::new(VecWriter::default(), config);
+    encode(value, schema, &mut encoder)?;
+    Ok(encoder.into_writer().collect())
+}
+
+/// The name of a field that contains the bytes at the end of a record, see [Value::Struct]
+const TRAILING_BYTES: &str = "..";
+
+/// The prefix of a field that contains an entry with an unknown tag, see [Value::Struct]
+const UNKNOWN_TAG: char = '#';
+
+fn is_u8(ty: &Type) -> bool {
+    *ty == Type::U8
+}
@@ -0,0 +1,369 @@
+//! Unsigned LEB128 integers, with zigzag encoding for signed integers. See [with_leb128_int_encoding](crate::config::Configuration::with_leb128_int_encoding).
+//!
+//! LEB128 has no byte order, so unlike the other varint functions these do not take an `Endian`.
+
+use crate::{
+    de::read::Reader,
+    enc::write::Writer,
+    error::{DecodeError, EncodeError, IntegerType},
+};
+
+const CONTINUATION_BIT: u8 = 0x80;
+const PAYLOAD_MASK: u8 = 0x7F;
+
+/// The maximum amount of bytes of an integer with `bits` bits
+const fn max_len(bits: u32) -> usize {
+    bits.div_ceil(7) as usize
+}
+
+fn encode_u64<W: Writer>(writer: &mut W, mut val: u64) -> Result<(), EncodeError> {
+    if val < CONTINUATION_BIT as u64 {
+        return writer.write(&[val as u8]);
+    }
+    // Collect the bytes first, so the writer is only called once
+    let mut bytes = [0u8; max_len(64)];
+    let mut len = 0;
+    while val >= CONTINUATION_BIT as u64 {
+        bytes[len] = val as u8 | CONTINUATION_BIT;
+        val >>= 7;
+        len += 1;
+    }
+    bytes[len] = val as u8;
+    writer.write(&bytes[..=len])
+}
+
+fn encode_u128<W: Writer>(writer: &mut W, mut val: u128) -> Result<(), EncodeError> {
+    if val < CONTINUATION_BIT as u128 {
+        return writer.write(&[val as u8]);
+    }
+    let mut bytes = [0u8; max_len(128)];
+    let mut len = 0;
+    while val >= CONTINUATION_BIT as u128 {
+        bytes[len] = val as u8 | CONTINUATION_BIT;
+        val >>= 7;
+        len += 1;
+    }
+    bytes[len] = val as u8;
+    writer.write(&bytes[..=len])
+}
+
+pub fn leb128_encode_u16<W: Writer>(writer: &mut W, val: u16) -> Result<(), EncodeError> {
+    encode_u64(writer, val as u64)
+}
+
+pub fn leb128_encode_u32<W: Writer>(writer: &mut W, val: u32) -> Result<(), EncodeError> {
+    encode_u64(writer, val as u64)
+}
+
+pub fn leb128_encode_u64<W: Writer>(writer: &mut W, val: u64) -> Result<(), EncodeError> {
+    encode_u64(writer, val)
+}
+
+pub fn leb128_encode_u128<W: Writer>(writer: &mut W, val: u128) -> Result<(), EncodeError> {
+    encode_u128(writer, val)
+}
+
+pub fn leb128_encode_usize<W: Writer>(writer: &mut W, val: usize) -> Result<(), EncodeError> {
+    encode_u64(writer, val as u64)
+}
+
+// Zigzag maps 0, -1, 1, -2, 2, ... to 0, 1, 2, 3, 4, ... so small negative numbers stay small.
+// This is the same mapping as `with_variable_int_encoding` uses.
+
+pub fn leb128_encode_i16<W: Writer>(writer: &mut W, val: i16) -> Result<(), EncodeError> {
+    encode_u64(writer, ((val << 1) ^ (val >> 15)) as u16 as u64)
+}
+
+pub fn leb128_encode_i32<W: Writer>(writer: &mut W, val: i32) -> Result<(), EncodeError> {
+    encode_u64(writer, ((val << 1) ^ (val >> 31)) as u32 as u64)
+}
+
+pub fn leb128_encode_i64<W: Writer>(writer: &mut W, val: i64) -> Result<(), EncodeError> {
+    encode_u64(writer, ((val << 1) ^ (val >> 63)) as u64)
+}
+
+pub fn leb128_encode_i128<W: Writer>(writer: &mut W, val: i128) -> Result<(), EncodeError> {
+    encode_u128(writer, ((val << 1) ^ (val >> 127)) as u128)
+}
+
+pub fn leb128_encode_isize<W: Writer>(writer: &mut W, val: isize) -> Result<(), EncodeError> {
+    leb128_encode_i64(writer, val as i64)
+}
+
+/// Add the payload of the byte at `index` to `val`. Returns `true` if this is the last byte of the integer.
+///
+/// The last possible byte of an integer with `bits` bits may only contain the bits that are left, everything else is an overflow.
+#[inline(always)]
+fn push_byte_u64(
+    val: &mut u64,
+    index: usize,
+    byte: u8,
+    bits: u32,
+    integer_type: IntegerType,
+) -> Result<bool, DecodeError> {
+    let shift = 7 * index as u32;
+    let payload = (byte & PAYLOAD_MASK) as u64;
+    if bits - shift < 7 && payload >> (bits - shift) != 0 {
+        return Err(DecodeError::Leb128Overflow {
+            expected: integer_type,
+        });
+    }
+    *val |= payload << shift;
+    Ok(byte & CONTINUATION_BIT == 0)
+}
+
+#[inline(always)]
+fn push_byte_u128(
+    val: &mut u128,
+    index: usize,
+    byte: u8,
+    integer_type: IntegerType,
+) -> Result<bool, DecodeError> {
+    let shift = 7 * index as u32;
+    let payload = (byte & PAYLOAD_MASK) as u128;
+    if 128 - shift < 7 && payload >> (128 - shift) != 0 {
+        return Err(DecodeError::Leb128Overflow {
+            expected: integer_type,
+        });
+    }
+    *val |= payload << shift;
+    Ok(byte & CONTINUATION_BIT == 0)
+}
+
+#[inline(never)]
+#[cold]
+fn decode_u64_cold<R: Reader>(
+    read: &mut R,
+    bits: u32,
+    integer_type: IntegerType,
+) -> Result<u64, DecodeError> {
+    let mut val = 0;
+    for index in 0..max_len(bits) {
+        let mut byte = [0u8; 1];
+        read.read(&mut byte)?;
+        if push_byte_u64(&mut val, index, byte[0], bits, integer_type)? {
+            return Ok(val);
+        }
+    }
+    Err(DecodeError::Leb128Overflow {
+        expected: integer_type,
+    })
+}
+
+#[inline(never)]
+#[cold]
+fn decode_u128_cold<R: Reader>(read: &mut R) -> Result<u128, DecodeError> {
+    let mut val = 0;
+    for index in 0..max_len(128) {
+        let mut byte = [0u8; 1];
+        read.read(&mut byte)?;
+        if push_byte_u128(&mut val, index, byte[0], IntegerType::U128)? {
+            return Ok(val);
+        }
+    }
+    Err(DecodeError::Leb128Overflow {
+        expected: IntegerType::U128,
+    })
+}
+
+/// Decode an integer with at most `bits` bits. If the reader can peek the maximum length of the integer, e.g. a `SliceReader` that is not at its end, the bytes are decoded without copying them.
+#[inline]
+fn decode_u64<R: Reader>(
+    read: &mut R,
+    bits: u32,
+    integer_type: IntegerType,
+) -> Result<u64, DecodeError> {
+    if let Some(bytes) = read.peek_read(max_len(bits)) {
+        let mut val = 0;
+        for (index, byte) in bytes.iter().enumerate() {
+            if push_byte_u64(&mut val, index, *byte, bits, integer_type)? {
+                read.consume(index + 1);
+                return Ok(val);
+            }
+        }
+        Err(DecodeError::Leb128Overflow {
+            expected: integer_type,
+        })
+    } else {
+        decode_u64_cold(read, bits, integer_type)
+    }
+}
+
+#[inline]
+fn decode_u128<R: Reader>(read: &mut R) -> Result<u128, DecodeError> {
+    if let Some(bytes) = read.peek_read(max_len(128)) {
+        let mut val = 0;
+        for (index, byte) in bytes.iter().enumerate() {
+            if push_byte_u128(&mut val, index, *byte, IntegerType::U128)? {
+                read.consume(index + 1);
+                return Ok(val);
+            }
+        }
+        Err(DecodeError::Leb128Overflow {
+            expected: IntegerType::U128,
+        })
+    } else {
+        decode_u128_cold(read)
+    }
+}
+
+pub fn leb128_decode_u16<R: Reader>(read: &mut R) -> Result<u16, DecodeError> {
+    decode_u64(read, 16, IntegerType::U16).map(|val| val as u16)
+}
+
+pub fn leb128_decode_u32<R: Reader>(read: &mut R) -> Result<u32, DecodeError> {
+    decode_u64(read, 32, IntegerType::U32).map(|val| val as u32)
+}
+
+pub fn leb128_decode_u64<R: Reader>(read: &mut R) -> Result<u64, DecodeError> {
+    decode_u64(read, 64, IntegerType::U64)
+}
+
+pub fn leb128_decode_u128<R: Reader>(read: &mut R) -> Result<u128, DecodeError> {
+    decode_u128(read)
+}
+
+pub fn leb128_decode_usize<R: Reader>(read: &mut R) -> Result<usize, DecodeError> {
+    decode_u64(
+        read,
+        core::mem::size_of::<usize>() as u32 * 8,
+        IntegerType::Usize,
+    )
+    .map(|val| val as usize)
+}
+
+pub fn leb128_decode_i16<R: Reader>(read: &mut R) -> Result<i16, DecodeError> {
+    let n = leb128_decode_u16(read).map_err(DecodeError::change_integer_type_to_signed)?;
+    Ok((n >> 1) as i16 ^ -((n & 1) as i16))
+}
+
+pub fn leb128_decode_i32<R: Reader>(read: &mut R) -> Result<i32, DecodeError> {
+    let n = leb128_decode_u32(read).map_err(DecodeError::change_integer_type_to_signed)?;
+    Ok((n >> 1) as i32 ^ -((n & 1) as i32))
+}
+
+pub fn leb128_decode_i64<R: Reader>(read: &mut R) -> Result<i64, DecodeError> {
+    let n = leb128_decode_u64(read).map_err(DecodeError::change_integer_type_to_signed)?;
+    Ok((n >> 1) as i64 ^ -((n & 1) as i64))
+}
+
+pub fn leb128_decode_i128<R: Reader>(read: &mut R) -> Result<i128, DecodeError> {
+    let n = leb128_decode_u128(read).map_err(DecodeError::change_integer_type_to_signed)?;
+    Ok((n >> 1) as i128 ^ -((n & 1) as i128))
+}
+
+pub fn leb128_decode_isize<R: Reader>(read: &mut R) -> Result<isize, DecodeError> {
+    let n = leb128_decode_usize(read).map_err(DecodeError::change_integer_type_to_signed)?;
+    Ok((n >> 1) as isize ^ -((n & 1) as isize))
+}
+
+#[test]
+fn test_leb128_known_values() {
+    use crate::{de::read::SliceReader, enc::write::SliceWriter};
+
+    fn encode(f: impl FnOnce(&mut SliceWriter) -> Result<(), EncodeError>) -> ([u8; 20], usize) {
+        let mut buffer = [0u8; 20];
+        let mut writer = SliceWriter::new(&mut buffer);
+        f(&mut writer).unwrap();
+        let len = writer.bytes_written();
+        (buffer, len)
+    }
+
+    // Values from the protobuf documentation
+    let cases: &[(u64, &[u8])] = &[
+        (0, &[0x00]),
+        (1, &[0x01]),
+        (127, &[0x7F]),
+        (128, &[0x80, 0x01]),
+        (150, &[0x96, 0x01]),
+        (300, &[0xAC, 0x02]),
+        (16_384, &[0x80, 0x80, 0x01]),
+        (
+            u64::MAX,
+            &[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01],
+        ),
+    ];
+    for (val, expected) in cases {
+        let (buffer, len) = encode(|writer| leb128_encode_u64(writer, *val));
+        assert_eq!(&buffer[..len], *expected);
+        assert_eq!(
+            leb128_decode_u64(&mut SliceReader::new(expected)).unwrap(),
+            *val
+        );
+    }
+
+    let cases: &[(i32, &[u8])] = &[
+        (0, &[0x00]),
+        (-1, &[0x01]),
+        (1, &[0x02]),
+        (-2, &[0x03]),
+        (-64, &[0x7F]),
+        (64, &[0x80, 0x01]),
+        (i32::MAX, &[0xFE, 0xFF, 0xFF, 0xFF, 0x0F]),
+        (i32::MIN, &[0xFF, 0xFF, 0xFF, 0xFF, 0x0F]),
+    ];
+    for (val, expected) in cases {
+        let (buffer, len) = encode(|writer| leb128_encode_i32(writer, *val));
+        assert_eq!(&buffer[..len], *expected);
+        assert_eq!(
+            leb128_decode_i32(&mut SliceReader::new(expected)).unwrap(),
+            *val
+        );
+    }
+
+    let (buffer, len) = encode(|writer| leb128_encode_u128(writer, u128::MAX));
+    assert_eq!(len, 19);
+    assert_eq!(buffer[18], 0x03);
+    assert_eq!(
+        leb128_decode_u128(&mut SliceReader::new(&buffer[..len])).unwrap(),
+        u128::MAX
+    );
+    let (buffer, len) = encode(|writer| leb128_encode_i128(writer, i128::MIN));
+    assert_eq!(
+        leb128_decode_i128(&mut SliceReader::new(&buffer[..len])).unwrap(),
+        i128::MIN
+    );
+}
+
+#[test]
+fn test_leb128_invalid() {
+    use crate::de::read::SliceReader;
+
+    // 65536 does not fit in an u16
+    assert_eq!(
+        leb128_decode_u16(&mut SliceReader::new(&[0x80, 0x80, 0x04])),
+        Err(DecodeError::Leb128Overflow {
+            expected: IntegerType::U16
+        })
+    );
+    assert_eq!(
+        leb128_decode_i16(&mut SliceReader::new(&[0x80, 0x80, 0x04])),
+        Err(DecodeError::Leb128Overflow {
+            expected: IntegerType::I16
+        })
+    );
+    // Too many continuation bytes, with and without the fast path
+    let bytes = [0x80; 12];
+    assert_eq!(
+        leb128_decode_u64(&mut SliceReader::new(&bytes)),
+        Err(DecodeError::Leb128Overflow {
+            expected: IntegerType::U64
+        })
+    );
+    assert_eq!(
+        leb128_decode_u32(&mut SliceReader::new(&bytes[..5])),
+        Err(DecodeError::Leb128Overflow {
+            expected: IntegerType::U32
+        })
+    );
+    assert_eq!(
+        leb128_decode_u32(&mut SliceReader::new(&bytes[..3])),
+        Err(DecodeError::UnexpectedEnd)
+    );
+
+    // Padded values are accepted, like in protobuf
+    assert_eq!(
+        leb128_decode_u32(&mut SliceReader::new(&[0x81, 0x80, 0x00])),
+        Ok(1)
+    );
+}
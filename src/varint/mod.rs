@@ -2,6 +2,7 @@ mod decode_signed;
 mod decode_unsigned;
 mod encode_signed;
 mod encode_unsigned;
+mod leb128;
 
 pub use self::{
     decode_signed::{
@@ -20,10 +21,17 @@ pub use self::{
         varint_encode_u128, varint_encode_u16, varint_encode_u32, varint_encode_u64,
         varint_encode_usize,
     },
+    leb128::{
+        leb128_decode_i128, leb128_decode_i16, leb128_decode_i32, leb128_decode_i64,
+        leb128_decode_isize, leb128_decode_u128, leb128_decode_u16, leb128_decode_u32,
+        leb128_decode_u64, leb128_decode_usize, leb128_encode_i128, leb128_encode_i16,
+        leb128_encode_i32, leb128_encode_i64, leb128_encode_isize, leb128_encode_u128,
+        leb128_encode_u16, leb128_encode_u32, leb128_encode_u64, leb128_encode_usize,
+    },
 };
 
-pub(self) const SINGLE_BYTE_MAX: u8 = 250;
-pub(self) const U16_BYTE: u8 = 251;
-pub(self) const U32_BYTE: u8 = 252;
-pub(self) const U64_BYTE: u8 = 253;
-pub(self) const U128_BYTE: u8 = 254;
+const SINGLE_BYTE_MAX: u8 = 250;
+const U16_BYTE: u8 = 251;
+const U32_BYTE: u8 = 252;
+const U64_BYTE: u8 = 253;
+const U128_BYTE: u8 = 254;
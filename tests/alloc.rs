@@ -71,7 +71,7 @@ fn test_alloc_commons() {
             map.push(5);
             map
         },
-        |a, b| a.into_iter().collect::<Vec<_>>() == b.into_iter().collect::<Vec<_>>(),
+        |a, b| a.iter().collect::<Vec<_>>() == b.iter().collect::<Vec<_>>(),
     );
     the_same({
         let mut map = BTreeMap::<u32, i32>::new();
@@ -91,6 +91,41 @@ fn test_alloc_commons() {
     });
 }
 
+#[test]
+fn test_byte_containers() {
+    let bytes: Vec<u8> = (0..=255).cycle().take(1000).collect();
+
+    the_same(bytes.clone());
+    the_same(bytes.clone().into_boxed_slice());
+    the_same(String::from_utf8(vec![b'a'; 1000]).unwrap());
+    the_same({
+        // make sure the ring buffer wraps around, so it consists of 2 slices
+        let mut deque = VecDeque::<u8>::with_capacity(8);
+        deque.extend([1, 2, 3, 4, 5, 6]);
+        deque.drain(..4);
+        deque.extend([7, 8, 9, 10, 11]);
+        assert!(!deque.as_slices().1.is_empty());
+        deque
+    });
+
+    // The bytes should be the same as encoding each u8 by itself
+    let config = bincode::config::legacy();
+    let encoded = bincode::encode_to_vec(&bytes, config).unwrap();
+    assert_eq!(&encoded[..8], &1000u64.to_le_bytes());
+    assert_eq!(&encoded[8..], &bytes[..]);
+    assert_eq!(
+        bincode::encode_to_vec(VecDeque::from(bytes.clone()), config).unwrap(),
+        encoded
+    );
+
+    // A payload that is shorter than its length should fail
+    let result = bincode::decode_from_slice::<Vec<u8>, _>(&encoded[..500], config);
+    assert_eq!(
+        result.unwrap_err(),
+        bincode::error::DecodeError::UnexpectedEnd
+    );
+}
+
 #[test]
 fn test_container_limits() {
     use bincode::{error::DecodeError, Decode};
@@ -99,8 +134,8 @@ fn test_container_limits() {
 
     // for this test we'll create a malformed package of a lot of bytes
     let test_cases = &[
-        // u64::max_value(), should overflow
-        bincode::encode_to_vec(u64::max_value(), bincode::config::standard()).unwrap(),
+        // u64::MAX, should overflow
+        bincode::encode_to_vec(u64::MAX, bincode::config::standard()).unwrap(),
         // A high value which doesn't overflow, but exceeds the decode limit
         bincode::encode_to_vec(DECODE_LIMIT as u64, bincode::config::standard()).unwrap(),
     ];
@@ -120,11 +155,71 @@ fn test_container_limits() {
         validate_fail::<BTreeSet<i32>>(slice);
         validate_fail::<VecDeque<i32>>(slice);
         validate_fail::<Vec<i32>>(slice);
+        validate_fail::<Vec<u8>>(slice);
+        validate_fail::<VecDeque<u8>>(slice);
         validate_fail::<String>(slice);
         validate_fail::<Box<[u8]>>(slice);
         #[cfg(feature = "std")]
         {
             validate_fail::<std::collections::HashMap<i32, i32>>(slice);
+            validate_fail::<std::collections::HashSet<i32>>(slice);
         }
     }
 }
+
+#[test]
+fn test_max_container_len() {
+    use bincode::{error::DecodeError, Decode};
+
+    const MAX_LEN: usize = 10;
+    let config = bincode::config::standard().with_max_container_len::<MAX_LEN>();
+
+    fn validate_fail<T: Decode + core::fmt::Debug>(slice: &[u8]) {
+        let result = bincode::decode_from_slice::<T, _>(
+            slice,
+            bincode::config::standard().with_max_container_len::<MAX_LEN>(),
+        );
+
+        assert_eq!(
+            result.unwrap_err(),
+            DecodeError::ContainerLengthExceeded {
+                found: MAX_LEN + 1,
+                allowed: MAX_LEN,
+            }
+        );
+    }
+
+    // Zero-sized items pass the byte limit, but not the container length limit
+    let slice = bincode::encode_to_vec(vec![(); MAX_LEN + 1], config).unwrap();
+    validate_fail::<Vec<()>>(&slice);
+    validate_fail::<BinaryHeap<()>>(&slice);
+    validate_fail::<BTreeSet<()>>(&slice);
+    validate_fail::<VecDeque<()>>(&slice);
+    validate_fail::<BTreeMap<(), ()>>(&slice);
+    validate_fail::<String>(&slice);
+    validate_fail::<Box<[u8]>>(&slice);
+    #[cfg(feature = "std")]
+    {
+        validate_fail::<std::collections::HashMap<(), ()>>(&slice);
+        validate_fail::<std::collections::HashSet<()>>(&slice);
+    }
+    assert_eq!(
+        bincode::decode_from_slice::<&str, _>(&slice, config).unwrap_err(),
+        DecodeError::ContainerLengthExceeded {
+            found: MAX_LEN + 1,
+            allowed: MAX_LEN,
+        }
+    );
+
+    // Containers up to the maximum length can be decoded
+    let slice = bincode::encode_to_vec(vec![(); MAX_LEN], config).unwrap();
+    let (decoded, _): (Vec<()>, usize) = bincode::decode_from_slice(&slice, config).unwrap();
+    assert_eq!(decoded.len(), MAX_LEN);
+
+    // Fixed size arrays are not affected
+    let config = config.write_fixed_array_length();
+    let slice = bincode::encode_to_vec([0u8; MAX_LEN + 1], config).unwrap();
+    let (decoded, _): ([u8; MAX_LEN + 1], usize) =
+        bincode::decode_from_slice(&slice, config).unwrap();
+    assert_eq!(decoded, [0u8; MAX_LEN + 1]);
+}
@@ -136,7 +136,7 @@ fn test_slice() {
     assert_eq!(&buffer[..8], &[7, 1, 2, 3, 4, 5, 6, 7]);
 
     let (output, len): (&[u8], usize) =
-        bincode::decode_from_slice(&mut buffer[..8], bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&buffer[..8], bincode::config::standard()).unwrap();
     assert_eq!(input, output);
     assert_eq!(len, 8);
 }
@@ -175,7 +175,7 @@ fn test_str() {
     );
 
     let (output, len): (&str, usize) =
-        bincode::decode_from_slice(&mut buffer[..12], bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&buffer[..12], bincode::config::standard()).unwrap();
     assert_eq!(input, output);
     assert_eq!(len, 12);
 }
@@ -214,7 +214,7 @@ fn test_array() {
     assert_eq!(&buffer[..10], &[10, 20, 30, 40, 50, 60, 70, 80, 90, 100]);
 
     let (output, len): ([u8; 10], usize) =
-        bincode::decode_from_slice(&mut buffer[..10], bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&buffer[..10], bincode::config::standard()).unwrap();
     assert_eq!(input, output);
     assert_eq!(len, 10);
 }
@@ -224,14 +224,14 @@ fn test_duration_out_of_range() {
     let mut input = [0u8; 14];
 
     bincode::encode_into_slice(
-        &(u64::MAX, u32::MAX),
+        (u64::MAX, u32::MAX),
         &mut input,
         bincode::config::standard(),
     )
     .unwrap();
 
     let result: Result<(std::time::Duration, usize), _> =
-        bincode::decode_from_slice(&mut input, bincode::config::standard());
+        bincode::decode_from_slice(&input, bincode::config::standard());
 
     assert_eq!(
         result.unwrap_err(),
@@ -247,16 +247,121 @@ fn test_duration_wrapping() {
     let mut input = [0u8; 14];
 
     bincode::encode_into_slice(
-        &(u64::MAX - 4, u32::MAX),
+        (u64::MAX - 4, u32::MAX),
         &mut input,
         bincode::config::standard(),
     )
     .unwrap();
 
     let (result, _): (std::time::Duration, _) =
-        bincode::decode_from_slice(&mut input, bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&input, bincode::config::standard()).unwrap();
 
     assert_eq!(result.as_secs(), u64::MAX);
 
     assert_eq!(result.subsec_nanos(), 294967295);
 }
+
+#[test]
+fn test_encoded_size() {
+    let input = (1000u32, [1u8, 2, 3, 4], "Hello");
+
+    // standard: 3 bytes varint u32, no array length, 1 byte length + 5 bytes str
+    let config = bincode::config::standard();
+    assert_eq!(bincode::encoded_size(input, config).unwrap(), 3 + 4 + 1 + 5);
+
+    // legacy: 4 bytes u32, 8 bytes array length, 8 bytes length + 5 bytes str
+    let config = bincode::config::legacy();
+    assert_eq!(
+        bincode::encoded_size(input, config).unwrap(),
+        4 + 8 + 4 + 8 + 5
+    );
+
+    let config = bincode::config::legacy().skip_fixed_array_length();
+    assert_eq!(bincode::encoded_size(input, config).unwrap(), 4 + 4 + 8 + 5);
+}
+
+#[test]
+fn test_max_encoded_size() {
+    use bincode::config;
+
+    type Input = (u32, [u16; 300], Option<char>, Result<u8, i64>);
+
+    // standard: varint, no array length
+    assert_eq!(
+        bincode::max_encoded_size::<Input, _>(config::standard()),
+        5 + 300 * 3 + 1 + 4 + 1 + 9
+    );
+    // varint array lengths are encoded as u64, 300 fits in 3 bytes
+    assert_eq!(
+        bincode::max_encoded_size::<Input, _>(config::standard().write_fixed_array_length()),
+        5 + 3 + 300 * 3 + 1 + 4 + 1 + 9
+    );
+    assert_eq!(
+        bincode::max_encoded_size::<Input, _>(config::legacy()),
+        4 + 8 + 300 * 2 + 1 + 4 + 1 + 8
+    );
+    // LEB128 holds 7 bits per byte, 300 fits in 2 bytes
+    assert_eq!(
+        bincode::max_encoded_size::<Input, _>(
+            config::standard()
+                .with_leb128_int_encoding()
+                .write_fixed_array_length()
+        ),
+        5 + 2 + 300 * 3 + 1 + 4 + 1 + 10
+    );
+
+    let input: Input = (u32::MAX, [u16::MAX; 300], Some(char::MAX), Err(i64::MIN));
+    assert_eq!(
+        bincode::encoded_size(input, config::standard()).unwrap(),
+        bincode::max_encoded_size::<Input, _>(config::standard())
+    );
+    assert_eq!(
+        bincode::encoded_size(input, config::legacy()).unwrap(),
+        bincode::max_encoded_size::<Input, _>(config::legacy())
+    );
+    let leb128 = config::standard().with_leb128_int_encoding();
+    assert_eq!(
+        bincode::encoded_size(input, leb128).unwrap(),
+        bincode::max_encoded_size::<Input, _>(leb128)
+    );
+}
+
+#[test]
+fn test_decode_iter() {
+    let config = bincode::config::standard();
+    let mut slice = [0u8; 32];
+    let mut len = 0;
+    for value in ["a", "", "bcd"] {
+        len += bincode::encode_into_slice(value, &mut slice[len..], config).unwrap();
+    }
+
+    let mut iter = bincode::decode_iter::<&str, _>(&slice[..len], config);
+    assert_eq!(iter.next(), Some(Ok("a")));
+    assert_eq!(iter.next(), Some(Ok("")));
+    assert_eq!(iter.next(), Some(Ok("bcd")));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+
+    // An empty slice contains no values
+    assert_eq!(bincode::decode_iter::<u32, _>(&[], config).next(), None);
+
+    // A value that is cut off is an error, after which the iterator stops
+    let mut iter = bincode::decode_iter::<&str, _>(&slice[..len - 1], config);
+    assert_eq!(iter.next(), Some(Ok("a")));
+    assert_eq!(iter.next(), Some(Ok("")));
+    assert_eq!(
+        iter.next(),
+        Some(Err(bincode::error::DecodeError::UnexpectedEnd))
+    );
+    assert_eq!(iter.remaining(), &slice[3..len - 1]);
+    assert_eq!(iter.next(), None);
+
+    // Values that are encoded as 0 bytes are an error instead of an endless iterator
+    let mut iter = bincode::decode_iter::<(), _>(&slice[..len], config);
+    assert!(matches!(
+        iter.next(),
+        Some(Err(bincode::error::DecodeError::Other(_)))
+    ));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.remaining(), &slice[..len]);
+}
@@ -0,0 +1,418 @@
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use bincode::compat::{check, Incompatibility, IncompatibilityKind};
+use bincode::schema::{Schema, StructLayout, Type, TypeSchema};
+
+fn standard_schema<T: Schema + ?Sized>() -> TypeSchema {
+    bincode::schema_of::<T, _>(bincode::config::standard())
+}
+
+fn legacy_schema<T: Schema + ?Sized>() -> TypeSchema {
+    bincode::schema_of::<T, _>(bincode::config::legacy())
+}
+
+fn incompatibility(path: &str, kind: IncompatibilityKind) -> Incompatibility {
+    Incompatibility {
+        path: path.to_string(),
+        kind,
+    }
+}
+
+// Only the schemas of these types are used
+#[allow(dead_code)]
+mod v1 {
+    #[derive(bincode::Schema)]
+    pub struct Settings {
+        pub volume: u16,
+        pub name: String,
+        pub mode: Mode,
+    }
+
+    #[derive(bincode::Schema)]
+    pub enum Mode {
+        Off,
+        On { level: u8 },
+        Auto,
+    }
+
+    #[derive(bincode::Schema)]
+    #[bincode(default_trailing)]
+    pub struct Trailing {
+        pub a: u32,
+        pub b: u32,
+    }
+
+    #[derive(bincode::Schema)]
+    #[bincode(tagged)]
+    pub struct Tagged {
+        #[bincode(tag = 1)]
+        pub a: u32,
+        #[bincode(tag = 2)]
+        pub b: String,
+    }
+
+    #[derive(bincode::Schema)]
+    #[bincode(version = 1)]
+    pub struct Versioned {
+        pub a: u32,
+    }
+}
+
+#[allow(dead_code)]
+mod v2 {
+    // Widened integer and a new variant at the end
+    #[derive(bincode::Schema)]
+    pub struct Settings {
+        pub volume: u64,
+        pub name: String,
+        pub mode: Mode,
+    }
+
+    #[derive(bincode::Schema)]
+    pub enum Mode {
+        Off,
+        On { level: u8 },
+        Auto,
+        Custom(Vec<u8>),
+    }
+
+    #[derive(bincode::Schema)]
+    #[bincode(default_trailing)]
+    pub struct Trailing {
+        pub a: u32,
+        pub b: u32,
+        pub c: Option<u32>,
+    }
+
+    #[derive(bincode::Schema)]
+    #[bincode(tagged)]
+    pub struct Tagged {
+        #[bincode(tag = 2)]
+        pub b: String,
+        #[bincode(tag = 3)]
+        pub c: u8,
+    }
+
+    #[derive(bincode::Schema)]
+    #[bincode(version = 2)]
+    pub struct Versioned {
+        pub a: String,
+    }
+}
+
+#[allow(dead_code)]
+mod v3 {
+    // Breaking changes
+    #[derive(bincode::Schema)]
+    pub struct Settings {
+        pub name: String,
+        pub volume: i64,
+        pub mode: Mode,
+    }
+
+    #[derive(bincode::Schema)]
+    #[bincode(tag_type = "u8")]
+    pub enum Mode {
+        Off,
+        Auto,
+        On { level: u16, extra: bool },
+    }
+
+    #[derive(bincode::Schema)]
+    #[bincode(default_trailing)]
+    pub struct Trailing {
+        pub a: u32,
+    }
+
+    #[derive(bincode::Schema)]
+    #[bincode(tagged)]
+    pub struct Tagged {
+        #[bincode(tag = 1)]
+        pub a: u8,
+    }
+
+    #[derive(bincode::Schema)]
+    pub struct Versioned {
+        pub a: u32,
+    }
+}
+
+#[test]
+fn test_compatible_changes() {
+    let old = standard_schema::<v1::Settings>();
+    assert_eq!(check(&old, &old), Ok(()));
+    assert_eq!(check(&old, &standard_schema::<v2::Settings>()), Ok(()));
+    assert_eq!(
+        check(
+            &standard_schema::<v1::Trailing>(),
+            &standard_schema::<v2::Trailing>()
+        ),
+        Ok(())
+    );
+    // Removing trailing fields is also allowed
+    assert_eq!(
+        check(
+            &standard_schema::<v2::Trailing>(),
+            &standard_schema::<v3::Trailing>()
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        check(
+            &standard_schema::<v1::Tagged>(),
+            &standard_schema::<v2::Tagged>()
+        ),
+        Ok(())
+    );
+    // Newer versions are migrated, so their fields are not compared
+    assert_eq!(
+        check(
+            &standard_schema::<v1::Versioned>(),
+            &standard_schema::<v2::Versioned>()
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        check(
+            &standard_schema::<Vec<u16>>(),
+            &standard_schema::<Vec<u128>>()
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_breaking_changes() {
+    let errors = check(
+        &standard_schema::<v2::Settings>(),
+        &standard_schema::<v3::Settings>(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            incompatibility(
+                "",
+                IncompatibilityKind::FieldMoved {
+                    name: "volume".to_string(),
+                    old: 0,
+                    new: 1
+                }
+            ),
+            incompatibility(
+                "",
+                IncompatibilityKind::FieldMoved {
+                    name: "name".to_string(),
+                    old: 1,
+                    new: 0
+                }
+            ),
+            incompatibility(
+                ".mode",
+                IncompatibilityKind::TagTypeChanged {
+                    old: Type::U32,
+                    new: Type::U8
+                }
+            ),
+            incompatibility(
+                ".mode",
+                IncompatibilityKind::VariantIndexChanged {
+                    name: "On".to_string(),
+                    old: 1,
+                    new: 2
+                }
+            ),
+            incompatibility(
+                ".mode::On.level",
+                IncompatibilityKind::TypeChanged {
+                    old: Type::U8,
+                    new: Type::U16
+                }
+            ),
+            incompatibility(
+                ".mode::On",
+                IncompatibilityKind::FieldAdded {
+                    name: "extra".to_string()
+                }
+            ),
+            incompatibility(
+                ".mode",
+                IncompatibilityKind::VariantIndexChanged {
+                    name: "Auto".to_string(),
+                    old: 2,
+                    new: 1
+                }
+            ),
+            incompatibility(
+                ".mode",
+                IncompatibilityKind::VariantRemoved {
+                    name: "Custom".to_string()
+                }
+            ),
+        ]
+    );
+    assert_eq!(
+        errors[0].to_string(),
+        "FieldMoved { name: \"volume\", old: 0, new: 1 }"
+    );
+    assert_eq!(
+        errors[4].to_string(),
+        ".mode::On.level: TypeChanged { old: U8, new: U16 }"
+    );
+
+    assert_eq!(
+        check(
+            &standard_schema::<v1::Tagged>(),
+            &standard_schema::<v3::Tagged>()
+        ),
+        Err(vec![incompatibility(
+            ".a",
+            IncompatibilityKind::TypeChanged {
+                old: Type::U32,
+                new: Type::U8
+            }
+        )])
+    );
+    assert_eq!(
+        check(
+            &standard_schema::<v1::Trailing>(),
+            &standard_schema::<v1::Tagged>()
+        ),
+        Err(vec![incompatibility(
+            "",
+            IncompatibilityKind::LayoutChanged {
+                old: StructLayout::DefaultTrailing,
+                new: StructLayout::Tagged
+            }
+        )])
+    );
+    assert_eq!(
+        check(
+            &standard_schema::<v2::Versioned>(),
+            &standard_schema::<v1::Versioned>()
+        ),
+        Err(vec![incompatibility(
+            "",
+            IncompatibilityKind::VersionDowngraded { old: 2, new: 1 }
+        )])
+    );
+    assert_eq!(
+        check(
+            &standard_schema::<v1::Versioned>(),
+            &standard_schema::<v3::Versioned>()
+        ),
+        Err(vec![incompatibility(
+            "",
+            IncompatibilityKind::VersionChanged {
+                old: Some(1),
+                new: None
+            }
+        )])
+    );
+    assert_eq!(
+        check(&standard_schema::<[u8; 4]>(), &standard_schema::<[u8; 5]>()),
+        Err(vec![incompatibility(
+            "",
+            IncompatibilityKind::ArrayLengthChanged { old: 4, new: 5 }
+        )])
+    );
+    assert_eq!(
+        check(
+            &standard_schema::<(u8, String)>(),
+            &standard_schema::<(u8, String, bool)>()
+        ),
+        Err(vec![incompatibility(
+            "",
+            IncompatibilityKind::TupleLengthChanged { old: 2, new: 3 }
+        )])
+    );
+    // Signed and unsigned integers are encoded differently
+    assert_eq!(
+        check(
+            &standard_schema::<Option<u32>>(),
+            &standard_schema::<Option<i64>>()
+        ),
+        Err(vec![incompatibility(
+            "",
+            IncompatibilityKind::TypeChanged {
+                old: Type::U32,
+                new: Type::I64
+            }
+        )])
+    );
+}
+
+#[test]
+fn test_config_changes() {
+    // Fixed int encoding writes every integer with the size of its type
+    assert_eq!(
+        check(
+            &legacy_schema::<v1::Settings>(),
+            &legacy_schema::<v2::Settings>()
+        ),
+        Err(vec![incompatibility(
+            ".volume",
+            IncompatibilityKind::TypeChanged {
+                old: Type::U16,
+                new: Type::U64
+            }
+        )])
+    );
+    // LEB128 integers can be widened like varints
+    let leb128 = bincode::config::standard().with_leb128_int_encoding();
+    assert_eq!(
+        check(
+            &bincode::schema_of::<v1::Settings, _>(leb128),
+            &bincode::schema_of::<v2::Settings, _>(leb128)
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        check(
+            &standard_schema::<u32>(),
+            &bincode::schema_of::<u32, _>(leb128)
+        ),
+        Err(vec![incompatibility(
+            "",
+            IncompatibilityKind::ConfigChanged {
+                option: "int_encoding"
+            }
+        )])
+    );
+
+    let old = standard_schema::<u32>();
+    let new = bincode::schema_of::<u32, _>(
+        bincode::config::standard()
+            .with_big_endian()
+            .with_fixed_int_encoding(),
+    );
+    assert_eq!(
+        check(&old, &new),
+        Err(vec![
+            incompatibility("", IncompatibilityKind::ConfigChanged { option: "endian" }),
+            incompatibility(
+                "",
+                IncompatibilityKind::ConfigChanged {
+                    option: "int_encoding"
+                }
+            ),
+        ])
+    );
+    assert_eq!(
+        check(&standard_schema::<u8>(), &legacy_schema::<u8>()),
+        Err(vec![
+            incompatibility(
+                "",
+                IncompatibilityKind::ConfigChanged {
+                    option: "int_encoding"
+                }
+            ),
+            incompatibility(
+                "",
+                IncompatibilityKind::ConfigChanged {
+                    option: "skip_fixed_array_length"
+                }
+            ),
+        ])
+    );
+}
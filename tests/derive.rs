@@ -83,9 +83,9 @@ fn test_encode_tuple() {
 #[test]
 fn test_decode_tuple() {
     let start = TestTupleStruct(5, 10, 1024);
-    let mut slice = [5, 10, 251, 0, 4];
+    let slice = [5, 10, 251, 0, 4];
     let (result, len): (TestTupleStruct, usize) =
-        bincode::decode_from_slice(&mut slice, bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&slice, bincode::config::standard()).unwrap();
     assert_eq!(result, start);
     assert_eq!(len, 5);
 }
@@ -109,9 +109,9 @@ fn test_encode_enum_struct_variant() {
 #[test]
 fn test_decode_enum_struct_variant() {
     let start = TestEnum::Bar { name: 5u32 };
-    let mut slice = [1, 5];
+    let slice = [1, 5];
     let (result, len): (TestEnum, usize) =
-        bincode::decode_from_slice(&mut slice, bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&slice, bincode::config::standard()).unwrap();
     assert_eq!(result, start);
     assert_eq!(len, 2);
 }
@@ -119,9 +119,9 @@ fn test_decode_enum_struct_variant() {
 #[test]
 fn test_decode_enum_unit_variant() {
     let start = TestEnum::Foo;
-    let mut slice = [0];
+    let slice = [0];
     let (result, len): (TestEnum, usize) =
-        bincode::decode_from_slice(&mut slice, bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&slice, bincode::config::standard()).unwrap();
     assert_eq!(result, start);
     assert_eq!(len, 1);
 }
@@ -149,9 +149,9 @@ fn test_encode_enum_tuple_variant() {
 #[test]
 fn test_decode_enum_tuple_variant() {
     let start = TestEnum::Baz(5, 10, 1024);
-    let mut slice = [2, 5, 10, 251, 0, 4];
+    let slice = [2, 5, 10, 251, 0, 4];
     let (result, len): (TestEnum, usize) =
-        bincode::decode_from_slice(&mut slice, bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&slice, bincode::config::standard()).unwrap();
     assert_eq!(result, start);
     assert_eq!(len, 6);
 }
@@ -176,9 +176,9 @@ fn test_encode_borrowed_enum_struct_variant() {
 #[test]
 fn test_decode_borrowed_enum_struct_variant() {
     let start = TestEnum2::Bar { name: "foo" };
-    let mut slice = [1, 3, 102, 111, 111];
+    let slice = [1, 3, 102, 111, 111];
     let (result, len): (TestEnum2, usize) =
-        bincode::decode_from_slice(&mut slice, bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&slice, bincode::config::standard()).unwrap();
     assert_eq!(result, start);
     assert_eq!(len, 5);
 }
@@ -186,9 +186,9 @@ fn test_decode_borrowed_enum_struct_variant() {
 #[test]
 fn test_decode_borrowed_enum_unit_variant() {
     let start = TestEnum2::Foo;
-    let mut slice = [0];
+    let slice = [0];
     let (result, len): (TestEnum2, usize) =
-        bincode::decode_from_slice(&mut slice, bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&slice, bincode::config::standard()).unwrap();
     assert_eq!(result, start);
     assert_eq!(len, 1);
 }
@@ -216,9 +216,9 @@ fn test_encode_borrowed_enum_tuple_variant() {
 #[test]
 fn test_decode_borrowed_enum_tuple_variant() {
     let start = TestEnum2::Baz(5, 10, 1024);
-    let mut slice = [2, 5, 10, 251, 0, 4];
+    let slice = [2, 5, 10, 251, 0, 4];
     let (result, len): (TestEnum2, usize) =
-        bincode::decode_from_slice(&mut slice, bincode::config::standard()).unwrap();
+        bincode::decode_from_slice(&slice, bincode::config::standard()).unwrap();
     assert_eq!(result, start);
     assert_eq!(len, 6);
 }
@@ -365,3 +365,1280 @@ fn test_enum_with_generics_roundtrip() {
             .0;
     assert_eq!(start, decoded);
 }
+
+#[derive(bincode::Encode, bincode::MaxSize)]
+pub struct MaxSizeStruct<T> {
+    a: T,
+    b: [u8; 4],
+    c: Option<u64>,
+}
+
+#[derive(bincode::Encode, bincode::MaxSize)]
+pub enum MaxSizeEnum {
+    Unit,
+    Small(u8),
+    Large { a: u128, b: char },
+}
+
+#[derive(bincode::MaxSize)]
+pub struct MaxSizeUnit;
+
+#[test]
+fn test_max_size_derive() {
+    use bincode::config;
+
+    // 3 + array + 1 + 9
+    const STANDARD: usize = bincode::max_encoded_size::<MaxSizeStruct<u16>, _>(config::standard());
+    assert_eq!(STANDARD, 3 + 4 + 1 + 9);
+    assert_eq!(
+        bincode::max_encoded_size::<MaxSizeStruct<u16>, _>(config::legacy()),
+        2 + 8 + 4 + 1 + 8
+    );
+    assert_eq!(
+        bincode::max_encoded_size::<MaxSizeStruct<u16>, _>(
+            config::standard().write_fixed_array_length()
+        ),
+        3 + 1 + 4 + 1 + 9
+    );
+
+    let mut slice = [0u8; STANDARD];
+    let value = MaxSizeStruct {
+        a: u16::MAX,
+        b: [0xFF; 4],
+        c: Some(u64::MAX),
+    };
+    let bytes_written = bincode::encode_into_slice(value, &mut slice, config::standard()).unwrap();
+    assert_eq!(bytes_written, STANDARD);
+
+    // variant index + largest variant
+    assert_eq!(
+        bincode::max_encoded_size::<MaxSizeEnum, _>(config::standard()),
+        5 + 17 + 4
+    );
+    assert_eq!(
+        bincode::max_encoded_size::<MaxSizeEnum, _>(config::legacy()),
+        4 + 16 + 4
+    );
+    let value = MaxSizeEnum::Large {
+        a: u128::MAX,
+        b: char::MAX,
+    };
+    assert_eq!(
+        bincode::encoded_size(value, config::legacy()).unwrap(),
+        bincode::max_encoded_size::<MaxSizeEnum, _>(config::legacy())
+    );
+
+    assert_eq!(
+        bincode::max_encoded_size::<MaxSizeUnit, _>(config::standard()),
+        0
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+pub enum DepthTree {
+    Leaf,
+    Node(Box<DepthTree>),
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+pub struct DepthWrapper {
+    tree: DepthTree,
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_max_depth() {
+    use bincode::error::DecodeError;
+
+    // 1 wrapper + 3 nodes + 1 leaf = 5 levels of nesting
+    let start = DepthWrapper {
+        tree: DepthTree::Node(Box::new(DepthTree::Node(Box::new(DepthTree::Node(
+            Box::new(DepthTree::Leaf),
+        ))))),
+    };
+    let bytes = bincode::encode_to_vec(&start, bincode::config::standard()).unwrap();
+
+    let config = bincode::config::standard().with_max_depth::<5>();
+    let (decoded, len): (DepthWrapper, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, start);
+    assert_eq!(len, bytes.len());
+
+    let config = bincode::config::standard().with_max_depth::<4>();
+    assert_eq!(
+        bincode::decode_from_slice::<DepthWrapper, _>(&bytes, config)
+            .unwrap_err()
+            .without_context(),
+        &DecodeError::DepthLimitExceeded
+    );
+
+    // A deeply nested tree is rejected before it overflows the stack
+    let mut bytes = vec![1u8; 1_000_000];
+    bytes.push(0);
+    let config = bincode::config::standard().with_max_depth::<100>();
+    assert_eq!(
+        bincode::decode_from_slice::<DepthTree, _>(&bytes, config)
+            .unwrap_err()
+            .without_context(),
+        &DecodeError::DepthLimitExceeded
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+pub struct ContextMessage {
+    id: u32,
+    header: ContextHeader,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+pub enum ContextHeader {
+    V1(u8),
+    V2 { name: String, kind: Option<u8> },
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_decode_error_context() {
+    use bincode::error::DecodeError;
+
+    let config = bincode::config::standard();
+    let mut bytes = bincode::encode_to_vec(
+        ContextMessage {
+            id: 1000,
+            header: ContextHeader::V2 {
+                name: String::from("abc"),
+                kind: Some(5),
+            },
+        },
+        config,
+    )
+    .unwrap();
+    // id (3 bytes), variant (1 byte), name (4 bytes), kind
+    assert_eq!(bytes, [251, 232, 3, 1, 3, b'a', b'b', b'c', 1, 5]);
+
+    // Invalid utf8 in `name`
+    bytes[5] = 0xFF;
+    let err = bincode::decode_from_slice::<ContextMessage, _>(&bytes, config).unwrap_err();
+    match &err {
+        DecodeError::WithContext {
+            offset,
+            path,
+            inner,
+        } => {
+            assert_eq!(*offset, 8);
+            assert_eq!(path, &["ContextMessage.header", "ContextHeader::V2.name"]);
+            assert!(matches!(**inner, DecodeError::Utf8(_)));
+        }
+        _ => panic!("Expected WithContext, got {:?}", err),
+    }
+    assert!(matches!(err.without_context(), DecodeError::Utf8(_)));
+
+    // Invalid `Option` variant in `kind`
+    bytes[5] = b'a';
+    bytes[8] = 2;
+    let err = bincode::decode_from_slice::<ContextMessage, _>(&bytes, config).unwrap_err();
+    match err {
+        DecodeError::WithContext { offset, path, .. } => {
+            assert_eq!(offset, 9);
+            assert_eq!(path, ["ContextMessage.header", "ContextHeader::V2.kind"]);
+        }
+        _ => panic!("Expected WithContext, got {:?}", err),
+    }
+
+    // Errors in the type itself do not get a path entry
+    bytes[3] = 5;
+    let err = bincode::decode_from_slice::<ContextMessage, _>(&bytes, config).unwrap_err();
+    match err {
+        DecodeError::WithContext {
+            offset,
+            path,
+            inner,
+        } => {
+            assert_eq!(offset, 4);
+            assert_eq!(path, ["ContextMessage.header"]);
+            assert!(matches!(
+                *inner,
+                DecodeError::UnexpectedVariant { found: 5, .. }
+            ));
+        }
+        _ => panic!("Expected WithContext, got {:?}", err),
+    }
+}
+
+fn skipped_default() -> u32 {
+    42
+}
+
+#[derive(bincode::Encode, bincode::Decode, bincode::MaxSize, PartialEq, Debug)]
+pub struct SkipStruct {
+    a: u32,
+    #[bincode(skip)]
+    cache: u64,
+    #[bincode(default = "skipped_default")]
+    handle: u32,
+    b: u8,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+pub struct SkipTupleStruct(
+    u32,
+    #[bincode(skip)] u64,
+    #[bincode(default = "skipped_default")] u32,
+);
+
+#[derive(bincode::Encode, bincode::Decode, bincode::MaxSize, PartialEq, Debug)]
+pub enum SkipEnum {
+    Struct {
+        #[bincode(skip)]
+        cache: u64,
+        a: u8,
+    },
+    Tuple(#[bincode(default = "skipped_default")] u32, u8),
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+pub struct SkipBorrowed<'a> {
+    name: &'a str,
+    #[bincode(skip)]
+    other: &'a str,
+    #[bincode(default = "skipped_default")]
+    handle: u32,
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_skip_fields() {
+    let config = bincode::config::standard();
+
+    let start = SkipStruct {
+        a: 5,
+        cache: 1000,
+        handle: 1,
+        b: 6,
+    };
+    let bytes = bincode::encode_to_vec(&start, config).unwrap();
+    assert_eq!(bytes, [5, 6]);
+    let (decoded, len): (SkipStruct, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(len, 2);
+    assert_eq!(
+        decoded,
+        SkipStruct {
+            a: 5,
+            cache: 0,
+            handle: 42,
+            b: 6,
+        }
+    );
+    assert_eq!(bincode::max_encoded_size::<SkipStruct, _>(config), 5 + 1);
+
+    let bytes = bincode::encode_to_vec(SkipTupleStruct(5, 1000, 1), config).unwrap();
+    assert_eq!(bytes, [5]);
+    let (decoded, _): (SkipTupleStruct, usize) =
+        bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, SkipTupleStruct(5, 0, 42));
+
+    let bytes = bincode::encode_to_vec(SkipEnum::Struct { cache: 1000, a: 5 }, config).unwrap();
+    assert_eq!(bytes, [0, 5]);
+    let (decoded, _): (SkipEnum, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, SkipEnum::Struct { cache: 0, a: 5 });
+
+    let bytes = bincode::encode_to_vec(SkipEnum::Tuple(1, 5), config).unwrap();
+    assert_eq!(bytes, [1, 5]);
+    let (decoded, _): (SkipEnum, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, SkipEnum::Tuple(42, 5));
+    assert_eq!(bincode::max_encoded_size::<SkipEnum, _>(config), 5 + 1);
+
+    let bytes = bincode::encode_to_vec(
+        SkipBorrowed {
+            name: "abc",
+            other: "def",
+            handle: 1,
+        },
+        config,
+    )
+    .unwrap();
+    assert_eq!(bytes, [3, b'a', b'b', b'c']);
+    let (decoded, _): (SkipBorrowed, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(
+        decoded,
+        SkipBorrowed {
+            name: "abc",
+            other: "",
+            handle: 42,
+        }
+    );
+}
+
+/// A type that does not implement `Encode` or `Decode`
+#[derive(PartialEq, Debug)]
+pub struct ForeignType {
+    x: u16,
+}
+
+mod foreign_type {
+    use super::ForeignType;
+    use bincode::{
+        de::Decoder,
+        enc::Encoder,
+        error::{DecodeError, EncodeError},
+        Decode, Encode,
+    };
+
+    pub fn encode<E: Encoder>(value: &ForeignType, encoder: &mut E) -> Result<(), EncodeError> {
+        value.x.encode(encoder)
+    }
+
+    pub fn decode<D: Decoder>(decoder: &mut D) -> Result<ForeignType, DecodeError> {
+        Ok(ForeignType {
+            x: u16::decode(decoder)?,
+        })
+    }
+}
+
+fn encode_doubled<E: bincode::enc::Encoder>(
+    value: &u32,
+    encoder: &mut E,
+) -> Result<(), bincode::error::EncodeError> {
+    bincode::Encode::encode(&(value * 2), encoder)
+}
+
+fn decode_halved<D: bincode::de::Decoder>(
+    decoder: &mut D,
+) -> Result<u32, bincode::error::DecodeError> {
+    let value: u32 = bincode::Decode::decode(decoder)?;
+    Ok(value / 2)
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+pub struct WithStruct {
+    #[bincode(with = "foreign_type")]
+    foreign: ForeignType,
+    #[bincode(encode_with = "encode_doubled", decode_with = "decode_halved")]
+    doubled: u32,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+pub enum WithEnum {
+    Tuple(#[bincode(with = "foreign_type")] ForeignType),
+    Struct {
+        #[bincode(encode_with = "encode_doubled")]
+        doubled: u32,
+        #[bincode(decode_with = "decode_halved")]
+        halved: u32,
+    },
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+pub struct WithBorrowed<'a> {
+    name: &'a str,
+    #[bincode(with = "foreign_type")]
+    foreign: ForeignType,
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_with_fields() {
+    let config = bincode::config::standard();
+
+    let start = WithStruct {
+        foreign: ForeignType { x: 1000 },
+        doubled: 5,
+    };
+    let bytes = bincode::encode_to_vec(&start, config).unwrap();
+    assert_eq!(bytes, [251, 232, 3, 10]);
+    let (decoded, _): (WithStruct, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, start);
+
+    let start = WithEnum::Tuple(ForeignType { x: 5 });
+    let bytes = bincode::encode_to_vec(&start, config).unwrap();
+    assert_eq!(bytes, [0, 5]);
+    let (decoded, _): (WithEnum, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, start);
+
+    let bytes = bincode::encode_to_vec(
+        WithEnum::Struct {
+            doubled: 5,
+            halved: 20,
+        },
+        config,
+    )
+    .unwrap();
+    assert_eq!(bytes, [1, 10, 20]);
+    let (decoded, _): (WithEnum, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(
+        decoded,
+        WithEnum::Struct {
+            doubled: 10,
+            halved: 10,
+        }
+    );
+
+    let start = WithBorrowed {
+        name: "abc",
+        foreign: ForeignType { x: 5 },
+    };
+    let bytes = bincode::encode_to_vec(&start, config).unwrap();
+    let (decoded, _): (WithBorrowed, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, start);
+}
+
+#[derive(bincode::Encode, bincode::Decode, bincode::MaxSize, Clone, Copy, PartialEq, Debug)]
+#[bincode(into = "u64", from = "u64")]
+pub struct Celsius(i32);
+
+impl From<Celsius> for u64 {
+    fn from(celsius: Celsius) -> u64 {
+        (celsius.0 + 273) as u64
+    }
+}
+
+impl From<u64> for Celsius {
+    fn from(kelvin: u64) -> Celsius {
+        Celsius(kelvin as i32 - 273)
+    }
+}
+
+#[derive(bincode::Encode, bincode::Decode, Clone, Copy, PartialEq, Debug)]
+#[bincode(into = "u8", try_from = "u8")]
+pub enum Color {
+    Red,
+    Green,
+}
+
+impl From<Color> for u8 {
+    fn from(color: Color) -> u8 {
+        match color {
+            Color::Red => b'r',
+            Color::Green => b'g',
+        }
+    }
+}
+
+impl TryFrom<u8> for Color {
+    type Error = &'static str;
+
+    fn try_from(value: u8) -> Result<Color, Self::Error> {
+        match value {
+            b'r' => Ok(Color::Red),
+            b'g' => Ok(Color::Green),
+            _ => Err("unknown color"),
+        }
+    }
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, Clone, PartialEq, Debug)]
+#[bincode(into = "&'a str", from = "&'a str")]
+pub struct Name<'a> {
+    name: &'a str,
+}
+
+impl<'a> From<Name<'a>> for &'a str {
+    fn from(name: Name<'a>) -> &'a str {
+        name.name
+    }
+}
+
+impl<'a> From<&'a str> for Name<'a> {
+    fn from(name: &'a str) -> Name<'a> {
+        Name { name }
+    }
+}
+
+#[test]
+fn test_container_conversion() {
+    use bincode::MaxSize;
+
+    let config = bincode::config::standard();
+    let mut buffer = [0u8; 16];
+
+    let len = bincode::encode_into_slice(Celsius(20), &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], &[251, 37, 1]);
+    let (decoded, _): (Celsius, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, Celsius(20));
+    assert_eq!(Celsius::MAX_SIZE, u64::MAX_SIZE);
+
+    let len = bincode::encode_into_slice(Color::Green, &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], b"g");
+    let (decoded, _): (Color, usize) = bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, Color::Green);
+
+    let err = bincode::decode_from_slice::<Color, _>(b"x", config).unwrap_err();
+    #[cfg(feature = "alloc")]
+    assert_eq!(
+        err,
+        bincode::error::DecodeError::ConversionFailedString {
+            type_name: core::any::type_name::<Color>(),
+            message: "unknown color".into(),
+        }
+    );
+    #[cfg(not(feature = "alloc"))]
+    assert_eq!(
+        err,
+        bincode::error::DecodeError::ConversionFailed {
+            type_name: core::any::type_name::<Color>(),
+        }
+    );
+
+    let start = Name { name: "abc" };
+    let len = bincode::encode_into_slice(start.clone(), &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], &[3, b'a', b'b', b'c']);
+    let (decoded, _): (Name, usize) = bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, start);
+}
+
+#[derive(PartialEq, Debug)]
+pub struct NotEncodable;
+
+#[derive(bincode::Encode, bincode::Decode, bincode::MaxSize, PartialEq, Debug)]
+#[bincode(bound = "")]
+pub struct Marker<T> {
+    id: u32,
+    marker: core::marker::PhantomData<T>,
+}
+
+pub trait Entity {
+    type Id;
+}
+
+impl Entity for NotEncodable {
+    type Id = u32;
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+pub struct Reference<T: Entity> {
+    #[bincode(
+        encode_bound = "T::Id: bincode::Encode",
+        decode_bound = "T::Id: bincode::Decode"
+    )]
+    id: T::Id,
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+#[bincode(
+    encode_bound = "T::Id: bincode::Encode",
+    borrow_decode_bound = "T::Id: bincode::BorrowDecode<'__de>"
+)]
+pub struct NamedReference<'a, T: Entity> {
+    name: &'a str,
+    id: T::Id,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(bound = "U: bincode::Encode + bincode::Decode")]
+pub enum Tagged<T, U> {
+    Marker(core::marker::PhantomData<T>),
+    Value(U),
+}
+
+#[test]
+fn test_generic_bounds() {
+    use bincode::MaxSize;
+
+    let config = bincode::config::standard();
+    let mut buffer = [0u8; 16];
+
+    let start = Marker::<NotEncodable> {
+        id: 5,
+        marker: core::marker::PhantomData,
+    };
+    let len = bincode::encode_into_slice(&start, &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], &[5]);
+    let (decoded, _): (Marker<NotEncodable>, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, start);
+    assert_eq!(Marker::<NotEncodable>::MAX_SIZE, u32::MAX_SIZE);
+
+    let start = Reference::<NotEncodable> { id: 10 };
+    let len = bincode::encode_into_slice(&start, &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], &[10]);
+    let (decoded, _): (Reference<NotEncodable>, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, start);
+
+    let start = NamedReference::<NotEncodable> { name: "a", id: 10 };
+    let len = bincode::encode_into_slice(&start, &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], &[1, b'a', 10]);
+    let (decoded, _): (NamedReference<NotEncodable>, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, start);
+
+    let start = Tagged::<NotEncodable, u8>::Value(3);
+    let len = bincode::encode_into_slice(&start, &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], &[1, 3]);
+    let (decoded, _): (Tagged<NotEncodable, u8>, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, start);
+}
+
+mod wire {
+    pub use ::bincode as codec;
+
+    // Without `#[bincode(crate = "...")]`, the generated `bincode::` paths would refer to this module
+    #[allow(dead_code)]
+    mod bincode {}
+
+    #[derive(::bincode::Encode, ::bincode::Decode, ::bincode::MaxSize, PartialEq, Debug)]
+    #[bincode(crate = "crate::wire::codec")]
+    pub struct Packet {
+        pub id: u32,
+        pub kind: Kind,
+    }
+
+    #[derive(::bincode::Encode, ::bincode::Decode, ::bincode::MaxSize, PartialEq, Debug)]
+    #[bincode(crate = "::bincode")]
+    pub enum Kind {
+        Ping,
+        Pong(u8),
+    }
+
+    #[derive(::bincode::Encode, ::bincode::BorrowDecode, PartialEq, Debug)]
+    #[bincode(crate = "crate::wire::codec")]
+    pub struct Text<'a> {
+        pub text: &'a str,
+    }
+}
+
+#[test]
+fn test_crate_path() {
+    use wire::{Kind, Packet, Text};
+
+    let config = bincode::config::standard();
+    let mut buffer = [0u8; 16];
+
+    let start = Packet {
+        id: 3,
+        kind: Kind::Pong(4),
+    };
+    let len = bincode::encode_into_slice(&start, &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], &[3, 1, 4]);
+    let (decoded, _): (Packet, usize) = bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, start);
+
+    let start = Text { text: "hi" };
+    let len = bincode::encode_into_slice(&start, &mut buffer, config).unwrap();
+    let (decoded, _): (Text, usize) = bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, start);
+}
+
+#[derive(bincode::Encode, bincode::Decode, bincode::MaxSize, PartialEq, Debug)]
+#[bincode(tag_type = "u8")]
+pub enum SmallTag {
+    Idle,
+    Reading(u16),
+    Error { code: u8 },
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(tag_type = "u16")]
+pub enum ExplicitTag {
+    A = 1,
+    B,
+    C = 500,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(tag_type = "u64")]
+pub enum WideTag {
+    A,
+    B(u8),
+}
+
+#[test]
+fn test_enum_tag_type() {
+    use bincode::error::{AllowedEnumVariants, DecodeError};
+
+    let config = bincode::config::legacy();
+    let mut buffer = [0u8; 16];
+
+    let len = bincode::encode_into_slice(SmallTag::Reading(5), &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], &[1, 5, 0]);
+    let (decoded, _): (SmallTag, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, SmallTag::Reading(5));
+    assert_eq!(
+        bincode::decode_from_slice::<SmallTag, _>(&[3], config).unwrap_err(),
+        DecodeError::UnexpectedVariant {
+            type_name: "SmallTag",
+            allowed: AllowedEnumVariants::Range { min: 0, max: 2 },
+            found: 3,
+        }
+    );
+    assert_eq!(bincode::max_encoded_size::<SmallTag, _>(config), 1 + 2);
+
+    let len = bincode::encode_into_slice(ExplicitTag::B, &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], &[2, 0]);
+    let len = bincode::encode_into_slice(ExplicitTag::C, &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], &500u16.to_le_bytes());
+    let (decoded, _): (ExplicitTag, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, ExplicitTag::C);
+    assert_eq!(
+        bincode::decode_from_slice::<ExplicitTag, _>(&[3, 0], config).unwrap_err(),
+        DecodeError::UnexpectedVariant {
+            type_name: "ExplicitTag",
+            allowed: AllowedEnumVariants::Allowed(&[1, 2, 500]),
+            found: 3,
+        }
+    );
+
+    let len = bincode::encode_into_slice(WideTag::B(7), &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], &[1, 0, 0, 0, 0, 0, 0, 0, 7]);
+    let (decoded, _): (WideTag, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, WideTag::B(7));
+    assert_eq!(
+        bincode::decode_from_slice::<WideTag, _>(&u64::MAX.to_le_bytes(), config).unwrap_err(),
+        DecodeError::UnexpectedVariant {
+            type_name: "WideTag",
+            allowed: AllowedEnumVariants::Range { min: 0, max: 1 },
+            found: u32::MAX,
+        }
+    );
+}
+
+#[derive(bincode::Encode, bincode::Decode, bincode::MaxSize, PartialEq, Debug)]
+#[bincode(default_trailing)]
+pub struct SettingsV1 {
+    volume: u8,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(default_trailing)]
+pub struct SettingsV2 {
+    volume: u8,
+    muted: bool,
+    brightness: u32,
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+#[bincode(default_trailing)]
+pub struct ProfileV2<'a> {
+    name: &'a str,
+    bio: Option<&'a str>,
+}
+
+#[test]
+fn test_default_trailing() {
+    use bincode::MaxSize;
+
+    let config = bincode::config::standard();
+    let mut buffer = [0u8; 32];
+
+    // record length, followed by the fields
+    let len = bincode::encode_into_slice(SettingsV1 { volume: 50 }, &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], &[1, 50]);
+
+    // fields that are missing at the end of the record are set to their default value
+    let (decoded, read): (SettingsV2, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(read, len);
+    assert_eq!(
+        decoded,
+        SettingsV2 {
+            volume: 50,
+            muted: false,
+            brightness: 0,
+        }
+    );
+
+    // unknown bytes at the end of the record are skipped
+    let start = (
+        SettingsV2 {
+            volume: 10,
+            muted: true,
+            brightness: 300,
+        },
+        7u8,
+    );
+    let len = bincode::encode_into_slice(&start, &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], &[5, 10, 1, 251, 44, 1, 7]);
+    let (decoded, read): ((SettingsV1, u8), usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(read, len);
+    assert_eq!(decoded, (SettingsV1 { volume: 10 }, 7));
+    let (decoded, _): ((SettingsV2, u8), usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, start);
+
+    let len = bincode::encode_into_slice(
+        ProfileV2 {
+            name: "a",
+            bio: Some("b"),
+        },
+        &mut buffer,
+        config,
+    )
+    .unwrap();
+    assert_eq!(&buffer[..len], &[5, 1, b'a', 1, 1, b'b']);
+    let (decoded, _): (ProfileV2, usize) =
+        bincode::decode_from_slice(&[2, 1, b'a'], config).unwrap();
+    assert_eq!(
+        decoded,
+        ProfileV2 {
+            name: "a",
+            bio: None,
+        }
+    );
+
+    // a field that continues past the end of the record
+    assert_eq!(
+        bincode::decode_from_slice::<SettingsV2, _>(&[3, 1, 1, 251, 44, 1], config)
+            .unwrap_err()
+            .without_context(),
+        &bincode::error::DecodeError::RecordLengthExceeded { length: 3, read: 5 }
+    );
+
+    assert_eq!(SettingsV1::MAX_SIZE, u64::MAX_SIZE.add(u8::MAX_SIZE));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_default_trailing_nested() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static ENCODE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counted;
+
+    impl bincode::Encode for Counted {
+        fn encode<E: bincode::enc::Encoder>(
+            &self,
+            encoder: &mut E,
+        ) -> Result<(), bincode::error::EncodeError> {
+            ENCODE_COUNT.fetch_add(1, Ordering::Relaxed);
+            0u8.encode(encoder)
+        }
+    }
+
+    #[derive(bincode::Encode)]
+    #[bincode(default_trailing)]
+    struct Nested {
+        value: Counted,
+        child: Option<Box<Nested>>,
+    }
+
+    const DEPTH: usize = 32;
+    let mut nested = Nested {
+        value: Counted,
+        child: None,
+    };
+    for _ in 1..DEPTH {
+        nested = Nested {
+            value: Counted,
+            child: Some(Box::new(nested)),
+        };
+    }
+
+    let bytes = bincode::encode_to_vec(&nested, bincode::config::standard()).unwrap();
+    assert_eq!(
+        bytes.len(),
+        bincode::encoded_size(&nested, bincode::config::standard()).unwrap()
+    );
+    // Every level is sized once by every level above it, instead of 2^depth encodes
+    assert!(ENCODE_COUNT.load(Ordering::Relaxed) <= 2 * DEPTH * DEPTH);
+}
+
+#[derive(bincode::Encode, bincode::Decode, bincode::MaxSize, PartialEq, Debug)]
+#[bincode(tagged)]
+pub struct TaggedV1 {
+    #[bincode(tag = 1)]
+    id: u32,
+    #[bincode(tag = 2)]
+    volume: u8,
+}
+
+// `volume` was removed, `muted` was added and the fields were reordered
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(tagged)]
+pub struct TaggedV2 {
+    #[bincode(tag = 3)]
+    muted: bool,
+    #[bincode(tag = 1)]
+    id: u32,
+}
+
+#[derive(bincode::Encode, bincode::BorrowDecode, PartialEq, Debug)]
+#[bincode(tagged)]
+pub struct TaggedTuple<'a>(
+    #[bincode(tag = 10)] &'a str,
+    #[bincode(skip)] u8,
+    #[bincode(tag = 20)] Option<&'a str>,
+);
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(tagged)]
+pub struct TaggedEmpty {}
+
+#[test]
+fn test_tagged_fields() {
+    use bincode::MaxSize;
+
+    let config = bincode::config::standard();
+    let mut buffer = [0u8; 32];
+
+    let start = (TaggedV1 { id: 5, volume: 7 }, 9u8);
+    let len = bincode::encode_into_slice(&start, &mut buffer, config).unwrap();
+    // record length, followed by (tag, length, value) of every field
+    assert_eq!(&buffer[..len], &[6, 1, 1, 5, 2, 1, 7, 9]);
+    let (decoded, _): ((TaggedV1, u8), usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, start);
+
+    // unknown fields are skipped, missing fields are set to their default value
+    let (decoded, read): ((TaggedV2, u8), usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(read, len);
+    assert_eq!(
+        decoded,
+        (
+            TaggedV2 {
+                muted: false,
+                id: 5
+            },
+            9
+        )
+    );
+
+    let len = bincode::encode_into_slice(
+        TaggedV2 {
+            muted: true,
+            id: 300,
+        },
+        &mut buffer,
+        config,
+    )
+    .unwrap();
+    assert_eq!(&buffer[..len], &[8, 3, 1, 1, 1, 3, 251, 44, 1]);
+    let (decoded, _): (TaggedV1, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, TaggedV1 { id: 300, volume: 0 });
+
+    let start = TaggedTuple("a", 0, Some("b"));
+    let len = bincode::encode_into_slice(&start, &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], &[9, 10, 2, 1, b'a', 20, 3, 1, 1, b'b']);
+    let (decoded, _): (TaggedTuple, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, start);
+
+    let len = bincode::encode_into_slice(TaggedEmpty {}, &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], &[0]);
+    let (decoded, _): (TaggedEmpty, usize) =
+        bincode::decode_from_slice(&[3, 1, 1, 5], config).unwrap();
+    assert_eq!(decoded, TaggedEmpty {});
+
+    // a value that is longer than its entry
+    assert_eq!(
+        bincode::decode_from_slice::<TaggedV1, _>(&[4, 1, 1, 251, 44, 1], config)
+            .unwrap_err()
+            .without_context(),
+        &bincode::error::DecodeError::RecordLengthExceeded { length: 1, read: 3 }
+    );
+
+    assert_eq!(
+        TaggedV1::MAX_SIZE,
+        u64::MAX_SIZE
+            .add(u32::MAX_SIZE)
+            .add(u64::MAX_SIZE)
+            .add(u32::MAX_SIZE)
+            .add(u32::MAX_SIZE)
+            .add(u64::MAX_SIZE)
+            .add(u8::MAX_SIZE)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_tagged_nested() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static ENCODE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counted;
+
+    impl bincode::Encode for Counted {
+        fn encode<E: bincode::enc::Encoder>(
+            &self,
+            encoder: &mut E,
+        ) -> Result<(), bincode::error::EncodeError> {
+            ENCODE_COUNT.fetch_add(1, Ordering::Relaxed);
+            0u8.encode(encoder)
+        }
+    }
+
+    #[derive(bincode::Encode)]
+    #[bincode(tagged)]
+    struct Nested {
+        #[bincode(tag = 1)]
+        value: Counted,
+        #[bincode(tag = 2)]
+        child: Option<Box<Nested>>,
+    }
+
+    const DEPTH: usize = 32;
+    let mut nested = Nested {
+        value: Counted,
+        child: None,
+    };
+    for _ in 1..DEPTH {
+        nested = Nested {
+            value: Counted,
+            child: Some(Box::new(nested)),
+        };
+    }
+
+    let bytes = bincode::encode_to_vec(&nested, bincode::config::standard()).unwrap();
+    assert_eq!(
+        bytes.len(),
+        bincode::encoded_size(&nested, bincode::config::standard()).unwrap()
+    );
+    // Every level is sized once by every level above it, instead of 2^depth encodes
+    assert!(ENCODE_COUNT.load(Ordering::Relaxed) <= 2 * DEPTH * DEPTH);
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(version = 1)]
+pub struct AccountV1 {
+    name: u8,
+}
+
+#[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+#[bincode(version = 2, migrate_from = "AccountV1")]
+pub struct AccountV2 {
+    name: u8,
+    balance: u32,
+}
+
+impl From<AccountV1> for AccountV2 {
+    fn from(account: AccountV1) -> Self {
+        AccountV2 {
+            name: account.name,
+            balance: 100,
+        }
+    }
+}
+
+#[derive(bincode::Encode, bincode::Decode, bincode::MaxSize, PartialEq, Debug)]
+#[bincode(version = 4, migrate_from = "AccountV2")]
+pub struct Account {
+    name: u8,
+    balance: u64,
+    frozen: bool,
+}
+
+impl From<AccountV2> for Account {
+    fn from(account: AccountV2) -> Self {
+        Account {
+            name: account.name,
+            balance: u64::from(account.balance),
+            frozen: false,
+        }
+    }
+}
+
+#[derive(bincode::Encode, bincode::Decode, bincode::MaxSize, PartialEq, Debug)]
+#[bincode(version = 3)]
+pub enum VersionedEnum<T> {
+    A,
+    B(T),
+}
+
+#[test]
+fn test_versioned() {
+    use bincode::MaxSize;
+
+    let config = bincode::config::standard();
+    let mut buffer = [0u8; 32];
+
+    let len = bincode::encode_into_slice(AccountV1 { name: 5 }, &mut buffer, config).unwrap();
+    // version, followed by the fields
+    assert_eq!(&buffer[..len], &[1, 5]);
+    let (decoded, _): (AccountV1, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, AccountV1 { name: 5 });
+
+    // version 1 is migrated through AccountV2
+    let (decoded, read): (Account, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(read, len);
+    assert_eq!(
+        decoded,
+        Account {
+            name: 5,
+            balance: 100,
+            frozen: false
+        }
+    );
+
+    let len = bincode::encode_into_slice(
+        AccountV2 {
+            name: 5,
+            balance: 300,
+        },
+        &mut buffer,
+        config,
+    )
+    .unwrap();
+    assert_eq!(&buffer[..len], &[2, 5, 251, 44, 1]);
+    let (decoded, _): (Account, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(
+        decoded,
+        Account {
+            name: 5,
+            balance: 300,
+            frozen: false
+        }
+    );
+
+    let start = Account {
+        name: 5,
+        balance: 7,
+        frozen: true,
+    };
+    let len = bincode::encode_into_slice(&start, &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], &[4, 5, 7, 1]);
+    let (decoded, _): (Account, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, start);
+
+    // a newer version can not be decoded, and there is no migration to an older type
+    assert_eq!(
+        bincode::decode_from_slice::<AccountV2, _>(&buffer[..len], config).unwrap_err(),
+        bincode::error::DecodeError::UnknownVersion {
+            type_name: "derive::AccountV2",
+            found: 4,
+            current: 2,
+        }
+    );
+    // version 3 was never released
+    assert_eq!(
+        bincode::decode_from_slice::<Account, _>(&[3, 5, 7, 1], config).unwrap_err(),
+        bincode::error::DecodeError::UnknownVersion {
+            type_name: "derive::AccountV2",
+            found: 3,
+            current: 2,
+        }
+    );
+    assert_eq!(
+        bincode::decode_from_slice::<AccountV1, _>(&[0, 5], config).unwrap_err(),
+        bincode::error::DecodeError::UnknownVersion {
+            type_name: "derive::AccountV1",
+            found: 0,
+            current: 1,
+        }
+    );
+
+    let start = VersionedEnum::B(9u8);
+    let len = bincode::encode_into_slice(&start, &mut buffer, config).unwrap();
+    assert_eq!(&buffer[..len], &[3, 1, 9]);
+    let (decoded, _): (VersionedEnum<u8>, usize) =
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
+    assert_eq!(decoded, start);
+
+    assert_eq!(
+        Account::MAX_SIZE,
+        u32::MAX_SIZE
+            .add(u8::MAX_SIZE)
+            .add(u64::MAX_SIZE)
+            .add(bool::MAX_SIZE)
+    );
+    assert_eq!(
+        VersionedEnum::<u8>::MAX_SIZE,
+        u32::MAX_SIZE.add(u8::MAX_SIZE).add(u32::MAX_SIZE)
+    );
+}
+
+#[test]
+fn test_default_trailing_custom_reader() {
+    use bincode::de::{
+        read::{OffsetReader, Reader},
+        Decode, DecoderImpl,
+    };
+    use bincode::error::DecodeError;
+
+    /// A reader that does not keep track of its offset
+    struct CustomReader<'a>(&'a [u8]);
+
+    impl Reader for CustomReader<'_> {
+        fn read(&mut self, bytes: &mut [u8]) -> Result<(), DecodeError> {
+            if bytes.len() > self.0.len() {
+                return Err(DecodeError::UnexpectedEnd);
+            }
+            let (read, rest) = self.0.split_at(bytes.len());
+            bytes.copy_from_slice(read);
+            self.0 = rest;
+            Ok(())
+        }
+    }
+
+    let config = bincode::config::standard();
+    let mut buffer = [0u8; 32];
+    let start = SettingsV2 {
+        volume: 10,
+        muted: true,
+        brightness: 300,
+    };
+    let len = bincode::encode_into_slice(&start, &mut buffer, config).unwrap();
+
+    // `decode_from_reader` keeps track of the offset itself
+    let decoded: SettingsV1 =
+        bincode::decode_from_reader(CustomReader(&buffer[..len]), config).unwrap();
+    assert_eq!(decoded, SettingsV1 { volume: 10 });
+
+    let mut decoder = DecoderImpl::new(CustomReader(&buffer[..len]), config);
+    assert_eq!(
+        SettingsV1::decode(&mut decoder).unwrap_err(),
+        DecodeError::Other(
+            "the reader does not keep track of its offset, wrap it in an `OffsetReader`"
+        )
+    );
+
+    let mut reader = OffsetReader::new(CustomReader(&buffer[..len]));
+    let mut decoder = DecoderImpl::new(&mut reader, config);
+    let decoded = SettingsV1::decode(&mut decoder).unwrap();
+    assert_eq!(decoded, SettingsV1 { volume: 10 });
+    assert_eq!(reader.offset(), len);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_default_trailing_buf_reader() {
+    use bincode::error::DecodeError;
+    let config = bincode::config::standard();
+    let mut buffer = [0u8; 32];
+    let start = SettingsV2 {
+        volume: 10,
+        muted: true,
+        brightness: 300,
+    };
+    let len = bincode::encode_into_slice(&start, &mut buffer, config).unwrap();
+
+    let reader = std::io::BufReader::new(&buffer[..len]);
+    let decoded: SettingsV1 = bincode::decode_from_reader(reader, config).unwrap();
+    assert_eq!(decoded, SettingsV1 { volume: 10 });
+
+    let start = TaggedV1 { id: 5, volume: 10 };
+    let len = bincode::encode_into_slice(&start, &mut buffer, config).unwrap();
+
+    let reader = std::io::BufReader::new(&buffer[..len]);
+    let decoded: TaggedV2 = bincode::decode_from_reader(reader, config).unwrap();
+    assert_eq!(
+        decoded,
+        TaggedV2 {
+            muted: false,
+            id: 5
+        }
+    );
+
+    // The offsets of errors are correct as well
+    let reader = std::io::BufReader::new(&buffer[..len - 1]);
+    let result: Result<TaggedV1, _> = bincode::decode_from_reader(reader, config);
+    match result.unwrap_err() {
+        DecodeError::WithContext { offset, .. } => assert_eq!(offset, len - 1),
+        e => panic!("Expected WithContext, got {:?}", e),
+    }
+}
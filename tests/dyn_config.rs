@@ -0,0 +1,137 @@
+#![cfg(feature = "std")]
+
+use bincode::config::{DynConfig, Endian, IntEncoding};
+use bincode::error::DecodeError;
+
+#[test]
+fn test_dyn_config_options() {
+    let value = (300u32, -2i64, [1u16, 2], 1.0f32);
+    let cases: [(DynConfig, &[u8]); 4] = [
+        (DynConfig::standard(), &[251, 44, 1, 3, 1, 2, 0, 0, 128, 63]),
+        (
+            DynConfig {
+                endian: Endian::Big,
+                ..DynConfig::standard()
+            },
+            &[251, 1, 44, 3, 1, 2, 63, 128, 0, 0],
+        ),
+        (
+            DynConfig {
+                int_encoding: IntEncoding::Leb128,
+                skip_fixed_array_length: false,
+                ..DynConfig::standard()
+            },
+            &[172, 2, 3, 2, 1, 2, 0, 0, 128, 63],
+        ),
+        (
+            DynConfig::legacy(),
+            &[
+                44, 1, 0, 0, 254, 255, 255, 255, 255, 255, 255, 255, 2, 0, 0, 0, 0, 0, 0, 0, 1, 0,
+                2, 0, 0, 0, 128, 63,
+            ],
+        ),
+    ];
+    for (config, expected) in cases {
+        let bytes = bincode::encode_to_vec(value, config).unwrap();
+        assert_eq!(bytes, expected, "{:?}", config);
+        assert_eq!(
+            bincode::decode_from_slice(&bytes, config).unwrap(),
+            (value, bytes.len())
+        );
+    }
+
+    assert_eq!(DynConfig::default(), DynConfig::standard());
+    assert_eq!(
+        DynConfig::from_config(bincode::config::legacy()),
+        DynConfig::legacy()
+    );
+    assert_eq!(
+        DynConfig::from_config(
+            bincode::config::standard()
+                .with_leb128_int_encoding()
+                .with_limit::<100>()
+                .with_max_container_len::<10>()
+                .with_max_depth::<5>()
+        ),
+        DynConfig {
+            int_encoding: IntEncoding::Leb128,
+            limit: Some(100),
+            max_container_len: Some(10),
+            max_depth: Some(5),
+            ..DynConfig::standard()
+        }
+    );
+}
+
+#[test]
+fn test_dyn_config_limits() {
+    let bytes = bincode::encode_to_vec(vec![0u8; 20], DynConfig::standard()).unwrap();
+
+    let config = DynConfig {
+        limit: Some(10),
+        ..DynConfig::standard()
+    };
+    assert_eq!(
+        bincode::decode_from_slice::<Vec<u8>, _>(&bytes, config).unwrap_err(),
+        DecodeError::LimitExceeded
+    );
+
+    let config = DynConfig {
+        max_container_len: Some(10),
+        ..DynConfig::standard()
+    };
+    assert_eq!(
+        bincode::decode_from_slice::<Vec<u8>, _>(&bytes, config).unwrap_err(),
+        DecodeError::ContainerLengthExceeded {
+            found: 20,
+            allowed: 10
+        }
+    );
+
+    let config = DynConfig {
+        limit: Some(100),
+        max_container_len: Some(20),
+        ..DynConfig::standard()
+    };
+    let (decoded, _): (Vec<u8>, usize) = bincode::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, vec![0u8; 20]);
+}
+
+#[test]
+fn test_dyn_config_std() {
+    let config = DynConfig {
+        endian: Endian::Big,
+        int_encoding: IntEncoding::Fixed,
+        ..DynConfig::standard()
+    };
+    let mut bytes = Vec::new();
+    let len = bincode::encode_into_std_write("hello", &mut bytes, config).unwrap();
+    assert_eq!(len, 13);
+    assert_eq!(&bytes[..8], &[0, 0, 0, 0, 0, 0, 0, 5]);
+
+    let decoded: String = bincode::decode_from_std_read(&mut &bytes[..], config).unwrap();
+    assert_eq!(decoded, "hello");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_dyn_config_serde() {
+    let config = DynConfig {
+        int_encoding: IntEncoding::Leb128,
+        ..DynConfig::standard()
+    };
+    let value = vec![1000i32, -1];
+    let bytes = bincode::serde::encode_to_vec(&value, config).unwrap();
+    assert_eq!(
+        bytes,
+        bincode::serde::encode_to_vec(
+            &value,
+            bincode::config::standard().with_leb128_int_encoding()
+        )
+        .unwrap()
+    );
+    let (decoded, len): (Vec<i32>, usize) =
+        bincode::serde::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, value);
+    assert_eq!(len, bytes.len());
+}
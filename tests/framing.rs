@@ -0,0 +1,157 @@
+#![cfg(feature = "std")]
+
+use bincode::framing::{FrameReader, FrameWriter};
+use bincode::{config, error::DecodeError};
+
+#[test]
+fn test_frames_roundtrip() {
+    let config = config::standard();
+    let mut stream = Vec::new();
+    let mut writer = FrameWriter::from_std_write(&mut stream, config);
+    assert_eq!(writer.write_frame(5u32).unwrap(), 2);
+    assert_eq!(writer.write_frame("Hello world").unwrap(), 13);
+    assert_eq!(writer.write_frame(vec![1u64, 2, 3]).unwrap(), 5);
+    assert_eq!(stream.len(), 20);
+
+    let mut reader = FrameReader::from_std_read(&stream[..], config);
+    assert_eq!(reader.read_frame::<u32>().unwrap(), 5);
+    assert_eq!(reader.read_frame::<String>().unwrap(), "Hello world");
+    assert_eq!(reader.read_frame::<Vec<u64>>().unwrap(), vec![1, 2, 3]);
+    assert_eq!(
+        reader.read_frame::<u32>().unwrap_err(),
+        DecodeError::UnexpectedEnd
+    );
+}
+
+#[test]
+fn test_frames_fixed_int_encoding() {
+    let config = config::legacy();
+    let mut stream = Vec::new();
+    let mut writer = FrameWriter::from_std_write(&mut stream, config);
+    assert_eq!(writer.write_frame(5u32).unwrap(), 12);
+    assert_eq!(stream, [4, 0, 0, 0, 0, 0, 0, 0, 5, 0, 0, 0]);
+
+    let mut reader = FrameReader::new(bincode::de::read::SliceReader::new(&stream), config);
+    assert_eq!(reader.read_frame::<u32>().unwrap(), 5);
+}
+
+#[test]
+fn test_frames_skip_unread_bytes() {
+    let config = config::standard();
+    let mut stream = Vec::new();
+    let mut writer = FrameWriter::from_std_write(&mut stream, config);
+    writer.write_frame((1u8, 2u8, 3u8)).unwrap();
+    writer.write_frame(4u8).unwrap();
+
+    let mut reader = FrameReader::from_std_read(&stream[..], config);
+    assert_eq!(reader.read_frame::<u8>().unwrap(), 1);
+    assert_eq!(reader.read_frame::<u8>().unwrap(), 4);
+}
+
+#[test]
+fn test_frames_skip_invalid_frame() {
+    let config = config::standard();
+    let mut stream = Vec::new();
+    let mut writer = FrameWriter::from_std_write(&mut stream, config);
+    writer.write_frame((5u8, 6u8)).unwrap();
+    writer.write_frame(true).unwrap();
+
+    // The first frame can not be decoded, but the next frame should still be readable
+    let mut reader = FrameReader::from_std_read(&stream[..], config);
+    assert_eq!(
+        reader.read_frame::<bool>().unwrap_err(),
+        DecodeError::InvalidBooleanValue(5)
+    );
+    assert!(reader.read_frame::<bool>().unwrap());
+}
+
+#[test]
+fn test_frames_can_not_read_past_frame() {
+    let config = config::standard();
+    let mut stream = Vec::new();
+    let mut writer = FrameWriter::from_std_write(&mut stream, config);
+    writer.write_frame(1u8).unwrap();
+    writer.write_frame(2u8).unwrap();
+
+    let mut reader = FrameReader::from_std_read(&stream[..], config);
+    assert_eq!(
+        reader.read_frame::<(u8, u8)>().unwrap_err(),
+        DecodeError::UnexpectedEnd
+    );
+}
+
+#[test]
+fn test_frames_limit() {
+    let mut stream = Vec::new();
+    let mut writer = FrameWriter::from_std_write(&mut stream, config::standard());
+    writer.write_frame(vec![0u8; 100]).unwrap();
+
+    let mut reader =
+        FrameReader::from_std_read(&stream[..], config::standard().with_limit::<100>());
+    assert_eq!(
+        reader.read_frame::<Vec<u8>>().unwrap_err(),
+        DecodeError::LimitExceeded
+    );
+
+    let mut reader =
+        FrameReader::from_std_read(&stream[..], config::standard().with_limit::<1024>());
+    assert_eq!(reader.read_frame::<Vec<u8>>().unwrap(), vec![0u8; 100]);
+}
+
+#[cfg(feature = "tokio_util")]
+mod codec {
+    use bincode::{config, framing::Codec};
+    use tokio_util_incl::{
+        bytes::BytesMut,
+        codec::{Decoder, Encoder},
+    };
+
+    #[test]
+    fn test_codec_roundtrip() {
+        let mut codec = Codec::<(u32, String), _>::new(config::standard());
+        let mut buffer = BytesMut::new();
+        codec.encode((5u32, "Hello"), &mut buffer).unwrap();
+        codec.encode((10u32, "world"), &mut buffer).unwrap();
+
+        assert_eq!(
+            codec.decode(&mut buffer).unwrap(),
+            Some((5, String::from("Hello")))
+        );
+        assert_eq!(
+            codec.decode(&mut buffer).unwrap(),
+            Some((10, String::from("world")))
+        );
+        assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+    }
+
+    #[test]
+    fn test_codec_partial_frames() {
+        let mut codec = Codec::<Vec<u64>, _>::new(config::standard());
+        let mut encoded = BytesMut::new();
+        codec.encode(vec![1u64, 2, 3, 1000], &mut encoded).unwrap();
+
+        let mut buffer = BytesMut::new();
+        for byte in &encoded[..encoded.len() - 1] {
+            buffer.extend_from_slice(&[*byte]);
+            assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+        }
+        buffer.extend_from_slice(&encoded[encoded.len() - 1..]);
+        assert_eq!(
+            codec.decode(&mut buffer).unwrap(),
+            Some(vec![1, 2, 3, 1000])
+        );
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_codec_limit() {
+        let mut buffer = BytesMut::new();
+        Codec::<(), _>::new(config::standard())
+            .encode(vec![0u8; 100], &mut buffer)
+            .unwrap();
+
+        let mut codec = Codec::<Vec<u8>, _>::new(config::standard().with_limit::<10>());
+        let err = codec.decode(&mut buffer).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
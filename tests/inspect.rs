@@ -0,0 +1,179 @@
+#![cfg(all(feature = "cli", feature = "derive"))]
+
+use std::path::Path;
+use std::process::Command;
+
+#[derive(bincode::Encode, bincode::Schema)]
+struct Reading {
+    sensor: String,
+    values: Vec<u16>,
+    status: Status,
+}
+
+#[derive(bincode::Encode, bincode::Schema)]
+enum Status {
+    Ok,
+    Failed { code: i32 },
+}
+
+fn readings() -> [Reading; 2] {
+    [
+        Reading {
+            sensor: "a\"b".to_string(),
+            values: vec![1, 1000],
+            status: Status::Ok,
+        },
+        Reading {
+            sensor: String::new(),
+            values: vec![],
+            status: Status::Failed { code: -2 },
+        },
+    ]
+}
+
+/// Write the schema of `Reading` and the readings encoded with `config` into `dir`
+fn write_files<C: bincode::config::Config>(dir: &Path, config: C) {
+    let schema = bincode::schema_of::<Reading, _>(config);
+    let schema = bincode::encode_to_vec(schema, bincode::config::standard()).unwrap();
+    std::fs::write(dir.join("reading.schema"), schema).unwrap();
+
+    let mut data = Vec::new();
+    for reading in readings() {
+        data.extend(bincode::encode_to_vec(reading, config).unwrap());
+    }
+    std::fs::write(dir.join("readings.bin"), data).unwrap();
+}
+
+fn inspect(dir: &Path, args: &[&str]) -> (bool, String, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_bincode-inspect"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+#[test]
+fn test_inspect_json() {
+    let dir = tempfile::tempdir().unwrap();
+    write_files(dir.path(), bincode::config::standard());
+
+    let (success, stdout, stderr) = inspect(dir.path(), &["reading.schema", "readings.bin"]);
+    assert!(success, "{}", stderr);
+    assert_eq!(
+        stdout,
+        r#"{
+  "sensor": "a\"b",
+  "values": [
+    1,
+    1000
+  ],
+  "status": "Ok"
+}
+{
+  "sensor": "",
+  "values": [],
+  "status": {
+    "Failed": {
+      "code": -2
+    }
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn test_inspect_hexdump() {
+    let dir = tempfile::tempdir().unwrap();
+    write_files(dir.path(), bincode::config::legacy().with_big_endian());
+
+    // The config of the schema is used by default
+    let (success, stdout, stderr) = inspect(
+        dir.path(),
+        &["--format", "hexdump", "reading.schema", "readings.bin"],
+    );
+    assert!(success, "{}", stderr);
+    assert_eq!(
+        stdout,
+        "\
+00000000  00 00 00 00 00 00 00 03 61 22 62                 $.sensor = \"a\\\"b\"
+0000000b  00 00 00 00 00 00 00 02                          $.values (length)
+00000013  00 01                                            $.values[0] = 1
+00000015  03 e8                                            $.values[1] = 1000
+00000017  00 00 00 00                                      $.status = \"Ok\"
+
+0000001b  00 00 00 00 00 00 00 00                          $.sensor = \"\"
+00000023  00 00 00 00 00 00 00 00                          $.values = []
+0000002b  00 00 00 01                                      $.status (variant)
+0000002f  ff ff ff fe                                      $.status::Failed.code = -2
+"
+    );
+}
+
+#[test]
+fn test_inspect_config() {
+    let dir = tempfile::tempdir().unwrap();
+    write_files(dir.path(), bincode::config::standard());
+
+    // A schema of the fixed int encoding can be used for data with the variable int encoding
+    let schema = bincode::schema_of::<Reading, _>(bincode::config::legacy());
+    let schema = bincode::encode_to_vec(schema, bincode::config::standard()).unwrap();
+    std::fs::write(dir.path().join("legacy.schema"), schema).unwrap();
+    let (success, expected, _) = inspect(dir.path(), &["reading.schema", "readings.bin"]);
+    assert!(success);
+    let (success, stdout, stderr) = inspect(
+        dir.path(),
+        &["--config", "standard", "legacy.schema", "readings.bin"],
+    );
+    assert!(success, "{}", stderr);
+    assert_eq!(stdout, expected);
+    let (success, stdout, _) = inspect(
+        dir.path(),
+        &[
+            "--int-encoding",
+            "variable",
+            "--endian",
+            "little",
+            "legacy.schema",
+            "readings.bin",
+        ],
+    );
+    assert!(success);
+    assert_eq!(stdout, expected);
+
+    // Decoding with the wrong config fails
+    let (success, _, stderr) = inspect(dir.path(), &["legacy.schema", "readings.bin"]);
+    assert!(!success);
+    assert!(
+        stderr.starts_with("error: could not decode `readings.bin` at byte 0:"),
+        "{}",
+        stderr
+    );
+
+    let (success, _, stderr) = inspect(dir.path(), &["--format", "xml", "reading.schema"]);
+    assert!(!success);
+    assert!(
+        stderr.starts_with("error: unknown format `xml`"),
+        "{}",
+        stderr
+    );
+
+    // The int encoding of the schema is used for LEB128 data
+    let leb128_dir = tempfile::tempdir().unwrap();
+    write_files(
+        leb128_dir.path(),
+        bincode::config::standard().with_leb128_int_encoding(),
+    );
+    let (success, stdout, stderr) = inspect(leb128_dir.path(), &["reading.schema", "readings.bin"]);
+    assert!(success, "{}", stderr);
+    assert_eq!(stdout, expected);
+
+    let (success, stdout, _) = inspect(dir.path(), &["--help"]);
+    assert!(success);
+    assert!(stdout.starts_with("Usage: bincode-inspect"));
+}
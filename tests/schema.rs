@@ -0,0 +1,258 @@
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use bincode::schema::{
+    Definition, Endian, EnumSchema, Field, IntEncoding, Schema, StructLayout, StructSchema, Type,
+    TypeSchema, Variant,
+};
+use std::collections::HashMap;
+
+fn standard_schema<T: Schema + ?Sized>() -> TypeSchema {
+    bincode::schema_of::<T, _>(bincode::config::standard())
+}
+
+fn definition<'a>(schema: &'a TypeSchema, ty: &Type) -> &'a Definition {
+    match ty {
+        Type::Named(name) => schema.definitions.get(name).unwrap(),
+        _ => panic!("Expected a named type, found {:?}", ty),
+    }
+}
+
+#[test]
+fn test_builtin_schemas() {
+    assert_eq!(standard_schema::<u32>().root, Type::U32);
+    assert_eq!(standard_schema::<usize>().root, Type::U64);
+    assert_eq!(standard_schema::<core::num::NonZeroI16>().root, Type::I16);
+    assert_eq!(standard_schema::<&str>().root, Type::String);
+    assert_eq!(
+        standard_schema::<Box<[u8]>>().root,
+        Type::Seq(Box::new(Type::U8))
+    );
+    assert_eq!(
+        standard_schema::<Vec<Option<char>>>().root,
+        Type::Seq(Box::new(Type::Option(Box::new(Type::Char))))
+    );
+    assert_eq!(
+        standard_schema::<HashMap<String, [u16; 3]>>().root,
+        Type::Map {
+            key: Box::new(Type::String),
+            value: Box::new(Type::Array {
+                item: Box::new(Type::U16),
+                len: 3
+            }),
+        }
+    );
+    assert_eq!(
+        standard_schema::<(bool, (), std::rc::Rc<f32>)>().root,
+        Type::Tuple(vec![Type::Bool, Type::Unit, Type::F32])
+    );
+
+    let result = standard_schema::<Result<u8, String>>();
+    assert_eq!(
+        definition(&result, &result.root),
+        &Definition::Enum(EnumSchema {
+            version: None,
+            tag_type: Type::U8,
+            variants: vec![
+                Variant::new("Ok", 0, vec![Field::new("0", Type::U8)]),
+                Variant::new("Err", 1, vec![Field::new("0", Type::String)]),
+            ],
+        })
+    );
+
+    // SystemTime is encoded as a Duration
+    let time = standard_schema::<std::time::SystemTime>();
+    assert_eq!(time.root, standard_schema::<core::time::Duration>().root);
+    assert_eq!(
+        definition(&time, &time.root),
+        &Definition::Struct(StructSchema {
+            version: None,
+            layout: StructLayout::Sequential,
+            fields: vec![
+                Field::new("secs", Type::U64),
+                Field::new("nanos", Type::U32)
+            ],
+        })
+    );
+}
+
+#[test]
+fn test_schema_config() {
+    let schema = bincode::schema_of::<u32, _>(bincode::config::standard());
+    assert_eq!(schema.endian, Endian::Little);
+    assert_eq!(schema.int_encoding, IntEncoding::Variable);
+    assert!(schema.skip_fixed_array_length);
+    assert!(schema.definitions.is_empty());
+
+    let schema = bincode::schema_of::<u32, _>(bincode::config::legacy().with_big_endian());
+    assert_eq!(schema.endian, Endian::Big);
+    assert_eq!(schema.int_encoding, IntEncoding::Fixed);
+    assert!(!schema.skip_fixed_array_length);
+}
+
+fn encode_as_u8<E: bincode::enc::Encoder>(
+    value: &u32,
+    encoder: &mut E,
+) -> Result<(), bincode::error::EncodeError> {
+    bincode::Encode::encode(&(*value as u8), encoder)
+}
+
+fn decode_from_u8<D: bincode::de::Decoder>(
+    decoder: &mut D,
+) -> Result<u32, bincode::error::DecodeError> {
+    <u8 as bincode::Decode>::decode(decoder).map(u32::from)
+}
+
+#[derive(bincode::Encode, bincode::Decode, bincode::Schema)]
+pub struct Message<T> {
+    id: u64,
+    #[bincode(skip)]
+    cached: bool,
+    #[bincode(encode_with = "encode_as_u8", decode_with = "decode_from_u8")]
+    small: u32,
+    payload: Option<T>,
+    kind: Kind,
+}
+
+#[derive(bincode::Encode, bincode::Decode, bincode::Schema)]
+#[bincode(tag_type = "u8")]
+#[repr(u8)]
+pub enum Kind {
+    Empty,
+    Point(i32, i32),
+    Named { name: String } = 5,
+    Next,
+}
+
+#[derive(bincode::Encode, bincode::Schema)]
+#[bincode(tagged, version = 3)]
+pub struct Tagged {
+    #[bincode(tag = 2)]
+    a: u8,
+    #[bincode(tag = 1)]
+    b: Vec<Tagged>,
+}
+
+#[derive(bincode::Encode, bincode::Schema)]
+#[bincode(default_trailing)]
+pub struct Trailing(u16);
+
+#[derive(bincode::Encode, bincode::Schema, Clone)]
+#[bincode(into = "u16", from = "u16")]
+pub struct Port(u16);
+
+impl From<Port> for u16 {
+    fn from(port: Port) -> u16 {
+        port.0
+    }
+}
+
+impl From<u16> for Port {
+    fn from(port: u16) -> Port {
+        Port(port)
+    }
+}
+
+#[test]
+fn test_derived_schema() {
+    let schema = standard_schema::<Message<&str>>();
+    assert_eq!(
+        schema.root,
+        Type::Named(core::any::type_name::<Message<&str>>().to_string())
+    );
+    assert_eq!(
+        definition(&schema, &schema.root),
+        &Definition::Struct(StructSchema {
+            version: None,
+            layout: StructLayout::Sequential,
+            fields: vec![
+                Field::new("id", Type::U64),
+                Field::new("small", Type::Opaque("u32".to_string())),
+                Field::new("payload", Type::Option(Box::new(Type::String))),
+                Field::new("kind", Type::Named("schema::Kind".to_string())),
+            ],
+        })
+    );
+    assert_eq!(
+        definition(&schema, &Type::Named("schema::Kind".to_string())),
+        &Definition::Enum(EnumSchema {
+            version: None,
+            tag_type: Type::U8,
+            variants: vec![
+                Variant::new("Empty", 0, vec![]),
+                Variant::new(
+                    "Point",
+                    1,
+                    vec![Field::new("0", Type::I32), Field::new("1", Type::I32)]
+                ),
+                Variant::new("Named", 5, vec![Field::new("name", Type::String)]),
+                Variant::new("Next", 6, vec![]),
+            ],
+        })
+    );
+
+    // Recursive types refer to their own definition
+    let schema = standard_schema::<Tagged>();
+    assert_eq!(schema.definitions.len(), 1);
+    assert_eq!(
+        definition(&schema, &schema.root),
+        &Definition::Struct(StructSchema {
+            version: Some(3),
+            layout: StructLayout::Tagged,
+            fields: vec![
+                Field {
+                    tag: Some(2),
+                    ..Field::new("a", Type::U8)
+                },
+                Field {
+                    tag: Some(1),
+                    ..Field::new("b", Type::Seq(Box::new(schema.root.clone())))
+                },
+            ],
+        })
+    );
+
+    let schema = standard_schema::<Trailing>();
+    assert_eq!(
+        definition(&schema, &schema.root),
+        &Definition::Struct(StructSchema {
+            version: None,
+            layout: StructLayout::DefaultTrailing,
+            fields: vec![Field::new("0", Type::U16)],
+        })
+    );
+
+    assert_eq!(standard_schema::<Port>().root, Type::U16);
+
+    // The fields in the schema are the fields that are encoded
+    let message = Message {
+        id: 1,
+        cached: true,
+        small: 7,
+        payload: Some(2u8),
+        kind: Kind::Next,
+    };
+    let encoded = bincode::encode_to_vec(&message, bincode::config::standard()).unwrap();
+    assert_eq!(encoded, &[1, 7, 1, 2, 6]);
+    let (decoded, _): (Message<u8>, usize) =
+        bincode::decode_from_slice(&encoded, bincode::config::standard()).unwrap();
+    assert!(!decoded.cached);
+}
+
+#[test]
+fn test_schema_encoding() {
+    let config = bincode::config::standard();
+    let start = standard_schema::<Message<(u8, [char; 2])>>();
+    let encoded = bincode::encode_to_vec(&start, config).unwrap();
+    let (decoded, len): (TypeSchema, usize) = bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(len, encoded.len());
+    assert_eq!(decoded, start);
+
+    // Deeply nested types count towards the max depth
+    let nested = standard_schema::<Option<Option<Option<u8>>>>();
+    let encoded = bincode::encode_to_vec(&nested, config).unwrap();
+    assert!(matches!(
+        bincode::decode_from_slice::<TypeSchema, _>(&encoded, config.with_max_depth::<3>())
+            .unwrap_err(),
+        bincode::error::DecodeError::DepthLimitExceeded
+    ));
+}
@@ -166,7 +166,7 @@ mod derive {
             assert_eq!(len, expected_len);
             let slice = &slice[..len];
             let (result, len): (T, usize) =
-                bincode::decode_from_slice(&slice, bincode::config::standard()).unwrap();
+                bincode::decode_from_slice(slice, bincode::config::standard()).unwrap();
 
             assert_eq!(start, result);
             assert_eq!(len, expected_len);
@@ -186,3 +186,70 @@ mod derive {
         );
     }
 }
+
+#[test]
+fn test_serde_max_container_len() {
+    let config = bincode::config::standard().with_max_container_len::<2>();
+    let bytes = bincode::serde::encode_to_vec(vec![1u32, 2, 3], config).unwrap();
+
+    assert_eq!(
+        bincode::serde::decode_from_slice::<Vec<u32>, _>(&bytes, config).unwrap_err(),
+        bincode::error::DecodeError::ContainerLengthExceeded {
+            found: 3,
+            allowed: 2
+        }
+    );
+    assert_eq!(
+        bincode::serde::decode_borrowed_from_slice::<Vec<u32>, _>(&bytes, config).unwrap_err(),
+        bincode::error::DecodeError::ContainerLengthExceeded {
+            found: 3,
+            allowed: 2
+        }
+    );
+
+    let config = config.with_no_max_container_len();
+    let decoded: (Vec<u32>, usize) = bincode::serde::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded.0, [1, 2, 3]);
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+#[serde(crate = "serde_incl")]
+pub enum SerdeTree {
+    Leaf,
+    Node(Box<SerdeTree>),
+}
+
+#[test]
+fn test_serde_max_depth() {
+    // Every enum counts as one level of nesting, so this tree is 4 levels deep
+    let start = SerdeTree::Node(Box::new(SerdeTree::Node(Box::new(SerdeTree::Node(
+        Box::new(SerdeTree::Leaf),
+    )))));
+    let bytes = bincode::serde::encode_to_vec(&start, bincode::config::standard()).unwrap();
+
+    let config = bincode::config::standard().with_max_depth::<4>();
+    let (decoded, _): (SerdeTree, usize) =
+        bincode::serde::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, start);
+    let decoded: SerdeTree = bincode::serde::decode_borrowed_from_slice(&bytes, config).unwrap();
+    assert_eq!(decoded, start);
+
+    let config = bincode::config::standard().with_max_depth::<3>();
+    assert_eq!(
+        bincode::serde::decode_from_slice::<SerdeTree, _>(&bytes, config).unwrap_err(),
+        bincode::error::DecodeError::DepthLimitExceeded
+    );
+    assert_eq!(
+        bincode::serde::decode_borrowed_from_slice::<SerdeTree, _>(&bytes, config).unwrap_err(),
+        bincode::error::DecodeError::DepthLimitExceeded
+    );
+
+    // A deeply nested tree is rejected before it overflows the stack
+    let mut bytes = vec![1u8; 1_000_000];
+    bytes.push(0);
+    let config = bincode::config::standard().with_max_depth::<100>();
+    assert_eq!(
+        bincode::serde::decode_from_slice::<SerdeTree, _>(&bytes, config).unwrap_err(),
+        bincode::error::DecodeError::DepthLimitExceeded
+    );
+}
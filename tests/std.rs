@@ -89,10 +89,10 @@ fn test_std_commons() {
         0,
     )));
     the_same_with_comparer(Mutex::new("Hello world".to_string()), |a, b| {
-        &*a.lock().unwrap() == &*b.lock().unwrap()
+        *a.lock().unwrap() == *b.lock().unwrap()
     });
     the_same_with_comparer(RwLock::new("Hello world".to_string()), |a, b| {
-        &*a.read().unwrap() == &*b.read().unwrap()
+        *a.read().unwrap() == *b.read().unwrap()
     });
 
     let mut map = std::collections::HashMap::new();
@@ -101,15 +101,22 @@ fn test_std_commons() {
     map.insert("you".to_owned(), "doing?".to_owned());
     the_same(map);
 
+    let mut set = std::collections::HashSet::new();
+    set.insert("Hello".to_owned());
+    set.insert("world".to_owned());
+    the_same(set);
+
     // Borrowed values
     let config = bincode::config::standard();
     let mut buffer = [0u8; 1024];
 
     // &CStr
+    // c"..." literals need Rust 1.77
+    #[allow(clippy::manual_c_str_literals)]
     let cstr = CStr::from_bytes_with_nul(b"Hello world\0").unwrap();
     let len = bincode::encode_into_slice(cstr, &mut buffer, config).unwrap();
     let (decoded, len): (&CStr, usize) =
-        bincode::decode_from_slice(&mut buffer[..len], config).unwrap();
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
     assert_eq!(cstr, decoded);
     assert_eq!(len, 13);
 
@@ -117,17 +124,17 @@ fn test_std_commons() {
     let path = Path::new("C:/Program Files/Foo");
     let len = bincode::encode_into_slice(path, &mut buffer, config).unwrap();
     let (decoded, len): (&Path, usize) =
-        bincode::decode_from_slice(&mut buffer[..len], config).unwrap();
+        bincode::decode_from_slice(&buffer[..len], config).unwrap();
     assert_eq!(path, decoded);
     assert_eq!(len, 21);
 }
 
 #[test]
 fn test_system_time_out_of_range() {
-    let mut input = [0xfd, 0x90, 0x0c, 0xfd, 0xfd, 0x90, 0x0c, 0xfd, 0x90, 0x90];
+    let input = [0xfd, 0x90, 0x0c, 0xfd, 0xfd, 0x90, 0x0c, 0xfd, 0x90, 0x90];
 
     let result: Result<(std::time::SystemTime, usize), _> =
-        bincode::decode_from_slice(&mut input, bincode::config::standard());
+        bincode::decode_from_slice(&input, bincode::config::standard());
 
     assert_eq!(
         result.unwrap_err(),
@@ -136,3 +143,84 @@ fn test_system_time_out_of_range() {
         }
     );
 }
+
+/// A simple FNV-1a hasher, to test collections with a custom `BuildHasher`
+#[derive(Default)]
+struct FnvHasher(u64);
+
+impl std::hash::Hasher for FnvHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+type FnvBuildHasher = std::hash::BuildHasherDefault<FnvHasher>;
+
+#[test]
+fn test_custom_hasher() {
+    let mut map = std::collections::HashMap::<u32, String, FnvBuildHasher>::default();
+    map.insert(1, "Hello".to_owned());
+    map.insert(2, "world".to_owned());
+    the_same(map.clone());
+
+    // The hasher does not influence the encoding
+    let config = bincode::config::standard();
+    let default_map: std::collections::HashMap<u32, String> = map.clone().into_iter().collect();
+    let encoded = bincode::encode_to_vec(&map, config).unwrap();
+    let (decoded, len): (std::collections::HashMap<u32, String>, usize) =
+        bincode::decode_from_slice(&encoded, config).unwrap();
+    assert_eq!(decoded, default_map);
+    assert_eq!(len, encoded.len());
+
+    let mut set = std::collections::HashSet::<u32, FnvBuildHasher>::default();
+    set.insert(1);
+    set.insert(1000);
+    set.insert(1_000_000);
+    the_same(set);
+}
+
+#[test]
+fn test_decode_iter_from_std_read() {
+    let config = bincode::config::standard();
+    let mut file = Vec::new();
+    for value in [(1u32, "a"), (300, "bc"), (70000, "")] {
+        bincode::encode_into_std_write(value, &mut file, config).unwrap();
+    }
+
+    let values = bincode::decode_iter_from_std_read::<(u32, String), _, _>(&file[..], config)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        values,
+        [(1, "a".into()), (300, "bc".into()), (70000, String::new())]
+    );
+
+    // An empty reader contains no values
+    let mut iter = bincode::decode_iter_from_std_read::<u32, _, _>(Cursor::new([]), config);
+    assert!(iter.next().is_none());
+
+    // A value that is cut off is an error, after which the iterator stops
+    let mut iter =
+        bincode::decode_iter_from_std_read::<(u32, String), _, _>(&file[..file.len() - 1], config);
+    assert_eq!(iter.next().unwrap().unwrap(), (1, "a".into()));
+    assert_eq!(iter.next().unwrap().unwrap(), (300, "bc".into()));
+    assert_eq!(
+        iter.next().unwrap().unwrap_err(),
+        bincode::error::DecodeError::UnexpectedEnd
+    );
+    assert!(iter.next().is_none());
+
+    // Values that are encoded as 0 bytes are an error instead of skipping bytes
+    let mut iter = bincode::decode_iter_from_std_read::<(), _, _>(&file[..], config);
+    assert!(matches!(
+        iter.next(),
+        Some(Err(bincode::error::DecodeError::Other(_)))
+    ));
+    assert!(iter.next().is_none());
+}
@@ -0,0 +1,174 @@
+#![cfg(feature = "tokio")]
+
+use tokio_incl::io::{duplex, AsyncWriteExt, BufReader};
+
+#[tokio_incl::test(crate = "tokio_incl")]
+async fn test_async_roundtrip() {
+    let config = bincode::config::standard();
+    let (mut client, mut server) = duplex(64);
+
+    let value = (5u32, String::from("Hello world"), vec![1u64, 2, 3]);
+    let len = bincode::encode_into_async_write(&value, &mut client, config)
+        .await
+        .unwrap();
+    assert_eq!(len, bincode::encoded_size(&value, config).unwrap());
+
+    let decoded: (u32, String, Vec<u64>) = bincode::decode_from_async_read(&mut server, config)
+        .await
+        .unwrap();
+    assert_eq!(decoded, value);
+}
+
+#[tokio_incl::test(crate = "tokio_incl")]
+async fn test_async_consecutive_values() {
+    let config = bincode::config::standard();
+    // A small buffer, so the values have to be split over multiple reads
+    let (mut client, mut server) = duplex(7);
+
+    let values: Vec<String> = (0..20).map(|i| "x".repeat(i)).collect();
+    let writer_values = values.clone();
+    let writer = tokio_incl::spawn(async move {
+        for value in &writer_values {
+            bincode::encode_into_async_write(value, &mut client, config)
+                .await
+                .unwrap();
+        }
+    });
+
+    for value in &values {
+        let decoded: String = bincode::decode_from_async_read(&mut server, config)
+            .await
+            .unwrap();
+        assert_eq!(&decoded, value);
+    }
+    writer.await.unwrap();
+}
+
+#[tokio_incl::test(crate = "tokio_incl")]
+async fn test_async_unexpected_end() {
+    let config = bincode::config::standard();
+    let (mut client, mut server) = duplex(64);
+
+    // A string of length 10, but only 3 bytes follow before the stream is closed
+    client.write_all(&[10, b'a', b'b', b'c']).await.unwrap();
+    drop(client);
+
+    let result: Result<String, _> = bincode::decode_from_async_read(&mut server, config).await;
+    assert_eq!(
+        result.unwrap_err(),
+        bincode::error::DecodeError::UnexpectedEnd
+    );
+}
+
+#[cfg(feature = "derive")]
+#[tokio_incl::test(crate = "tokio_incl")]
+async fn test_async_derive_split_over_reads() {
+    #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+    struct Message {
+        id: u32,
+        text: String,
+    }
+
+    let config = bincode::config::standard();
+    let (mut client, mut server) = duplex(64);
+
+    let value = Message {
+        id: 5,
+        text: String::from("Hello world"),
+    };
+    let bytes = bincode::encode_to_vec(&value, config).unwrap();
+    let writer = tokio_incl::spawn(async move {
+        // Errors from the fields of a derived type have a context, which should not stop the decoder from waiting for the rest of the value
+        for chunk in bytes.chunks(3) {
+            client.write_all(chunk).await.unwrap();
+            client.flush().await.unwrap();
+            tokio_incl::task::yield_now().await;
+        }
+    });
+
+    let decoded: Message = bincode::decode_from_async_read(&mut server, config)
+        .await
+        .unwrap();
+    assert_eq!(decoded, value);
+    writer.await.unwrap();
+}
+
+#[tokio_incl::test(crate = "tokio_incl")]
+async fn test_async_large_value() {
+    let config = bincode::config::standard();
+    // A value that is much larger than the buffer of the stream
+    let (mut client, mut server) = duplex(16);
+
+    let value = ("x".repeat(10_000), vec![7u32; 1000]);
+    let writer_value = value.clone();
+    let writer = tokio_incl::spawn(async move {
+        bincode::encode_into_async_write(&writer_value, &mut client, config)
+            .await
+            .unwrap();
+    });
+
+    let decoded: (String, Vec<u32>) = bincode::decode_from_async_read(&mut server, config)
+        .await
+        .unwrap();
+    assert_eq!(decoded, value);
+    writer.await.unwrap();
+}
+
+#[tokio_incl::test(crate = "tokio_incl")]
+async fn test_async_limit() {
+    let config = bincode::config::standard().with_limit::<100>();
+    let (mut client, mut server) = duplex(64);
+
+    // The length of a string that is larger than the limit. The client does not close the stream, so this
+    // should fail without waiting for more bytes.
+    client
+        .write_all(&bincode::encode_to_vec(1000u64, config).unwrap())
+        .await
+        .unwrap();
+
+    let result: Result<String, _> = bincode::decode_from_async_read(&mut server, config).await;
+    assert_eq!(
+        result.unwrap_err(),
+        bincode::error::DecodeError::LimitExceeded
+    );
+    drop(client);
+}
+
+#[tokio_incl::test(crate = "tokio_incl")]
+async fn test_async_default_limit() {
+    let config = bincode::config::standard();
+    let (mut client, mut server) = duplex(64);
+
+    // Without a limit in the config, the bytes that are kept in memory are still limited
+    client
+        .write_all(&bincode::encode_to_vec(1u64 << 30, config).unwrap())
+        .await
+        .unwrap();
+
+    let result: Result<Vec<u8>, _> = bincode::decode_from_async_read(&mut server, config).await;
+    assert_eq!(
+        result.unwrap_err(),
+        bincode::error::DecodeError::LimitExceeded
+    );
+    drop(client);
+}
+
+#[tokio_incl::test(crate = "tokio_incl")]
+async fn test_async_buf_reader() {
+    let config = bincode::config::standard();
+    let (mut client, server) = duplex(256);
+    // A buffered reader works as well, the bytes it has buffered after a value are not lost
+    let mut server = BufReader::new(server);
+
+    for i in 0..10u32 {
+        bincode::encode_into_async_write((i, "x".repeat(i as usize)), &mut client, config)
+            .await
+            .unwrap();
+    }
+    for i in 0..10u32 {
+        let decoded: (u32, String) = bincode::decode_from_async_read(&mut server, config)
+            .await
+            .unwrap();
+        assert_eq!(decoded, (i, "x".repeat(i as usize)));
+    }
+}
@@ -7,24 +7,38 @@ where
     CMP: Fn(&V, &V) -> bool,
 {
     let mut buffer = [0u8; 2048];
-    let len = bincode::encode_into_slice(&element, &mut buffer, config).unwrap();
+    let len = bincode::encode_into_slice(element, &mut buffer, config).unwrap();
     println!(
         "{:?}: {:?} ({:?})",
         element,
         &buffer[..len],
         core::any::type_name::<C>()
     );
-    let (decoded, decoded_len): (V, usize) =
-        bincode::decode_from_slice(&mut buffer, config).unwrap();
+    let (decoded, decoded_len): (V, usize) = bincode::decode_from_slice(&buffer, config).unwrap();
 
     assert!(
-        cmp(&element, &decoded),
+        cmp(element, &decoded),
         "Comparison failed\nDecoded:  {:?}\nExpected: {:?}\nBytes: {:?}",
         decoded,
         element,
         &buffer[..len],
     );
     assert_eq!(len, decoded_len);
+
+    // A `DynConfig` with the same options produces the same bytes
+    let dyn_config = bincode::config::DynConfig::from_config(config);
+    let mut dyn_buffer = [0u8; 2048];
+    let dyn_len = bincode::encode_into_slice(element, &mut dyn_buffer, dyn_config).unwrap();
+    assert_eq!(&buffer[..len], &dyn_buffer[..dyn_len]);
+    let (decoded, decoded_len): (V, usize) =
+        bincode::decode_from_slice(&dyn_buffer, dyn_config).unwrap();
+    assert!(
+        cmp(element, &decoded),
+        "Comparison failed with DynConfig\nDecoded:  {:?}\nExpected: {:?}",
+        decoded,
+        element,
+    );
+    assert_eq!(len, decoded_len);
 }
 
 pub fn the_same_with_comparer<V, CMP>(element: V, cmp: CMP)
@@ -97,6 +111,38 @@ where
             .write_fixed_array_length(),
         &cmp,
     );
+    the_same_with_config(
+        &element,
+        bincode::config::standard()
+            .with_little_endian()
+            .with_leb128_int_encoding()
+            .skip_fixed_array_length(),
+        &cmp,
+    );
+    the_same_with_config(
+        &element,
+        bincode::config::standard()
+            .with_big_endian()
+            .with_leb128_int_encoding()
+            .skip_fixed_array_length(),
+        &cmp,
+    );
+    the_same_with_config(
+        &element,
+        bincode::config::standard()
+            .with_little_endian()
+            .with_leb128_int_encoding()
+            .write_fixed_array_length(),
+        &cmp,
+    );
+    the_same_with_config(
+        &element,
+        bincode::config::standard()
+            .with_big_endian()
+            .with_leb128_int_encoding()
+            .write_fixed_array_length(),
+        &cmp,
+    );
 }
 
 #[allow(dead_code)] // This is not used in every test
@@ -0,0 +1,423 @@
+#![cfg(all(feature = "std", feature = "derive"))]
+
+use bincode::config::Config;
+use bincode::schema::Schema;
+use bincode::value::Value;
+use std::collections::BTreeMap;
+
+/// Encode `value`, decode it as a [Value] and make sure it encodes to the same bytes
+fn roundtrip<T, C>(value: &T, config: C) -> Value
+where
+    T: bincode::Encode + Schema,
+    C: Config,
+{
+    let schema = bincode::schema_of::<T, _>(config);
+    let bytes = bincode::encode_to_vec(value, config).unwrap();
+    let (decoded, len) = bincode::value::decode_from_slice(&bytes, &schema, config).unwrap();
+    assert_eq!(len, bytes.len());
+    assert_eq!(
+        bincode::value::encode_to_vec(&decoded, &schema, config).unwrap(),
+        bytes
+    );
+    decoded
+}
+
+fn field(name: &str, value: Value) -> (String, Value) {
+    (name.to_string(), value)
+}
+
+#[derive(bincode::Encode, bincode::Schema)]
+struct Everything {
+    unit: (),
+    flags: (bool, char),
+    small: (u8, i8, u16, i16),
+    large: (u32, i32, u64, i64, u128, i128),
+    floats: (f32, f64),
+    name: String,
+    bytes: Vec<u8>,
+    array: [u16; 3],
+    byte_array: [u8; 2],
+    option: Option<Box<Everything>>,
+    map: BTreeMap<u32, Vec<Shape>>,
+}
+
+#[derive(bincode::Encode, bincode::Schema)]
+#[bincode(tag_type = "u8")]
+enum Shape {
+    Empty,
+    Circle(f32),
+    Rect { width: u32, height: u32 },
+}
+
+fn everything(option: Option<Box<Everything>>) -> Everything {
+    Everything {
+        unit: (),
+        flags: (true, 'ß'),
+        small: (255, -1, 1000, -1000),
+        large: (u32::MAX, i32::MIN, 300, -300, u128::MAX, i128::MIN + 1),
+        floats: (1.5, -0.25),
+        name: "Hello".to_string(),
+        bytes: vec![1, 2, 3],
+        array: [1, 500, 4464],
+        byte_array: [9, 8],
+        option,
+        map: BTreeMap::from([
+            (1, vec![Shape::Empty, Shape::Circle(2.0)]),
+            (
+                1000,
+                vec![Shape::Rect {
+                    width: 3,
+                    height: 4,
+                }],
+            ),
+        ]),
+    }
+}
+
+#[test]
+fn test_value_roundtrip() {
+    let value = everything(Some(Box::new(everything(None))));
+    let configs: [fn(&Everything) -> Value; 3] = [
+        |value| roundtrip(value, bincode::config::standard()),
+        |value| roundtrip(value, bincode::config::legacy()),
+        |value| {
+            roundtrip(
+                value,
+                bincode::config::standard()
+                    .with_big_endian()
+                    .with_fixed_int_encoding(),
+            )
+        },
+    ];
+    for roundtrip in configs {
+        let decoded = roundtrip(&value);
+        let fields = match decoded {
+            Value::Struct(fields) => fields,
+            other => panic!("Expected a struct, found {:?}", other),
+        };
+        let names: Vec<&str> = fields.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "unit",
+                "flags",
+                "small",
+                "large",
+                "floats",
+                "name",
+                "bytes",
+                "array",
+                "byte_array",
+                "option",
+                "map"
+            ]
+        );
+        assert_eq!(fields[0].1, Value::Unit);
+        assert_eq!(
+            fields[2].1,
+            Value::Seq(vec![
+                Value::U8(255),
+                Value::I8(-1),
+                Value::U16(1000),
+                Value::I16(-1000)
+            ])
+        );
+        assert_eq!(fields[5].1, Value::String("Hello".to_string()));
+        assert_eq!(fields[6].1, Value::Bytes(vec![1, 2, 3]));
+        assert_eq!(
+            fields[7].1,
+            Value::Seq(vec![Value::U16(1), Value::U16(500), Value::U16(4464)])
+        );
+        assert_eq!(fields[8].1, Value::Bytes(vec![9, 8]));
+        assert!(
+            matches!(&fields[9].1, Value::Option(Some(inner)) if matches!(**inner, Value::Struct(_)))
+        );
+        assert_eq!(
+            fields[10].1,
+            Value::Map(vec![
+                (
+                    Value::U32(1),
+                    Value::Seq(vec![
+                        Value::Enum {
+                            variant: "Empty".to_string(),
+                            fields: vec![]
+                        },
+                        Value::Enum {
+                            variant: "Circle".to_string(),
+                            fields: vec![field("0", Value::F32(2.0))]
+                        },
+                    ])
+                ),
+                (
+                    Value::U32(1000),
+                    Value::Seq(vec![Value::Enum {
+                        variant: "Rect".to_string(),
+                        fields: vec![
+                            field("width", Value::U32(3)),
+                            field("height", Value::U32(4))
+                        ]
+                    }])
+                ),
+            ])
+        );
+    }
+}
+
+mod v1 {
+    #[derive(bincode::Encode, bincode::Schema)]
+    #[bincode(default_trailing)]
+    pub struct Trailing {
+        pub a: u8,
+    }
+
+    #[derive(bincode::Encode, bincode::Schema)]
+    #[bincode(tagged, version = 2)]
+    pub struct Tagged {
+        #[bincode(tag = 1)]
+        pub a: u32,
+    }
+}
+
+mod v2 {
+    #[derive(bincode::Encode, bincode::Schema)]
+    #[bincode(default_trailing)]
+    pub struct Trailing {
+        pub a: u8,
+        pub b: String,
+    }
+
+    #[derive(bincode::Encode, bincode::Schema)]
+    #[bincode(tagged, version = 2)]
+    pub struct Tagged {
+        #[bincode(tag = 3)]
+        pub c: String,
+        #[bincode(tag = 1)]
+        pub a: u32,
+    }
+}
+
+#[test]
+fn test_value_schema_evolution() {
+    let config = bincode::config::standard();
+    let old_trailing = bincode::schema_of::<v1::Trailing, _>(config);
+    let new_trailing = bincode::schema_of::<v2::Trailing, _>(config);
+
+    // Fields that were added after the schema are kept as raw bytes
+    let bytes = bincode::encode_to_vec(
+        v2::Trailing {
+            a: 1,
+            b: "new".to_string(),
+        },
+        config,
+    )
+    .unwrap();
+    let (value, _) = bincode::value::decode_from_slice(&bytes, &old_trailing, config).unwrap();
+    assert_eq!(
+        value,
+        Value::Struct(vec![
+            field("a", Value::U8(1)),
+            field("..", Value::Raw(vec![3, b'n', b'e', b'w'])),
+        ])
+    );
+    assert_eq!(
+        bincode::value::encode_to_vec(&value, &old_trailing, config).unwrap(),
+        bytes
+    );
+
+    // Fields that were added after the data was written are left out
+    let bytes = bincode::encode_to_vec(v1::Trailing { a: 1 }, config).unwrap();
+    let (value, _) = bincode::value::decode_from_slice(&bytes, &new_trailing, config).unwrap();
+    assert_eq!(value, Value::Struct(vec![field("a", Value::U8(1))]));
+    assert_eq!(
+        bincode::value::encode_to_vec(&value, &new_trailing, config).unwrap(),
+        bytes
+    );
+
+    // Entries with an unknown tag are kept as raw bytes, in their original order
+    let old_tagged = bincode::schema_of::<v1::Tagged, _>(config);
+    let bytes = bincode::encode_to_vec(
+        v2::Tagged {
+            c: "c".to_string(),
+            a: 300,
+        },
+        config,
+    )
+    .unwrap();
+    let (value, _) = bincode::value::decode_from_slice(&bytes, &old_tagged, config).unwrap();
+    assert_eq!(
+        value,
+        Value::Struct(vec![
+            field("#3", Value::Raw(vec![1, b'c'])),
+            field("a", Value::U32(300)),
+        ])
+    );
+    assert_eq!(
+        bincode::value::encode_to_vec(&value, &old_tagged, config).unwrap(),
+        bytes
+    );
+
+    // Only the version in the schema can be decoded
+    let mut wrong_version = bytes.clone();
+    wrong_version[0] = 1;
+    assert!(matches!(
+        bincode::value::decode_from_slice(&wrong_version, &old_tagged, config).unwrap_err(),
+        bincode::error::DecodeError::OtherString(_)
+    ));
+}
+
+#[derive(bincode::Encode, bincode::Schema)]
+struct Tree {
+    value: i32,
+    children: Vec<Tree>,
+}
+
+#[test]
+fn test_value_recursive() {
+    let tree = Tree {
+        value: -1,
+        children: vec![Tree {
+            value: 2,
+            children: vec![],
+        }],
+    };
+    assert_eq!(
+        roundtrip(&tree, bincode::config::standard()),
+        Value::Struct(vec![
+            field("value", Value::I32(-1)),
+            field(
+                "children",
+                Value::Seq(vec![Value::Struct(vec![
+                    field("value", Value::I32(2)),
+                    field("children", Value::Seq(vec![])),
+                ])])
+            ),
+        ])
+    );
+
+    // Every struct counts towards the max depth
+    let config = bincode::config::standard().with_max_depth::<1>();
+    let schema = bincode::schema_of::<Tree, _>(config);
+    let bytes = bincode::encode_to_vec(&tree, config).unwrap();
+    assert!(matches!(
+        bincode::value::decode_from_slice(&bytes, &schema, config).unwrap_err(),
+        bincode::error::DecodeError::DepthLimitExceeded
+    ));
+}
+
+#[derive(bincode::Encode, bincode::Schema)]
+#[bincode(tagged)]
+struct Chain {
+    #[bincode(tag = 1)]
+    next: Option<Box<Chain>>,
+}
+
+#[test]
+fn test_value_nested_records() {
+    // Records have to be sized before they are encoded, nested records should not make this exponential
+    let mut chain = Chain { next: None };
+    for _ in 0..64 {
+        chain = Chain {
+            next: Some(Box::new(chain)),
+        };
+    }
+    roundtrip(&chain, bincode::config::standard());
+}
+
+fn encode_as_u8<E: bincode::enc::Encoder>(
+    value: &u32,
+    encoder: &mut E,
+) -> Result<(), bincode::error::EncodeError> {
+    bincode::Encode::encode(&(*value as u8), encoder)
+}
+
+#[derive(bincode::Encode, bincode::Schema)]
+struct Custom {
+    #[bincode(encode_with = "encode_as_u8")]
+    value: u32,
+}
+
+#[test]
+fn test_value_errors() {
+    let config = bincode::config::standard();
+
+    // Types with a custom encoding can not be decoded
+    let schema = bincode::schema_of::<Custom, _>(config);
+    let bytes = bincode::encode_to_vec(Custom { value: 5 }, config).unwrap();
+    assert!(matches!(
+        bincode::value::decode_from_slice(&bytes, &schema, config).unwrap_err(),
+        bincode::error::DecodeError::OtherString(_)
+    ));
+
+    // Values that do not match the schema can not be encoded
+    let schema = bincode::schema_of::<(u8, String), _>(config);
+    for value in [
+        Value::Seq(vec![Value::U16(1), Value::String("a".to_string())]),
+        Value::Seq(vec![Value::U8(1)]),
+        Value::Struct(vec![]),
+    ] {
+        assert!(matches!(
+            bincode::value::encode_to_vec(&value, &schema, config).unwrap_err(),
+            bincode::error::EncodeError::OtherString(_)
+        ));
+    }
+
+    let schema = bincode::schema_of::<Shape, _>(config);
+    let value = Value::Enum {
+        variant: "Triangle".to_string(),
+        fields: vec![],
+    };
+    assert!(matches!(
+        bincode::value::encode_to_vec(&value, &schema, config).unwrap_err(),
+        bincode::error::EncodeError::OtherString(_)
+    ));
+    // Unknown variant index
+    assert!(matches!(
+        bincode::value::decode_from_slice(&[3], &schema, config).unwrap_err(),
+        bincode::error::DecodeError::OtherString(_)
+    ));
+
+    // A record length that is larger than the input should not be allocated up front
+    let schema = bincode::schema_of::<v1::Trailing, _>(config);
+    assert!(matches!(
+        bincode::value::decode_from_slice(&[253, 0, 0, 0, 0, 0, 1, 0, 0, 1], &schema, config)
+            .unwrap_err(),
+        bincode::error::DecodeError::UnexpectedEnd
+    ));
+}
+
+#[test]
+fn test_value_spans() {
+    let config = bincode::config::standard();
+    let schema = bincode::schema_of::<(BTreeMap<u8, Shape>, v1::Trailing), _>(config);
+    let value = (
+        BTreeMap::from([(7u8, Shape::Circle(1.0))]),
+        v1::Trailing { a: 2 },
+    );
+    let bytes = bincode::encode_to_vec(&value, config).unwrap();
+    let mut decoder =
+        bincode::de::DecoderImpl::new(bincode::de::read::SliceReader::new(&bytes), config);
+    let (decoded, spans) = bincode::value::decode_with_spans(&mut decoder, &schema).unwrap();
+    assert_eq!(
+        decoded,
+        bincode::value::decode_from_slice(&bytes, &schema, config)
+            .unwrap()
+            .0
+    );
+
+    let spans: Vec<(&str, std::ops::Range<usize>)> = spans
+        .iter()
+        .map(|span| (span.path.as_str(), span.range.clone()))
+        .collect();
+    assert_eq!(
+        spans,
+        [
+            ("", 0..9),
+            (".0", 0..7),
+            (".0[0].key", 1..2),
+            (".0[0].value", 2..7),
+            (".0[0].value::Circle.0", 3..7),
+            (".1", 7..9),
+            (".1.a", 8..9),
+        ]
+    );
+}